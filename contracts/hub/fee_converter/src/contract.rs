@@ -0,0 +1,113 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    ensure, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use euclid::error::ContractError;
+use euclid::msgs::fee_converter::{Config, ExecuteMsg, InstantiateMsg, QueryMsg};
+use euclid::msgs::router::{QueryMsg as RouterQueryMsg, StateResponse};
+
+use crate::reply::{self, CONVERT_REPLY_ID};
+use crate::state::{CONFIG, TOTAL_CONVERTED};
+use crate::{execute, query};
+
+// version info for migration info
+pub(crate) const CONTRACT_NAME: &str = "crates.io:fee_converter";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    ensure!(
+        msg.max_slippage_bps.le(&10_000),
+        ContractError::InvalidSlippageTolerance {}
+    );
+    let router = deps.api.addr_validate(&msg.router)?;
+    let router_state: StateResponse = deps
+        .querier
+        .query_wasm_smart(router.clone(), &RouterQueryMsg::GetState {})?;
+    let virtual_balance = router_state
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?;
+    let keepers = msg
+        .keepers
+        .iter()
+        .map(|keeper| deps.api.addr_validate(keeper))
+        .collect::<StdResult<_>>()?;
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+
+    let config = Config {
+        router,
+        virtual_balance,
+        target_token: msg.target_token,
+        recipient: msg.recipient,
+        max_slippage_bps: msg.max_slippage_bps,
+        route_tokens: msg.route_tokens,
+        keepers,
+        admin,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL_CONVERTED.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("fee_converter_address", env.contract.address)
+        .add_attribute("target_token", config.target_token.to_string())
+        .add_attribute("admin", config.admin))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Convert { token, amount } => {
+            execute::execute_convert(deps, env, info, token, amount)
+        }
+        ExecuteMsg::UpdateConfig {
+            recipient,
+            max_slippage_bps,
+            route_tokens,
+            keepers,
+        } => execute::execute_update_config(
+            deps,
+            info,
+            recipient,
+            max_slippage_bps,
+            route_tokens,
+            keepers,
+        ),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::GetConfig {} => query::query_config(deps),
+        QueryMsg::GetPendingFees {} => query::query_pending_fees(deps, env),
+        QueryMsg::GetConversions { pagination } => query::query_conversions(deps, pagination),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CONVERT_REPLY_ID => reply::on_convert_reply(deps, env),
+        id => Err(ContractError::Generic {
+            err: format!("Unknown reply id: {id}"),
+        }),
+    }
+}
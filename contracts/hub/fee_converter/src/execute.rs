@@ -0,0 +1,188 @@
+use cosmwasm_std::{
+    ensure, to_json_binary, Decimal, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+    SubMsg, Uint128, WasmMsg,
+};
+use euclid::{
+    chain::{ChainUid, CrossChainUser},
+    error::ContractError,
+    msgs::{
+        fee_converter::Conversion,
+        router::{QueryMsg as RouterQueryMsg, QuerySimulateSwap, SimulateSwapResponse},
+        vlp::{GetLiquidityResponse, QueryMsg as VlpQueryMsg},
+    },
+    token::Token,
+};
+
+use crate::{
+    query::{find_route, query_virtual_balance},
+    reply::CONVERT_REPLY_ID,
+    state::{PendingConversion, CONFIG, CONVERSIONS, PENDING_CONVERSION, TOTAL_CONVERTED},
+};
+
+pub fn execute_convert(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Token,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        !PENDING_CONVERSION.exists(deps.storage),
+        ContractError::new("Conversion in progress")
+    );
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin || config.keepers.contains(&info.sender),
+        ContractError::Unauthorized {}
+    );
+    let converter = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: env.contract.address.to_string(),
+    };
+
+    let balance = query_virtual_balance(deps.as_ref(), &config, &converter, &token)?;
+    let amount_in = amount.unwrap_or(balance);
+    ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
+    ensure!(amount_in.le(&balance), ContractError::InsufficientFunds {});
+
+    // Fees already paid in the target token only need to be forwarded
+    if token == config.target_token {
+        record_conversion(deps.storage, &token, amount_in, amount_in)?;
+        let transfer_msg = token.create_virtual_balance_transfer_msg(
+            config.virtual_balance.into_string(),
+            amount_in,
+            converter,
+            config.recipient,
+        )?;
+        return Ok(Response::new()
+            .add_attribute("method", "convert")
+            .add_attribute("token", token.to_string())
+            .add_attribute("amount_in", amount_in)
+            .add_attribute("amount_out", amount_in)
+            .add_message(transfer_msg));
+    }
+
+    let route = find_route(deps.as_ref(), &config, &token)?;
+
+    // Output at the current pool prices without price impact or fees
+    let mut spot_amount_out = amount_in;
+    for (swap, vlp) in &route {
+        let liquidity: GetLiquidityResponse = deps
+            .querier
+            .query_wasm_smart(vlp, &VlpQueryMsg::Liquidity {})?;
+        let (reserve_in, reserve_out) = if liquidity.pair.token_1 == swap.token_in {
+            (liquidity.token_1_reserve, liquidity.token_2_reserve)
+        } else {
+            (liquidity.token_2_reserve, liquidity.token_1_reserve)
+        };
+        ensure!(
+            !reserve_in.is_zero(),
+            ContractError::new("Route has no liquidity")
+        );
+        spot_amount_out = spot_amount_out.checked_multiply_ratio(reserve_out, reserve_in)?;
+    }
+    let min_amount_out =
+        spot_amount_out.checked_mul_ceil(Decimal::bps(10_000 - config.max_slippage_bps))?;
+
+    let swaps: Vec<_> = route.into_iter().map(|(swap, _)| swap).collect();
+    let simulation: SimulateSwapResponse = deps.querier.query_wasm_smart(
+        config.router.clone(),
+        &RouterQueryMsg::SimulateSwap(QuerySimulateSwap {
+            asset_in: token.clone(),
+            amount_in,
+            asset_out: config.target_token.clone(),
+            min_amount_out,
+            swaps: swaps.clone(),
+        }),
+    )?;
+    ensure!(
+        simulation.amount_out.ge(&min_amount_out),
+        ContractError::SlippageExceeded {
+            amount: simulation.amount_out,
+            min_amount_out,
+        }
+    );
+
+    let target_balance =
+        query_virtual_balance(deps.as_ref(), &config, &converter, &config.target_token)?;
+    PENDING_CONVERSION.save(
+        deps.storage,
+        &PendingConversion {
+            token: token.clone(),
+            amount_in,
+            target_balance,
+        },
+    )?;
+
+    // Router swaps from the converter's virtual balance and credits the output back to it
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: config.router.into_string(),
+        msg: to_json_binary(&euclid::msgs::router::ExecuteMsg::VslSwap {
+            asset_in: token.clone(),
+            amount_in,
+            asset_out: config.target_token,
+            min_amount_out,
+            swaps,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "convert")
+        .add_attribute("token", token.to_string())
+        .add_attribute("amount_in", amount_in)
+        .add_attribute("min_amount_out", min_amount_out)
+        .add_submessage(SubMsg::reply_on_success(swap_msg, CONVERT_REPLY_ID)))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: Option<CrossChainUser>,
+    max_slippage_bps: Option<u64>,
+    route_tokens: Option<Vec<Token>>,
+    keepers: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure!(info.sender == config.admin, ContractError::Unauthorized {});
+
+    config.recipient = recipient.unwrap_or(config.recipient);
+    config.max_slippage_bps = max_slippage_bps.unwrap_or(config.max_slippage_bps);
+    ensure!(
+        config.max_slippage_bps.le(&10_000),
+        ContractError::InvalidSlippageTolerance {}
+    );
+    config.route_tokens = route_tokens.unwrap_or(config.route_tokens);
+    if let Some(keepers) = keepers {
+        config.keepers = keepers
+            .iter()
+            .map(|keeper| deps.api.addr_validate(keeper))
+            .collect::<StdResult<_>>()?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+pub fn record_conversion(
+    storage: &mut dyn Storage,
+    token: &Token,
+    amount_in: Uint128,
+    amount_out: Uint128,
+) -> Result<(), ContractError> {
+    let mut conversion = CONVERSIONS
+        .may_load(storage, token.clone())?
+        .unwrap_or(Conversion {
+            amount_in: Uint128::zero(),
+            amount_out: Uint128::zero(),
+            conversions: 0,
+        });
+    conversion.amount_in = conversion.amount_in.checked_add(amount_in)?;
+    conversion.amount_out = conversion.amount_out.checked_add(amount_out)?;
+    conversion.conversions += 1;
+    CONVERSIONS.save(storage, token.clone(), &conversion)?;
+
+    let total_converted = TOTAL_CONVERTED.may_load(storage)?.unwrap_or_default();
+    TOTAL_CONVERTED.save(storage, &total_converted.checked_add(amount_out)?)?;
+    Ok(())
+}
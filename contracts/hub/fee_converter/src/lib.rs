@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod execute;
+pub mod migrate;
+pub mod query;
+pub mod reply;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
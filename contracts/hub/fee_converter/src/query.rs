@@ -0,0 +1,155 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, Uint128};
+use cw_storage_plus::Bound;
+use euclid::{
+    chain::{ChainUid, CrossChainUser},
+    error::ContractError,
+    msgs::{
+        fee_converter::{
+            Config, ConfigResponse, ConversionResponse, ConversionsResponse, PendingFee,
+            PendingFeesResponse,
+        },
+        router::{QueryMsg as RouterQueryMsg, VlpResponse},
+        virtual_balance::{
+            GetBalanceResponse, GetUserBalancesResponse, QueryMsg as VirtualBalanceQueryMsg,
+        },
+    },
+    swap::NextSwapPair,
+    token::{Pair, Token},
+    utils::Pagination,
+    virtual_balance::BalanceKey,
+};
+
+use crate::state::{CONFIG, CONVERSIONS, TOTAL_CONVERTED};
+
+pub fn query_config(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_json_binary(&ConfigResponse {
+        config: CONFIG.load(deps.storage)?,
+    })?)
+}
+
+pub fn query_pending_fees(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let balances: GetUserBalancesResponse = deps.querier.query_wasm_smart(
+        config.virtual_balance,
+        &VirtualBalanceQueryMsg::GetUserBalances {
+            user: CrossChainUser {
+                chain_uid: ChainUid::vsl_chain_uid()?,
+                address: env.contract.address.to_string(),
+            },
+        },
+    )?;
+
+    let fees: Result<_, ContractError> = balances
+        .balances
+        .into_iter()
+        .filter(|balance| !balance.amount.is_zero())
+        .map(|balance| {
+            Ok(PendingFee {
+                token: Token::create(balance.token_id)?,
+                amount: balance.amount,
+            })
+        })
+        .collect();
+
+    Ok(to_json_binary(&PendingFeesResponse { fees: fees? })?)
+}
+
+pub fn query_conversions(
+    deps: Deps,
+    pagination: Pagination<Token>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let conversions: Result<_, ContractError> = CONVERSIONS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|item| {
+            let (token, conversion) = item?;
+            Ok(ConversionResponse { token, conversion })
+        })
+        .collect();
+
+    Ok(to_json_binary(&ConversionsResponse {
+        conversions: conversions?,
+        total_converted: TOTAL_CONVERTED.may_load(deps.storage)?.unwrap_or_default(),
+    })?)
+}
+
+pub fn query_virtual_balance(
+    deps: Deps,
+    config: &Config,
+    user: &CrossChainUser,
+    token: &Token,
+) -> Result<Uint128, ContractError> {
+    let balance: GetBalanceResponse = deps.querier.query_wasm_smart(
+        config.virtual_balance.clone(),
+        &VirtualBalanceQueryMsg::GetBalance {
+            balance_key: BalanceKey {
+                cross_chain_user: user.clone(),
+                token_id: token.to_string(),
+            },
+        },
+    )?;
+    Ok(balance.amount)
+}
+
+// Swaps from the token to the target token with the vlp of each swap. Uses the direct pool when the
+// router has one, otherwise the first route token that has a pool with both tokens
+pub fn find_route(
+    deps: Deps,
+    config: &Config,
+    token: &Token,
+) -> Result<Vec<(NextSwapPair, String)>, ContractError> {
+    let target_token = &config.target_token;
+    if let Some(vlp) = query_vlp(deps, config, token, target_token) {
+        return Ok(vec![(swap_pair(token, target_token), vlp)]);
+    }
+    for route_token in &config.route_tokens {
+        if route_token == token || route_token == target_token {
+            continue;
+        }
+        let (Some(first_vlp), Some(second_vlp)) = (
+            query_vlp(deps, config, token, route_token),
+            query_vlp(deps, config, route_token, target_token),
+        ) else {
+            continue;
+        };
+        return Ok(vec![
+            (swap_pair(token, route_token), first_vlp),
+            (swap_pair(route_token, target_token), second_vlp),
+        ]);
+    }
+    Err(ContractError::new("No route to the target token"))
+}
+
+fn query_vlp(deps: Deps, config: &Config, token_1: &Token, token_2: &Token) -> Option<String> {
+    deps.querier
+        .query_wasm_smart::<VlpResponse>(
+            config.router.clone(),
+            &RouterQueryMsg::GetVlp {
+                pair: Pair {
+                    token_1: token_1.clone(),
+                    token_2: token_2.clone(),
+                },
+            },
+        )
+        .ok()
+        .map(|vlp| vlp.vlp)
+}
+
+fn swap_pair(token_in: &Token, token_out: &Token) -> NextSwapPair {
+    NextSwapPair {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        test_fail: None,
+    }
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::{DepsMut, Env, Response};
+use euclid::{
+    chain::{ChainUid, CrossChainUser},
+    error::ContractError,
+};
+
+use crate::{
+    execute::record_conversion,
+    query::query_virtual_balance,
+    state::{CONFIG, PENDING_CONVERSION},
+};
+
+pub const CONVERT_REPLY_ID: u64 = 1;
+
+pub fn on_convert_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let pending = PENDING_CONVERSION.load(deps.storage)?;
+    PENDING_CONVERSION.remove(deps.storage);
+    let config = CONFIG.load(deps.storage)?;
+
+    let converter = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: env.contract.address.to_string(),
+    };
+    let target_balance =
+        query_virtual_balance(deps.as_ref(), &config, &converter, &config.target_token)?;
+    let amount_out = target_balance.checked_sub(pending.target_balance)?;
+    record_conversion(deps.storage, &pending.token, pending.amount_in, amount_out)?;
+
+    let transfer_msg = config.target_token.create_virtual_balance_transfer_msg(
+        config.virtual_balance.into_string(),
+        amount_out,
+        converter,
+        config.recipient,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reply_convert")
+        .add_attribute("token", pending.token.to_string())
+        .add_attribute("amount_in", pending.amount_in)
+        .add_attribute("amount_out", amount_out)
+        .add_message(transfer_msg))
+}
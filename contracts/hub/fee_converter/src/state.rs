@@ -0,0 +1,26 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+use cw_storage_plus::{Item, Map};
+use euclid::{
+    msgs::fee_converter::{Config, Conversion},
+    token::Token,
+};
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// Converted amounts for each token
+pub const CONVERSIONS: Map<Token, Conversion> = Map::new("conversions");
+
+// Target token sent to the recipient over all conversions
+pub const TOTAL_CONVERTED: Item<Uint128> = Item::new("total_converted");
+
+#[cw_serde]
+pub struct PendingConversion {
+    pub token: Token,
+    pub amount_in: Uint128,
+    // Target token balance before the swap, the swap output is whatever was added to it
+    pub target_balance: Uint128,
+}
+
+// Conversion waiting for its swap reply
+pub const PENDING_CONVERSION: Item<PendingConversion> = Item::new("pending_conversion");
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    use crate::contract::{execute, instantiate, query, reply};
+    use crate::reply::CONVERT_REPLY_ID;
+    use crate::state::PENDING_CONVERSION;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult, CosmosMsg, Decimal, Empty, OwnedDeps,
+        Reply, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128, WasmMsg,
+        WasmQuery,
+    };
+    use euclid::chain::{ChainUid, CrossChainUser};
+    use euclid::error::ContractError;
+    use euclid::msgs::fee_converter::{
+        ConversionsResponse, ExecuteMsg, InstantiateMsg, PendingFeesResponse, QueryMsg,
+    };
+    use euclid::msgs::router::{
+        ExecuteMsg as RouterExecuteMsg, QueryMsg as RouterQueryMsg, SimulateSwapResponse,
+        StateResponse, VlpResponse,
+    };
+    use euclid::msgs::virtual_balance::{
+        ExecuteMsg as VirtualBalanceExecuteMsg, GetBalanceResponse, GetUserBalancesResponse,
+        GetUserBalancesResponseItem, QueryMsg as VirtualBalanceQueryMsg,
+    };
+    use euclid::msgs::vlp::{GetLiquidityResponse, QueryMsg as VlpQueryMsg};
+    use euclid::token::{Pair, Token};
+    use euclid::utils::Pagination;
+
+    type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>;
+
+    fn token(id: &str) -> Token {
+        Token::create(id.to_string()).unwrap()
+    }
+
+    // Router knows fee/eth and eth/usdc pools, usdc is worth half an eth
+    fn mock_queries(deps: &mut MockDeps, simulated_out: u128, usdc_balance: u128) {
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { contract_addr, msg } = query else {
+                return SystemResult::Err(SystemError::Unknown {});
+            };
+            let response = match contract_addr.as_str() {
+                "router" => match from_json(msg).unwrap() {
+                    RouterQueryMsg::GetState {} => to_json_binary(&StateResponse {
+                        admin: "admin".to_string(),
+                        vlp_code_id: 1,
+                        virtual_balance_address: Some(Addr::unchecked("vb")),
+                        locked: false,
+                    }),
+                    RouterQueryMsg::GetVlp { pair } => {
+                        let vlp = match pair.get_tupple() {
+                            (token_1, token_2)
+                                if token_1 == token("eth") && token_2 == token("fee") =>
+                            {
+                                "vlp_a"
+                            }
+                            (token_1, token_2)
+                                if token_1 == token("eth") && token_2 == token("usdc") =>
+                            {
+                                "vlp_b"
+                            }
+                            _ => {
+                                return SystemResult::Ok(ContractResult::Err(
+                                    "not found".to_string(),
+                                ))
+                            }
+                        };
+                        to_json_binary(&VlpResponse {
+                            vlp: vlp.to_string(),
+                            token_1: pair.token_1,
+                            token_2: pair.token_2,
+                            metadata: None,
+                        })
+                    }
+                    RouterQueryMsg::SimulateSwap(_) => to_json_binary(&SimulateSwapResponse {
+                        amount_out: Uint128::new(simulated_out),
+                        asset_out: token("usdc"),
+                        hops: vec![],
+                        execution_price: Decimal::zero(),
+                        price_impact_bps: 0,
+                        lp_fee_bps: 0,
+                        euclid_fee_bps: 0,
+                    }),
+                    _ => return SystemResult::Err(SystemError::Unknown {}),
+                },
+                "vlp_a" | "vlp_b" => {
+                    let VlpQueryMsg::Liquidity {} = from_json(msg).unwrap() else {
+                        return SystemResult::Err(SystemError::Unknown {});
+                    };
+                    let (pair, token_1_reserve, token_2_reserve) = if contract_addr == "vlp_a" {
+                        (("fee", "eth"), 1000, 1000)
+                    } else {
+                        (("eth", "usdc"), 1000, 2000)
+                    };
+                    to_json_binary(&GetLiquidityResponse {
+                        pair: Pair {
+                            token_1: token(pair.0),
+                            token_2: token(pair.1),
+                        },
+                        token_1_reserve: Uint128::new(token_1_reserve),
+                        token_2_reserve: Uint128::new(token_2_reserve),
+                        total_lp_tokens: Uint128::new(1000),
+                        locked_lp_tokens: Uint128::zero(),
+                    })
+                }
+                "vb" => match from_json(msg).unwrap() {
+                    VirtualBalanceQueryMsg::GetBalance { balance_key } => {
+                        let amount = if balance_key.token_id == "usdc" {
+                            usdc_balance
+                        } else {
+                            100
+                        };
+                        to_json_binary(&GetBalanceResponse {
+                            amount: Uint128::new(amount),
+                        })
+                    }
+                    VirtualBalanceQueryMsg::GetUserBalances { .. } => {
+                        to_json_binary(&GetUserBalancesResponse {
+                            balances: vec![
+                                GetUserBalancesResponseItem {
+                                    amount: Uint128::new(100),
+                                    token_id: "fee".to_string(),
+                                },
+                                GetUserBalancesResponseItem {
+                                    amount: Uint128::new(usdc_balance),
+                                    token_id: "usdc".to_string(),
+                                },
+                            ],
+                        })
+                    }
+                    _ => return SystemResult::Err(SystemError::Unknown {}),
+                },
+                _ => return SystemResult::Err(SystemError::Unknown {}),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        });
+    }
+
+    fn init(deps: &mut MockDeps) {
+        let msg = InstantiateMsg {
+            router: "router".to_string(),
+            target_token: token("usdc"),
+            recipient: CrossChainUser {
+                chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                address: "treasury".to_string(),
+            },
+            max_slippage_bps: 2000,
+            route_tokens: vec![token("eth")],
+            keepers: vec!["keeper".to_string()],
+            admin: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_convert() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        mock_queries(&mut deps, 150, 0);
+        init(&mut deps);
+
+        let pending: PendingFeesResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetPendingFees {}).unwrap())
+                .unwrap();
+        assert_eq!(pending.fees.len(), 1);
+        assert_eq!(pending.fees[0].token, token("fee"));
+
+        let convert_msg = ExecuteMsg::Convert {
+            token: token("fee"),
+            amount: None,
+        };
+        // Conversions are priced off same block spot reserves, so only trusted callers can trigger them
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            convert_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Spot output through eth is 200 usdc, the route can lose at most 20%
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            convert_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SlippageExceeded {
+                amount: Uint128::new(150),
+                min_amount_out: Uint128::new(160),
+            }
+        );
+
+        mock_queries(&mut deps, 180, 0);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            convert_msg,
+        )
+        .unwrap();
+        assert_eq!(res.messages[0].id, CONVERT_REPLY_ID);
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[0].msg else {
+            panic!("Unexpected message");
+        };
+        let RouterExecuteMsg::VslSwap {
+            amount_in,
+            min_amount_out,
+            swaps,
+            ..
+        } = from_json(msg).unwrap()
+        else {
+            panic!("Unexpected message");
+        };
+        assert_eq!(amount_in, Uint128::new(100));
+        assert_eq!(min_amount_out, Uint128::new(160));
+        assert_eq!(swaps.len(), 2);
+        assert_eq!(swaps[0].token_out, token("eth"));
+
+        // Output credited to the converter is forwarded to the recipient
+        mock_queries(&mut deps, 180, 185);
+        let res = reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: CONVERT_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+        assert!(!PENDING_CONVERSION.exists(&deps.storage));
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[0].msg else {
+            panic!("Unexpected message");
+        };
+        let VirtualBalanceExecuteMsg::Transfer(transfer) = from_json(msg).unwrap() else {
+            panic!("Unexpected message");
+        };
+        assert_eq!(transfer.amount, Uint128::new(185));
+        assert_eq!(transfer.to.address, "treasury");
+
+        let conversions: ConversionsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::GetConversions {
+                    pagination: Pagination::new(None, None, None, None),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(conversions.total_converted, Uint128::new(185));
+        assert_eq!(conversions.conversions[0].token, token("fee"));
+        assert_eq!(
+            conversions.conversions[0].conversion.amount_in,
+            Uint128::new(100)
+        );
+    }
+}
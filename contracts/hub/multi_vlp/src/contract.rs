@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, Uint128};
+use cw2::set_contract_version;
+use euclid::fee::{DenomFees, TotalFees};
+use euclid::pool::sort_pool_tokens;
+
+use crate::reply::{NEXT_SWAP_REPLY_ID, VIRTUAL_BALANCE_TRANSFER_REPLY_ID};
+use crate::state::{State, BALANCES, STATE};
+use crate::{execute, query, reply};
+use euclid::error::ContractError;
+use euclid::msgs::multi_vlp::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+// version info for migration info
+pub(crate) const CONTRACT_NAME: &str = "crates.io:multi_vlp";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let tokens = sort_pool_tokens(msg.tokens)?;
+
+    let state = State {
+        tokens,
+        virtual_balance: msg.virtual_balance,
+        router: info.sender.to_string(),
+        fee: msg.fee,
+        total_fees_collected: TotalFees {
+            lp_fees: DenomFees {
+                totals: HashMap::default(),
+            },
+            euclid_fees: DenomFees {
+                totals: HashMap::default(),
+            },
+        },
+        total_lp_tokens: Uint128::zero(),
+        admin: msg.admin,
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    STATE.save(deps.storage, &state)?;
+
+    for token in state.tokens.iter() {
+        BALANCES.save(deps.storage, token.clone(), &Uint128::zero())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("vlp_address", env.contract.address.to_string())
+        .add_attribute("tokens", state.tokens.len().to_string())
+        .add_attribute("owner", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateFee {
+            lp_fee_bps,
+            euclid_fee_bps,
+            recipient,
+        } => execute::update_fee(deps, info, lp_fee_bps, euclid_fee_bps, recipient),
+        ExecuteMsg::DistributeFees {} => execute::distribute_fees(deps, env),
+        ExecuteMsg::Swap {
+            sender,
+            tx_id,
+            asset_in,
+            amount_in,
+            min_token_out,
+            next_swaps,
+            test_fail,
+            asset_out,
+        } => execute::execute_swap(
+            deps,
+            env,
+            sender,
+            asset_in,
+            amount_in,
+            min_token_out,
+            tx_id,
+            next_swaps,
+            test_fail,
+            asset_out,
+        ),
+        ExecuteMsg::AddLiquidity {
+            sender,
+            tx_id,
+            liquidity,
+            min_lp_allocation,
+        } => execute::add_liquidity(deps, env, info, sender, liquidity, min_lp_allocation, tx_id),
+        ExecuteMsg::RemoveLiquidity {
+            sender,
+            tx_id,
+            lp_allocation,
+        } => execute::remove_liquidity(deps, env, info, sender, lp_allocation, tx_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::State {} => query::query_state(deps),
+        QueryMsg::SimulateSwap {
+            asset,
+            asset_amount,
+            swaps,
+            asset_out,
+        } => query::query_simulate_swap(deps, env, asset, asset_amount, swaps, asset_out),
+        QueryMsg::LiquidityDepth {
+            asset_in,
+            target,
+            asset_out,
+        } => query::query_liquidity_depth(deps, env, asset_in, target, asset_out),
+        QueryMsg::Liquidity {} => query::query_liquidity(deps),
+        QueryMsg::LpShares { user } => query::query_lp_shares(deps, user),
+        QueryMsg::UndistributedFees {} => query::query_undistributed_fees(deps),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        VIRTUAL_BALANCE_TRANSFER_REPLY_ID => reply::on_virtual_balance_transfer_reply(deps, msg),
+        NEXT_SWAP_REPLY_ID => reply::on_next_swap_reply(deps, msg),
+
+        id => Err(ContractError::Generic {
+            err: format!("Unknown reply id: {id}"),
+        }),
+    }
+}
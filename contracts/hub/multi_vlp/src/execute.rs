@@ -0,0 +1,506 @@
+use cosmwasm_std::{
+    ensure, to_json_binary, Decimal, DepsMut, Env, MessageInfo, Response, StdResult, SubMsg,
+    Uint128, WasmMsg,
+};
+use euclid::{
+    chain::{ChainUid, CrossChainUser},
+    error::ContractError,
+    events::{simple_event, tx_event, TxType},
+    fee::{split_fee, FeeRecipient, MAX_FEE_BPS},
+    msgs::{
+        multi_vlp::{MultiVlpAddLiquidityResponse, MultiVlpRemoveLiquidityResponse},
+        virtual_balance::{ExecuteMsg as VirtualBalanceExecuteMsg, ExecuteTransfer},
+        vlp::VlpSwapResponse,
+    },
+    pool::MINIMUM_LIQUIDITY,
+    swap::NextSwapVlp,
+    token::Token,
+    virtual_balance::BalanceKey,
+};
+
+use crate::{
+    query::{calculate_invariant, calculate_swap, validate_swap_tokens},
+    reply::{NEXT_SWAP_REPLY_ID, VIRTUAL_BALANCE_TRANSFER_REPLY_ID},
+    state::{BALANCES, EUCLID_FEES_HELD, LP_SHARES, STATE},
+};
+
+/// Adds liquidity to the pool, the router transfers the liquidity to the pool virtual balance first.
+///
+/// The lp allocation is the smallest share of the reserves provided over all tokens. Only that share
+/// of every token is added and the rest is sent back to the sender, so an unbalanced deposit can't
+/// move the pool prices. The first deposit sets the prices, mints the pool invariant as lp shares
+/// and locks `MINIMUM_LIQUIDITY` of them.
+pub fn add_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: CrossChainUser,
+    liquidity: Vec<(Token, Uint128)>,
+    min_lp_allocation: Uint128,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.router, ContractError::Unauthorized {});
+
+    // Every pool token has to be provided exactly once
+    ensure!(
+        liquidity.len() == state.tokens.len()
+            && state.tokens.iter().all(|token| liquidity
+                .iter()
+                .filter(|(t, _)| t == token)
+                .count()
+                == 1),
+        ContractError::new("Liquidity has to be provided for every token of the pool")
+    );
+    ensure!(
+        liquidity.iter().all(|(_, amount)| !amount.is_zero()),
+        ContractError::ZeroAssetAmount {}
+    );
+
+    let mut reserves = vec![];
+    for (token, amount) in liquidity.iter() {
+        reserves.push((
+            token.clone(),
+            BALANCES.load(deps.storage, token.clone())?,
+            *amount,
+        ));
+    }
+
+    let (lp_allocation, minted_lp_tokens) = if state.total_lp_tokens.is_zero() {
+        let amounts: Vec<Uint128> = liquidity.iter().map(|(_, amount)| *amount).collect();
+        let invariant = calculate_invariant(&amounts)?;
+        (
+            invariant.checked_sub(Uint128::new(MINIMUM_LIQUIDITY))?,
+            invariant,
+        )
+    } else {
+        let mut lp_allocation: Option<Uint128> = None;
+        for (_, reserve, amount) in reserves.iter() {
+            let allocation = amount.checked_multiply_ratio(state.total_lp_tokens, *reserve)?;
+            lp_allocation = Some(lp_allocation.map_or(allocation, |lp| lp.min(allocation)));
+        }
+        let lp_allocation = lp_allocation.unwrap_or_default();
+        (lp_allocation, lp_allocation)
+    };
+
+    ensure!(
+        !lp_allocation.is_zero(),
+        ContractError::new("LP Allocation cannot be zero")
+    );
+    ensure!(
+        lp_allocation.ge(&min_lp_allocation),
+        ContractError::new("LP Allocation is below the minimum")
+    );
+
+    let vlp_cross_chain_struct = CrossChainUser {
+        address: env.contract.address.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let mut response = Response::new();
+    for (token, reserve, amount) in reserves {
+        let added = if state.total_lp_tokens.is_zero() {
+            amount
+        } else {
+            reserve.checked_mul_ceil((minted_lp_tokens, state.total_lp_tokens))?
+        };
+        BALANCES.save(deps.storage, token.clone(), &reserve.checked_add(added)?)?;
+        response = response.add_attribute(format!("liquidity_added_{token}"), added);
+
+        let refund = amount.checked_sub(added)?;
+        if !refund.is_zero() {
+            let refund_msg = token.create_virtual_balance_transfer_msg(
+                state.virtual_balance.clone(),
+                refund,
+                vlp_cross_chain_struct.clone(),
+                sender.clone(),
+            )?;
+            response = response.add_submessage(SubMsg::reply_on_error(
+                refund_msg,
+                VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+            ));
+        }
+    }
+
+    LP_SHARES.update(
+        deps.storage,
+        (sender.chain_uid.clone(), sender.address.clone()),
+        |shares| -> StdResult<_> { Ok(shares.unwrap_or_default().checked_add(lp_allocation)?) },
+    )?;
+    state.total_lp_tokens = state.total_lp_tokens.checked_add(minted_lp_tokens)?;
+    STATE.save(deps.storage, &state)?;
+
+    let liquidity_response = MultiVlpAddLiquidityResponse {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        mint_lp_tokens: lp_allocation,
+        vlp_address: env.contract.address.to_string(),
+    };
+
+    Ok(response
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("action", "add_liquidity")
+        .add_attribute("sender", sender.to_sender_string())
+        .add_attribute("lp_allocation", lp_allocation)
+        .set_data(to_json_binary(&liquidity_response)?))
+}
+
+/// Burns the sender's lp shares and releases the same share of every reserve to its virtual balance.
+pub fn remove_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: CrossChainUser,
+    lp_allocation: Uint128,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(!lp_allocation.is_zero(), ContractError::ZeroAssetAmount {});
+
+    let shares_key = LP_SHARES.key((sender.chain_uid.clone(), sender.address.clone()));
+    let shares = shares_key.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(
+        shares.ge(&lp_allocation),
+        ContractError::InsufficientFunds {}
+    );
+    let remaining_shares = shares.checked_sub(lp_allocation)?;
+    if remaining_shares.is_zero() {
+        shares_key.remove(deps.storage);
+    } else {
+        shares_key.save(deps.storage, &remaining_shares)?;
+    }
+
+    let vlp_cross_chain_struct = CrossChainUser {
+        address: env.contract.address.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let mut liquidity_released = vec![];
+    let mut response = Response::new();
+    for token in state.tokens.iter() {
+        let reserve = BALANCES.load(deps.storage, token.clone())?;
+        let released = reserve.checked_multiply_ratio(lp_allocation, state.total_lp_tokens)?;
+        BALANCES.save(deps.storage, token.clone(), &reserve.checked_sub(released)?)?;
+        liquidity_released.push((token.clone(), released));
+        response = response.add_attribute(format!("liquidity_removed_{token}"), released);
+
+        if released.is_zero() {
+            continue;
+        }
+        let transfer_msg = token.create_virtual_balance_transfer_msg(
+            state.virtual_balance.clone(),
+            released,
+            vlp_cross_chain_struct.clone(),
+            sender.clone(),
+        )?;
+        response = response.add_submessage(SubMsg::reply_on_error(
+            transfer_msg,
+            VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+        ));
+    }
+
+    state.total_lp_tokens = state.total_lp_tokens.checked_sub(lp_allocation)?;
+    STATE.save(deps.storage, &state)?;
+
+    let liquidity_response = MultiVlpRemoveLiquidityResponse {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        liquidity_released,
+        burn_lp_tokens: lp_allocation,
+        vlp_address: env.contract.address.to_string(),
+    };
+
+    Ok(response
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::RemoveLiquidity,
+        ))
+        .add_attribute("action", "remove_liquidity")
+        .add_attribute("sender", sender.to_sender_string())
+        .add_attribute("burn_lp", lp_allocation)
+        .set_data(to_json_binary(&liquidity_response)?))
+}
+
+pub fn execute_swap(
+    deps: DepsMut,
+    env: Env,
+    sender: CrossChainUser,
+    asset_in: Token,
+    amount_in: Uint128,
+    min_token_out: Uint128,
+    tx_id: String,
+    next_swaps: Vec<NextSwapVlp>,
+    test_fail: Option<bool>,
+    asset_out: Option<Token>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        !test_fail.unwrap_or(false),
+        ContractError::new("Force fail flag")
+    );
+    // Verify that the asset amount is non-zero
+    ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
+
+    let mut state = STATE.load(deps.storage)?;
+    let asset_out = validate_swap_tokens(&state, &asset_in, asset_out)?;
+
+    let fee = state.fee.clone();
+    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(fee.lp_fee_bps))?;
+    let euclid_fee = amount_in.checked_mul_floor(Decimal::bps(fee.euclid_fee_bps))?;
+    let total_fee = lp_fee.checked_add(euclid_fee)?;
+    let swap_amount = amount_in.checked_sub(total_fee)?;
+
+    let token_in_reserve = BALANCES.load(deps.storage, asset_in.clone())?;
+    let token_out_reserve = BALANCES.load(deps.storage, asset_out.clone())?;
+
+    // The previous hop or the router already moved amount_in to this pool's virtual balance
+    let pool_virtual_balance: euclid::msgs::virtual_balance::GetBalanceResponse =
+        deps.querier.query_wasm_smart(
+            state.virtual_balance.clone(),
+            &euclid::msgs::virtual_balance::QueryMsg::GetBalance {
+                balance_key: BalanceKey {
+                    cross_chain_user: CrossChainUser {
+                        address: env.contract.address.to_string(),
+                        chain_uid: ChainUid::vsl_chain_uid()?,
+                    },
+                    token_id: asset_in.to_string(),
+                },
+            },
+        )?;
+    let fees_held = EUCLID_FEES_HELD
+        .may_load(deps.storage, asset_in.clone())?
+        .unwrap_or_default();
+    ensure!(
+        pool_virtual_balance.amount
+            == token_in_reserve
+                .checked_add(fees_held)?
+                .checked_add(amount_in)?,
+        ContractError::new("Swap didn't receive any funds!")
+    );
+
+    let receive_amount = calculate_swap(swap_amount, token_in_reserve, token_out_reserve)?;
+    ensure!(
+        !receive_amount.is_zero(),
+        ContractError::SlippageExceeded {
+            amount: receive_amount,
+            min_amount_out: min_token_out,
+        }
+    );
+
+    // Lp fee compounds into the reserves, euclid fee stays in the pool until DistributeFees is called
+    BALANCES.save(
+        deps.storage,
+        asset_in.clone(),
+        &token_in_reserve
+            .checked_add(swap_amount)?
+            .checked_add(lp_fee)?,
+    )?;
+    BALANCES.save(
+        deps.storage,
+        asset_out.clone(),
+        &token_out_reserve.checked_sub(receive_amount)?,
+    )?;
+    state
+        .total_fees_collected
+        .lp_fees
+        .add_fee(asset_in.to_string(), lp_fee);
+    if !euclid_fee.is_zero() {
+        state
+            .total_fees_collected
+            .euclid_fees
+            .add_fee(asset_in.to_string(), euclid_fee);
+        EUCLID_FEES_HELD.save(
+            deps.storage,
+            asset_in.clone(),
+            &fees_held.checked_add(euclid_fee)?,
+        )?;
+    }
+    STATE.save(deps.storage, &state)?;
+
+    let swap_response = VlpSwapResponse {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        asset_out: asset_out.clone(),
+        amount_out: receive_amount,
+    };
+
+    let mut response = Response::new();
+    match next_swaps.split_first() {
+        Some((next_swap, forward_swaps)) => {
+            let virtual_balance_transfer_msg =
+                VirtualBalanceExecuteMsg::Transfer(ExecuteTransfer {
+                    amount: receive_amount,
+                    token_id: asset_out.to_string(),
+                    from: CrossChainUser {
+                        address: env.contract.address.to_string(),
+                        chain_uid: ChainUid::vsl_chain_uid()?,
+                    },
+                    to: CrossChainUser {
+                        address: next_swap.vlp_address.clone(),
+                        chain_uid: ChainUid::vsl_chain_uid()?,
+                    },
+                });
+            let virtual_balance_transfer_msg = WasmMsg::Execute {
+                contract_addr: state.virtual_balance.clone(),
+                msg: to_json_binary(&virtual_balance_transfer_msg)?,
+                funds: vec![],
+            };
+
+            // Pair vlps and multi asset pools accept the same swap message
+            let next_swap_msg = euclid::msgs::vlp::ExecuteMsg::Swap {
+                sender: sender.clone(),
+                asset_in: asset_out.clone(),
+                amount_in: receive_amount,
+                min_token_out,
+                tx_id: tx_id.clone(),
+                next_swaps: forward_swaps.to_vec(),
+                test_fail: next_swap.test_fail,
+                asset_out: next_swap.asset_out.clone(),
+            };
+            let next_swap_msg = WasmMsg::Execute {
+                contract_addr: next_swap.vlp_address.clone(),
+                msg: to_json_binary(&next_swap_msg)?,
+                funds: vec![],
+            };
+
+            response = response
+                .add_attribute("swap_type", "forward_swap")
+                .add_attribute("forward_to", next_swap.vlp_address.clone())
+                .add_submessage(SubMsg::reply_on_error(
+                    virtual_balance_transfer_msg,
+                    VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+                ))
+                .add_submessage(SubMsg::reply_always(next_swap_msg, NEXT_SWAP_REPLY_ID));
+        }
+        None => {
+            // Verify that the receive amount is >= min amount as its last swap
+            ensure!(
+                receive_amount.ge(&min_token_out),
+                ContractError::SlippageExceeded {
+                    amount: receive_amount,
+                    min_amount_out: min_token_out,
+                }
+            );
+            let transfer_msg = asset_out.create_virtual_balance_transfer_msg(
+                state.virtual_balance.clone(),
+                receive_amount,
+                CrossChainUser {
+                    address: env.contract.address.to_string(),
+                    chain_uid: ChainUid::vsl_chain_uid()?,
+                },
+                sender.clone(),
+            )?;
+            response = response
+                .add_attribute("swap_type", "final_swap")
+                .add_attribute("receiver_address", sender.address.clone())
+                .add_attribute("receiver_chain_id", sender.chain_uid.to_string())
+                .add_submessage(SubMsg::reply_on_error(
+                    transfer_msg,
+                    VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+                ));
+        }
+    }
+
+    Ok(response
+        .add_event(tx_event(&tx_id, &sender.to_sender_string(), TxType::Swap))
+        .add_attribute("action", "swap")
+        .add_attribute("amount_in", amount_in)
+        .add_attribute("asset_in", asset_in.to_string())
+        .add_attribute("asset_out", asset_out.to_string())
+        .add_attribute("total_fee", total_fee)
+        .add_attribute("euclid_fee", euclid_fee)
+        .add_attribute("lp_fee", lp_fee)
+        .add_attribute("receive_amount", receive_amount)
+        .set_data(to_json_binary(&swap_response)?))
+}
+
+pub fn update_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_fee_bps: Option<u64>,
+    euclid_fee_bps: Option<u64>,
+    recipient: Option<CrossChainUser>,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    state.fee.lp_fee_bps = lp_fee_bps.unwrap_or(state.fee.lp_fee_bps);
+    ensure!(
+        state.fee.lp_fee_bps.le(&MAX_FEE_BPS),
+        ContractError::new("LP Fee cannot exceed maximum limit")
+    );
+    state.fee.euclid_fee_bps = euclid_fee_bps.unwrap_or(state.fee.euclid_fee_bps);
+    ensure!(
+        state.fee.euclid_fee_bps.le(&MAX_FEE_BPS),
+        ContractError::new("Euclid Fee cannot exceed maximum limit")
+    );
+    state.fee.recipient = recipient.unwrap_or(state.fee.recipient);
+
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_event(simple_event())
+        .add_attribute("action", "update_fee"))
+}
+
+/// Sends the held euclid fees with the router default fee split, or all of them to the fee recipient.
+pub fn distribute_fees(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+
+    let mut fees = vec![];
+    for token in state.tokens.iter() {
+        let amount = EUCLID_FEES_HELD
+            .may_load(deps.storage, token.clone())?
+            .unwrap_or_default();
+        if !amount.is_zero() {
+            fees.push((token.clone(), amount));
+        }
+    }
+    ensure!(
+        !fees.is_empty(),
+        ContractError::new("No fees to distribute")
+    );
+
+    let default_recipients: euclid::msgs::router::FeeRecipientsResponse =
+        deps.querier.query_wasm_smart(
+            state.router.clone(),
+            &euclid::msgs::router::QueryMsg::GetDefaultFeeRecipients {},
+        )?;
+    let recipients = default_recipients.recipients.unwrap_or(vec![FeeRecipient {
+        recipient: state.fee.recipient.clone(),
+        weight_bps: 10_000,
+    }]);
+
+    let vlp_cross_chain_struct = CrossChainUser {
+        address: env.contract.address.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let mut response = Response::new();
+    for (token, amount) in fees {
+        EUCLID_FEES_HELD.remove(deps.storage, token.clone());
+        response = response.add_attribute(format!("distributed_{token}"), amount);
+
+        for (recipient, share) in split_fee(amount, &recipients)? {
+            if share.is_zero() {
+                continue;
+            }
+            let transfer_msg = token.create_virtual_balance_transfer_msg(
+                state.virtual_balance.clone(),
+                share,
+                vlp_cross_chain_struct.clone(),
+                recipient,
+            )?;
+            response = response.add_submessage(SubMsg::reply_on_error(
+                transfer_msg,
+                VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+            ));
+        }
+    }
+
+    Ok(response
+        .add_event(simple_event())
+        .add_attribute("action", "distribute_fees"))
+}
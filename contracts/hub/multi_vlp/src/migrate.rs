@@ -0,0 +1,18 @@
+use cosmwasm_std::{entry_point, DepsMut, Env, Response};
+use euclid::{
+    error::ContractError, migrate::ensure_migration_version, msgs::multi_vlp::MigrateMsg,
+};
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+
+/// This is the migrate entry point for the multi asset vlp, it only checks and bumps the stored contract version.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_migration_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &[])?;
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,245 @@
+use cosmwasm_std::{ensure, to_json_binary, Binary, Decimal, Deps, Env, Uint128, Uint512};
+use euclid::{
+    chain::CrossChainUser,
+    error::ContractError,
+    fee::compound_fee_bps,
+    msgs::{
+        multi_vlp::{
+            LiquidityResponse, LpSharesResponse, StateResponse, UndistributedFeesResponse,
+        },
+        vlp::{GetSwapResponse, LiquidityDepthResponse},
+    },
+    swap::{
+        get_amount_in_before_fees, get_compounded_fee, get_route_price_impact_bps, get_spot_price,
+        get_swap_amount_to_price, DepthTarget, NextSwapVlp, SwapHop,
+    },
+    token::Token,
+};
+
+use crate::state::{State, BALANCES, EUCLID_FEES_HELD, LP_SHARES, STATE};
+
+pub fn query_state(deps: Deps) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    Ok(to_json_binary(&StateResponse {
+        tokens: state.tokens,
+        router: state.router,
+        virtual_balance: state.virtual_balance,
+        fee: state.fee,
+        total_fees_collected: state.total_fees_collected,
+        total_lp_tokens: state.total_lp_tokens,
+        admin: state.admin,
+    })?)
+}
+
+pub fn query_simulate_swap(
+    deps: Deps,
+    env: Env,
+    asset_in: Token,
+    amount_in: Uint128,
+    next_swaps: Vec<NextSwapVlp>,
+    asset_out: Option<Token>,
+) -> Result<Binary, ContractError> {
+    // Verify that the asset amount is non-zero
+    ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
+
+    let state = STATE.load(deps.storage)?;
+    let asset_out = validate_swap_tokens(&state, &asset_in, asset_out)?;
+
+    let fee = state.fee;
+    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(fee.lp_fee_bps))?;
+    let euclid_fee = amount_in.checked_mul_floor(Decimal::bps(fee.euclid_fee_bps))?;
+    let swap_amount = amount_in.checked_sub(lp_fee.checked_add(euclid_fee)?)?;
+
+    let token_in_reserve = BALANCES.load(deps.storage, asset_in.clone())?;
+    let token_out_reserve = BALANCES.load(deps.storage, asset_out.clone())?;
+
+    let receive_amount = calculate_swap(swap_amount, token_in_reserve, token_out_reserve)?;
+    let mut hop = SwapHop {
+        vlp_address: env.contract.address.to_string(),
+        asset_in,
+        amount_in,
+        asset_out: asset_out.clone(),
+        amount_out: receive_amount,
+        lp_fee,
+        euclid_fee,
+        spot_price: get_spot_price(token_in_reserve, token_out_reserve)?,
+        price_impact_bps: 0,
+    };
+    hop.price_impact_bps = get_route_price_impact_bps(&[hop.clone()])?;
+
+    let response = match next_swaps.split_first() {
+        Some((next_swap, forward_swaps)) => {
+            let next_swap_response: GetSwapResponse = deps.querier.query_wasm_smart(
+                next_swap.vlp_address.clone(),
+                &euclid::msgs::vlp::QueryMsg::SimulateSwap {
+                    asset: asset_out,
+                    asset_amount: receive_amount,
+                    swaps: forward_swaps.to_vec(),
+                    asset_out: next_swap.asset_out.clone(),
+                },
+            )?;
+            let mut hops = vec![hop];
+            hops.extend(next_swap_response.hops);
+            let (route_lp_fee_bps, route_euclid_fee_bps) = compound_fee_bps(
+                (fee.lp_fee_bps, fee.euclid_fee_bps),
+                (
+                    next_swap_response.lp_fee_bps,
+                    next_swap_response.euclid_fee_bps,
+                ),
+            );
+            GetSwapResponse::new(
+                amount_in,
+                next_swap_response.amount_out,
+                next_swap_response.asset_out,
+                hops,
+                route_lp_fee_bps,
+                route_euclid_fee_bps,
+            )?
+        }
+        None => GetSwapResponse::new(
+            amount_in,
+            receive_amount,
+            asset_out,
+            vec![hop],
+            fee.lp_fee_bps,
+            fee.euclid_fee_bps,
+        )?,
+    };
+    Ok(to_json_binary(&response)?)
+}
+
+pub fn query_liquidity_depth(
+    deps: Deps,
+    env: Env,
+    asset_in: Token,
+    target: DepthTarget,
+    asset_out: Option<Token>,
+) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let asset_out = validate_swap_tokens(&state, &asset_in, asset_out)?;
+
+    let mut response = LiquidityDepthResponse {
+        vlp_address: env.contract.address.to_string(),
+        asset_in: asset_in.clone(),
+        asset_out: asset_out.clone(),
+        amount_in: Uint128::zero(),
+        amount_out: Uint128::zero(),
+        spot_price: Decimal::zero(),
+        target_price: Decimal::zero(),
+    };
+    let reserve_in = BALANCES.load(deps.storage, asset_in)?;
+    let reserve_out = BALANCES.load(deps.storage, asset_out)?;
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Ok(to_json_binary(&response)?);
+    }
+
+    // A swap only moves the two reserves involved, so the pair follows the two token curve.
+    // The whole lp fee compounds into reserve_in
+    response.spot_price = get_spot_price(reserve_in, reserve_out)?;
+    response.target_price = target.get_target_price(response.spot_price)?;
+    let total_fee_bps = state.fee.lp_fee_bps + state.fee.euclid_fee_bps;
+    let compounded_fee = get_compounded_fee(state.fee.lp_fee_bps, total_fee_bps, Decimal::one())?;
+    let swap_amount = get_swap_amount_to_price(
+        reserve_in,
+        reserve_out,
+        response.target_price,
+        compounded_fee,
+    )?;
+    if !swap_amount.is_zero() {
+        response.amount_in = get_amount_in_before_fees(swap_amount, total_fee_bps)?;
+        response.amount_out = calculate_swap(swap_amount, reserve_in, reserve_out)?;
+    }
+    Ok(to_json_binary(&response)?)
+}
+
+pub fn query_liquidity(deps: Deps) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    Ok(to_json_binary(&LiquidityResponse {
+        reserves: get_reserves(deps, &state)?,
+        total_lp_tokens: state.total_lp_tokens,
+    })?)
+}
+
+pub fn query_lp_shares(deps: Deps, user: CrossChainUser) -> Result<Binary, ContractError> {
+    let lp_shares = LP_SHARES
+        .may_load(deps.storage, (user.chain_uid.clone(), user.address.clone()))?
+        .unwrap_or_default();
+    Ok(to_json_binary(&LpSharesResponse { user, lp_shares })?)
+}
+
+pub fn query_undistributed_fees(deps: Deps) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let mut fees = vec![];
+    for token in state.tokens {
+        let amount = EUCLID_FEES_HELD
+            .may_load(deps.storage, token.clone())?
+            .unwrap_or_default();
+        fees.push((token, amount));
+    }
+    Ok(to_json_binary(&UndistributedFeesResponse { fees })?)
+}
+
+pub fn get_reserves(deps: Deps, state: &State) -> Result<Vec<(Token, Uint128)>, ContractError> {
+    let mut reserves = vec![];
+    for token in state.tokens.iter() {
+        reserves.push((token.clone(), BALANCES.load(deps.storage, token.clone())?));
+    }
+    Ok(reserves)
+}
+
+// Both tokens have to be in the pool, a multi asset pool can't guess which token to return
+pub fn validate_swap_tokens(
+    state: &State,
+    asset_in: &Token,
+    asset_out: Option<Token>,
+) -> Result<Token, ContractError> {
+    let asset_out = asset_out.ok_or(ContractError::new(
+        "Asset out is required to swap on a multi asset pool",
+    ))?;
+    ensure!(
+        state.tokens.contains(asset_in) && state.tokens.contains(&asset_out),
+        ContractError::AssetDoesNotExist {}
+    );
+    ensure!(*asset_in != asset_out, ContractError::DuplicateTokens {});
+    Ok(asset_out)
+}
+
+// Equal weights keep the product of all reserves constant, so a swap only moves the
+// two reserves involved along their constant product curve
+pub fn calculate_swap(
+    swap_amount: Uint128,
+    reserve_in: Uint128,
+    reserve_out: Uint128,
+) -> Result<Uint128, ContractError> {
+    let new_reserve_in = reserve_in.checked_add(swap_amount)?;
+    Ok(reserve_out.checked_multiply_ratio(swap_amount, new_reserve_in)?)
+}
+
+// Equal weight invariant of the reserves, their geometric mean. Reserves are scaled down to 64 bits
+// first so the product of up to 8 of them fits in a Uint512
+pub fn calculate_invariant(reserves: &[Uint128]) -> Result<Uint128, ContractError> {
+    let max_bits = reserves
+        .iter()
+        .map(|reserve| 128 - reserve.u128().leading_zeros())
+        .max()
+        .unwrap_or_default();
+    let shift = max_bits.saturating_sub(64);
+    let product = reserves
+        .iter()
+        .try_fold(Uint512::one(), |product, reserve| {
+            product.checked_mul(Uint512::from(reserve.u128() >> shift))
+        })?;
+
+    // Largest value whose power by the number of reserves doesn't exceed the product
+    let exp = reserves.len() as u32;
+    let (mut low, mut high) = (0u128, u64::MAX as u128);
+    while low < high {
+        let mid = high - (high - low) / 2;
+        if Uint512::from(mid).checked_pow(exp)? <= product {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(Uint128::new(low << shift))
+}
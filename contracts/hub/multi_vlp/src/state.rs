@@ -0,0 +1,35 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+use cw_storage_plus::{Item, Map};
+use euclid::{
+    chain::ChainUid,
+    fee::{Fee, TotalFees},
+    token::Token,
+};
+
+#[cw_serde]
+pub struct State {
+    // Sorted pool tokens
+    pub tokens: Vec<Token>,
+    // Router Contract
+    pub router: String,
+    // Virtual Coin Contract
+    pub virtual_balance: String,
+    // Fee per swap for each transaction
+    pub fee: Fee,
+    // Total lp and euclid fees collected
+    pub total_fees_collected: TotalFees,
+    // Total number of LP shares issued, including the locked minimum liquidity
+    pub total_lp_tokens: Uint128,
+    pub admin: String,
+}
+
+pub const STATE: Item<State> = Item::new("state");
+
+pub const BALANCES: Map<Token, Uint128> = Map::new("balances");
+
+// Lp shares of each user, (Chain UID, Address)
+pub const LP_SHARES: Map<(ChainUid, String), Uint128> = Map::new("lp_shares");
+
+// Euclid fees held in the pool virtual balance until they are distributed
+pub const EUCLID_FEES_HELD: Map<Token, Uint128> = Map::new("euclid_fees_held");
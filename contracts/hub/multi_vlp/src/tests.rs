@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod tests {
+    use crate::contract::{execute, instantiate, query};
+    use crate::query::calculate_invariant;
+    use crate::state::{BALANCES, EUCLID_FEES_HELD};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        from_json, to_json_binary, ContractResult, CosmosMsg, Empty, OwnedDeps, SystemError,
+        SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use euclid::chain::{ChainUid, CrossChainUser};
+    use euclid::error::ContractError;
+    use euclid::fee::Fee;
+    use euclid::msgs::multi_vlp::{
+        ExecuteMsg, InstantiateMsg, LiquidityResponse, LpSharesResponse, QueryMsg,
+    };
+    use euclid::msgs::virtual_balance::{
+        ExecuteMsg as VirtualBalanceExecuteMsg, GetBalanceResponse,
+        QueryMsg as VirtualBalanceQueryMsg,
+    };
+    use euclid::msgs::vlp::{GetSwapResponse, VlpSwapResponse};
+    use euclid::token::Token;
+
+    type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>;
+
+    fn token(id: &str) -> Token {
+        Token::create(id.to_string()).unwrap()
+    }
+
+    fn user(address: &str) -> CrossChainUser {
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+            address: address.to_string(),
+        }
+    }
+
+    fn mock_pool_balance(deps: &mut MockDeps, balance: u128) {
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "vb" => {
+                let VirtualBalanceQueryMsg::GetBalance { .. } = from_json(msg).unwrap() else {
+                    return SystemResult::Err(SystemError::Unknown {});
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&GetBalanceResponse {
+                        amount: Uint128::new(balance),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+    }
+
+    fn init(deps: &mut MockDeps) {
+        let msg = InstantiateMsg {
+            router: "router".to_string(),
+            virtual_balance: "vb".to_string(),
+            tokens: vec![token("usdc"), token("eth"), token("atom")],
+            fee: Fee {
+                lp_fee_bps: 10,
+                euclid_fee_bps: 10,
+                recipient: user("admin"),
+            },
+            admin: "admin".to_string(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+    }
+
+    fn add_liquidity(deps: &mut MockDeps, sender: &str, amounts: [u128; 3]) -> Vec<CosmosMsg> {
+        let msg = ExecuteMsg::AddLiquidity {
+            sender: user(sender),
+            tx_id: "tx".to_string(),
+            liquidity: vec![
+                (token("atom"), Uint128::new(amounts[0])),
+                (token("eth"), Uint128::new(amounts[1])),
+                (token("usdc"), Uint128::new(amounts[2])),
+            ],
+            min_lp_allocation: Uint128::zero(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg)
+            .unwrap()
+            .messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect()
+    }
+
+    fn lp_shares(deps: &MockDeps, address: &str) -> Uint128 {
+        let res: LpSharesResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::LpShares {
+                    user: user(address),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.lp_shares
+    }
+
+    #[test]
+    fn test_instantiate_validates_tokens() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            router: "router".to_string(),
+            virtual_balance: "vb".to_string(),
+            tokens: vec![token("usdc"), token("eth")],
+            fee: Fee {
+                lp_fee_bps: 10,
+                euclid_fee_bps: 10,
+                recipient: user("admin"),
+            },
+            admin: "admin".to_string(),
+        };
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("router", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Multi asset pools need between 3 and 8 tokens")
+        );
+
+        let msg = InstantiateMsg {
+            tokens: vec![token("usdc"), token("eth"), token("usdc")],
+            ..msg
+        };
+        let err =
+            instantiate(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateTokens {});
+    }
+
+    #[test]
+    fn test_calculate_invariant() {
+        let invariant = calculate_invariant(&[
+            Uint128::new(10_000),
+            Uint128::new(20_000),
+            Uint128::new(40_000),
+        ])
+        .unwrap();
+        assert_eq!(invariant, Uint128::new(20_000));
+
+        // Eight 18 decimal reserves don't overflow
+        let reserves = vec![Uint128::new(10u128.pow(30)); 8];
+        let invariant = calculate_invariant(&reserves).unwrap();
+        assert!(invariant <= Uint128::new(10u128.pow(30)));
+        assert!(invariant > Uint128::new(10u128.pow(30) - 10u128.pow(18)));
+    }
+
+    #[test]
+    fn test_multi_asset_liquidity_and_swap() {
+        let mut deps = mock_dependencies();
+        init(&mut deps);
+
+        // First deposit sets the prices and locks the minimum liquidity
+        let msgs = add_liquidity(&mut deps, "alice", [10_000, 20_000, 40_000]);
+        assert!(msgs.is_empty());
+        // Cube root of 10000 * 20000 * 40000
+        assert_eq!(lp_shares(&deps, "alice"), Uint128::new(19_000));
+
+        // Only the proportional part of an unbalanced deposit is added, extra eth goes back
+        let msgs = add_liquidity(&mut deps, "bob", [1_000, 3_000, 4_000]);
+        assert_eq!(lp_shares(&deps, "bob"), Uint128::new(2_000));
+        assert_eq!(msgs.len(), 1);
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &msgs[0] else {
+            panic!("Expected a refund transfer");
+        };
+        let VirtualBalanceExecuteMsg::Transfer(transfer) = from_json(msg).unwrap() else {
+            panic!("Expected a refund transfer");
+        };
+        assert_eq!(transfer.token_id, "eth");
+        assert_eq!(transfer.amount, Uint128::new(1_000));
+        assert_eq!(transfer.to, user("bob"));
+
+        let liquidity: LiquidityResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Liquidity {}).unwrap()).unwrap();
+        assert_eq!(
+            liquidity.reserves,
+            vec![
+                (token("atom"), Uint128::new(11_000)),
+                (token("eth"), Uint128::new(22_000)),
+                (token("usdc"), Uint128::new(44_000)),
+            ]
+        );
+        assert_eq!(liquidity.total_lp_tokens, Uint128::new(22_000));
+
+        // Swaps between any two pool tokens, the output token has to be named
+        let simulate: GetSwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateSwap {
+                    asset: token("atom"),
+                    asset_amount: Uint128::new(1_000),
+                    swaps: vec![],
+                    asset_out: Some(token("usdc")),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // 998 after fees, 44000 * 998 / 11998
+        assert_eq!(simulate.amount_out, Uint128::new(3_659));
+
+        let swap_msg = |asset_out| ExecuteMsg::Swap {
+            sender: user("carol"),
+            tx_id: "swap".to_string(),
+            asset_in: token("atom"),
+            amount_in: Uint128::new(1_000),
+            min_token_out: Uint128::new(3_600),
+            next_swaps: vec![],
+            test_fail: None,
+            asset_out,
+        };
+        mock_pool_balance(&mut deps, 12_000);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("router", &[]),
+            swap_msg(None),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Asset out is required to swap on a multi asset pool")
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("router", &[]),
+            swap_msg(Some(token("usdc"))),
+        )
+        .unwrap();
+        let swap_response: VlpSwapResponse = from_json(res.data.unwrap()).unwrap();
+        assert_eq!(swap_response.amount_out, Uint128::new(3_659));
+        assert_eq!(swap_response.asset_out, token("usdc"));
+
+        // Lp fee compounds into the reserve, euclid fee is held, eth is untouched
+        assert_eq!(
+            BALANCES.load(&deps.storage, token("atom")).unwrap(),
+            Uint128::new(11_999)
+        );
+        assert_eq!(
+            BALANCES.load(&deps.storage, token("usdc")).unwrap(),
+            Uint128::new(40_341)
+        );
+        assert_eq!(
+            BALANCES.load(&deps.storage, token("eth")).unwrap(),
+            Uint128::new(22_000)
+        );
+        assert_eq!(
+            EUCLID_FEES_HELD.load(&deps.storage, token("atom")).unwrap(),
+            Uint128::new(1)
+        );
+
+        // Removing liquidity releases the same share of every token
+        let msg = ExecuteMsg::RemoveLiquidity {
+            sender: user("bob"),
+            tx_id: "remove".to_string(),
+            lp_allocation: Uint128::new(2_000),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(lp_shares(&deps, "bob"), Uint128::zero());
+        assert_eq!(
+            BALANCES.load(&deps.storage, token("eth")).unwrap(),
+            Uint128::new(20_000)
+        );
+
+        let msg = ExecuteMsg::RemoveLiquidity {
+            sender: user("bob"),
+            tx_id: "remove".to_string(),
+            lp_allocation: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+    }
+}
@@ -10,28 +10,44 @@ use euclid::error::ContractError;
 use euclid_ibc::msg::HUB_IBC_EXECUTE_MSG_QUEUE_RANGE;
 
 use crate::execute::{
-    execute_deregister_chain, execute_native_receive_callback, execute_register_factory,
-    execute_release_escrow, execute_reregister_chain, execute_update_factory_channel,
-    execute_update_lock, execute_update_vlp_code_id, execute_withdraw_voucher,
+    execute_add_liquidity_from_virtual_balance, execute_cancel_limit_order,
+    execute_claim_vsl_lp_fees, execute_create_multi_asset_pool, execute_deregister_chain,
+    execute_execute_orders, execute_limit_order_internal, execute_migrate_vlps,
+    execute_native_receive_callback, execute_place_limit_order, execute_refund_liquidity_intent,
+    execute_register_factory, execute_release_escrow, execute_reregister_chain,
+    execute_retry_lp_mint, execute_sunset_vlp, execute_update_default_fee_recipients,
+    execute_update_factory_channel, execute_update_lock, execute_update_multi_vlp_code_id,
+    execute_update_pool_creation_mode, execute_update_vlp_code_id, execute_vsl_add_liquidity,
+    execute_vsl_add_multi_asset_liquidity, execute_vsl_remove_liquidity,
+    execute_vsl_remove_multi_asset_liquidity, execute_vsl_swap, execute_withdraw_voucher,
 };
 use crate::ibc::ack_and_timeout::ibc_ack_packet_internal_call;
 use crate::ibc::receive::ibc_receive_internal_call;
 use crate::query::{
-    self, query_all_chains, query_all_tokens, query_all_vlps, query_chain,
-    query_simulate_escrow_release, query_state, query_token_escrows, query_vlp,
+    self, query_all_chains, query_all_multi_asset_vlps, query_all_tokens, query_all_vlps,
+    query_chain, query_default_fee_recipients, query_failed_escrow_releases, query_failed_lp_mints,
+    query_limit_order, query_limit_orders, query_liquidity_intent, query_multi_asset_vlp,
+    query_pool_creation_mode, query_predict_vlp_address, query_retiring_escrows,
+    query_simulate_escrow_release, query_state, query_sunset_vlps, query_token_escrows,
+    query_unclaimed_vsl_lp_fees, query_user_limit_orders, query_vlp, query_vlp_migration_status,
+    query_vlps_by_token, query_vsl_lp_shares,
 };
 use crate::reply::{
-    self, ADD_LIQUIDITY_REPLY_ID, IBC_ACK_AND_TIMEOUT_REPLY_ID, IBC_RECEIVE_REPLY_ID,
-    REMOVE_LIQUIDITY_REPLY_ID, SWAP_REPLY_ID, VIRTUAL_BALANCE_BURN_REPLY_ID,
-    VIRTUAL_BALANCE_INSTANTIATE_REPLY_ID, VIRTUAL_BALANCE_MINT_REPLY_ID,
-    VIRTUAL_BALANCE_TRANSFER_REPLY_ID, VLP_INSTANTIATE_REPLY_ID, VLP_POOL_REGISTER_REPLY_ID,
+    self, ADD_LIQUIDITY_LEG_REPLY_ID, ADD_LIQUIDITY_REPLY_ID, ESCROW_RELEASE_RETRY_REPLY_ID,
+    IBC_ACK_AND_TIMEOUT_REPLY_ID, IBC_RECEIVE_REPLY_ID, LIMIT_ORDER_EXECUTE_REPLY_ID,
+    MULTI_VLP_INSTANTIATE_REPLY_ID, POOL_CREATION_ADD_LIQUIDITY_REPLY_ID,
+    REMOVE_LIQUIDITY_REPLY_ID, SWAP_REPLY_ID, VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID,
+    VIRTUAL_BALANCE_BURN_REPLY_ID, VIRTUAL_BALANCE_INSTANTIATE_REPLY_ID,
+    VIRTUAL_BALANCE_MINT_REPLY_ID, VIRTUAL_BALANCE_TRANSFER_REPLY_ID, VLP_INSTANTIATE_REPLY_ID,
+    VLP_MIGRATE_REPLY_ID, VLP_POOL_REGISTER_REPLY_ID, VSL_ADD_LIQUIDITY_REPLY_ID,
+    VSL_REMOVE_LIQUIDITY_REPLY_ID,
 };
 use crate::state::{State, DEREGISTERED_CHAINS, STATE};
 use euclid::msgs::router::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:factory";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:router";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -106,10 +122,15 @@ pub fn execute(
             ExecuteMsg::UpdateVLPCodeId { new_vlp_code_id } => {
                 execute_update_vlp_code_id(deps, info, new_vlp_code_id)
             }
+            ExecuteMsg::UpdateMultiVlpCodeId { new_code_id } => {
+                execute_update_multi_vlp_code_id(deps, info, new_code_id)
+            }
+            ExecuteMsg::MigrateVlps { limit } => execute_migrate_vlps(deps, env, info, limit),
             ExecuteMsg::RegisterFactory {
                 chain_uid,
                 chain_info,
             } => execute_register_factory(&mut deps, env, info, chain_uid, chain_info),
+            ExecuteMsg::SunsetVlp { pair } => execute_sunset_vlp(&mut deps, env, info, pair),
             ExecuteMsg::ReleaseEscrowInternal {
                 sender,
                 token,
@@ -117,6 +138,7 @@ pub fn execute(
                 cross_chain_addresses,
                 timeout,
                 tx_id,
+                original_tx_id,
             } => execute_release_escrow(
                 &mut deps,
                 env,
@@ -127,7 +149,14 @@ pub fn execute(
                 cross_chain_addresses,
                 timeout,
                 tx_id,
+                original_tx_id,
             ),
+            ExecuteMsg::RefundLiquidityIntent { sender, intent_id } => {
+                execute_refund_liquidity_intent(&mut deps, env, sender, intent_id)
+            }
+            ExecuteMsg::RetryLpMint { recipient, tx_id } => {
+                execute_retry_lp_mint(&mut deps, env, recipient, tx_id)
+            }
             ExecuteMsg::WithdrawVoucher {
                 token,
                 amount,
@@ -142,6 +171,113 @@ pub fn execute(
                 cross_chain_addresses,
                 timeout,
             ),
+            ExecuteMsg::VslSwap {
+                asset_in,
+                amount_in,
+                asset_out,
+                min_amount_out,
+                swaps,
+            } => execute_vsl_swap(
+                &mut deps,
+                env,
+                info,
+                asset_in,
+                amount_in,
+                asset_out,
+                min_amount_out,
+                swaps,
+            ),
+            ExecuteMsg::VslAddLiquidity {
+                pair,
+                token_1_liquidity,
+                token_2_liquidity,
+                slippage_tolerance,
+            } => execute_vsl_add_liquidity(
+                &mut deps,
+                env,
+                info,
+                pair,
+                token_1_liquidity,
+                token_2_liquidity,
+                slippage_tolerance,
+            ),
+            ExecuteMsg::VslRemoveLiquidity {
+                pair,
+                lp_allocation,
+            } => execute_vsl_remove_liquidity(&mut deps, env, info, pair, lp_allocation),
+            ExecuteMsg::CreateMultiAssetPool { tokens } => {
+                execute_create_multi_asset_pool(&mut deps, env, info, tokens)
+            }
+            ExecuteMsg::VslAddMultiAssetLiquidity {
+                vlp_address,
+                liquidity,
+                min_lp_allocation,
+            } => execute_vsl_add_multi_asset_liquidity(
+                &mut deps,
+                env,
+                info,
+                vlp_address,
+                liquidity,
+                min_lp_allocation,
+            ),
+            ExecuteMsg::VslRemoveMultiAssetLiquidity {
+                vlp_address,
+                lp_allocation,
+            } => execute_vsl_remove_multi_asset_liquidity(
+                &mut deps,
+                env,
+                info,
+                vlp_address,
+                lp_allocation,
+            ),
+            ExecuteMsg::UpdatePoolCreationMode { chain_uid, mode } => {
+                execute_update_pool_creation_mode(deps, info, chain_uid, mode)
+            }
+            ExecuteMsg::UpdateDefaultFeeRecipients { recipients } => {
+                execute_update_default_fee_recipients(deps, info, recipients)
+            }
+            ExecuteMsg::ClaimVslLpFees { pair } => execute_claim_vsl_lp_fees(&mut deps, info, pair),
+            ExecuteMsg::PlaceLimitOrder {
+                asset_in,
+                amount_in,
+                asset_out,
+                limit_price,
+                swaps,
+            } => execute_place_limit_order(
+                &mut deps,
+                env,
+                info,
+                asset_in,
+                amount_in,
+                asset_out,
+                limit_price,
+                swaps,
+            ),
+            ExecuteMsg::CancelLimitOrder { order_id } => {
+                execute_cancel_limit_order(&mut deps, env, info, order_id)
+            }
+            ExecuteMsg::ExecuteOrders { order_ids } => {
+                execute_execute_orders(&mut deps, env, order_ids)
+            }
+            ExecuteMsg::ExecuteLimitOrderInternal { order_id } => {
+                execute_limit_order_internal(&mut deps, env, info, order_id)
+            }
+            ExecuteMsg::AddLiquidityFromVirtualBalance {
+                pair,
+                token_1_liquidity,
+                token_2_liquidity,
+                slippage_tolerance,
+                recipient,
+            } => execute_add_liquidity_from_virtual_balance(
+                &mut deps,
+                env,
+                info,
+                pair,
+                token_1_liquidity,
+                token_2_liquidity,
+                slippage_tolerance,
+                recipient,
+            ),
             ExecuteMsg::IbcCallbackReceive { receive_msg } => {
                 ibc_receive_internal_call(&mut deps, env, info, receive_msg)
             }
@@ -157,14 +293,51 @@ pub fn execute(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::GetState {} => query_state(deps),
         QueryMsg::GetChain { chain_uid } => query_chain(deps, chain_uid),
         QueryMsg::GetAllChains {} => query_all_chains(deps),
         QueryMsg::GetVlp { pair } => query_vlp(deps, pair),
+        QueryMsg::PredictVlpAddress { pair } => query_predict_vlp_address(deps, env, pair),
         QueryMsg::GetAllVlps { pagination } => query_all_vlps(deps, pagination),
+        QueryMsg::GetVlpsByToken { token, pagination } => {
+            query_vlps_by_token(deps, token, pagination)
+        }
+        QueryMsg::GetMultiAssetVlp { tokens } => query_multi_asset_vlp(deps, tokens),
+        QueryMsg::GetAllMultiAssetVlps { pagination } => {
+            query_all_multi_asset_vlps(deps, pagination)
+        }
+        QueryMsg::GetSunsetVlps { pagination } => query_sunset_vlps(deps, pagination),
+        QueryMsg::GetLiquidityIntent { sender, intent_id } => {
+            query_liquidity_intent(deps, sender, intent_id)
+        }
+        QueryMsg::GetVslLpShares { user, pagination } => {
+            query_vsl_lp_shares(deps, user, pagination)
+        }
+        QueryMsg::GetDefaultFeeRecipients {} => query_default_fee_recipients(deps),
+        QueryMsg::GetPoolCreationMode { chain_uid } => query_pool_creation_mode(deps, chain_uid),
+        QueryMsg::GetUnclaimedVslLpFees { user, pair } => {
+            query_unclaimed_vsl_lp_fees(deps, user, pair)
+        }
+        QueryMsg::GetVlpMigrationStatus { pagination } => {
+            query_vlp_migration_status(deps, pagination)
+        }
+        QueryMsg::GetLimitOrder { order_id } => query_limit_order(deps, order_id),
+        QueryMsg::GetLimitOrders { pagination } => query_limit_orders(deps, pagination),
+        QueryMsg::GetUserLimitOrders { user, pagination } => {
+            query_user_limit_orders(deps, user, pagination)
+        }
         QueryMsg::SimulateSwap(msg) => query::query_simulate_swap(deps, msg),
+        QueryMsg::GetVolume {
+            chain_uid,
+            pagination,
+        } => query::query_volume(deps, chain_uid, pagination),
+        QueryMsg::GetLiquidityDepth {
+            asset_in,
+            asset_out,
+            price_move_bps,
+        } => query::query_liquidity_depth(deps, asset_in, asset_out, price_move_bps),
         QueryMsg::SimulateReleaseEscrow {
             token,
             amount,
@@ -174,6 +347,13 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             query_token_escrows(deps, token, pagination)
         }
         QueryMsg::QueryAllTokens { pagination } => query_all_tokens(deps, pagination),
+        QueryMsg::GetRetiringEscrows { pagination } => query_retiring_escrows(deps, pagination),
+        QueryMsg::GetFailedEscrowReleases { user, pagination } => {
+            query_failed_escrow_releases(deps, user, pagination)
+        }
+        QueryMsg::GetFailedLpMints { user, pagination } => {
+            query_failed_lp_mints(deps, user, pagination)
+        }
     }
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -204,6 +384,20 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
         IBC_ACK_AND_TIMEOUT_REPLY_ID => reply::on_ibc_ack_and_timeout_reply(deps, msg),
         IBC_RECEIVE_REPLY_ID => reply::on_ibc_receive_reply(deps, msg),
 
+        ESCROW_RELEASE_RETRY_REPLY_ID => reply::on_escrow_release_retry_reply(deps, msg),
+        VLP_MIGRATE_REPLY_ID => reply::on_vlp_migrate_reply(deps, msg),
+        VSL_ADD_LIQUIDITY_REPLY_ID => reply::on_vsl_add_liquidity_reply(deps, msg),
+        ADD_LIQUIDITY_LEG_REPLY_ID => reply::on_add_liquidity_leg_reply(deps, env, msg),
+        VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID => {
+            reply::on_virtual_balance_add_liquidity_reply(deps, env, msg)
+        }
+        VSL_REMOVE_LIQUIDITY_REPLY_ID => reply::on_vsl_remove_liquidity_reply(deps, env, msg),
+        LIMIT_ORDER_EXECUTE_REPLY_ID => reply::on_limit_order_execute_reply(deps, msg),
+        MULTI_VLP_INSTANTIATE_REPLY_ID => reply::on_multi_vlp_instantiate_reply(deps, msg),
+        POOL_CREATION_ADD_LIQUIDITY_REPLY_ID => {
+            reply::on_pool_creation_add_liquidity_reply(deps, msg)
+        }
+
         id => Err(ContractError::Std(StdError::generic_err(format!(
             "Unknown reply id: {}",
             id
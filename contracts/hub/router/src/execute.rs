@@ -1,18 +1,25 @@
 use cosmwasm_std::{
-    ensure, from_json, to_json_binary, Binary, CosmosMsg, DepsMut, Env, IbcMsg, IbcTimeout,
-    MessageInfo, Response, SubMsg, Uint128, WasmMsg,
+    ensure, from_json, to_json_binary, Addr, Binary, CosmosMsg, Decimal, DepsMut, Env, IbcMsg,
+    IbcTimeout, MessageInfo, Order, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
+use cw_storage_plus::Bound;
 use euclid::{
     chain::{Chain, ChainUid, CrossChainUser, CrossChainUserWithLimit},
     error::ContractError,
     events::{tx_event, TxType},
+    fee::{validate_fee_recipients, Fee, FeeRecipient},
     msgs::{
-        router::{ExecuteMsg, RegisterFactoryChainType},
+        router::{
+            ExecuteMsg, QuerySimulateSwap, RegisterFactoryChainType, SimulateSwapResponse,
+            VlpMigrationResult,
+        },
         virtual_balance::ExecuteBurn,
     },
+    pool::{get_pool_id, sort_pool_tokens, PoolCreationMode},
+    swap::{LimitOrder, NextSwapPair},
     timeout::get_timeout,
-    token::Token,
+    token::{Pair, Token},
     utils::generate_tx,
     virtual_balance::BalanceKey,
 };
@@ -20,9 +27,21 @@ use euclid_ibc::msg::{ChainIbcExecuteMsg, HubIbcExecuteMsg};
 
 use crate::{
     ibc::receive,
-    reply::VIRTUAL_BALANCE_BURN_REPLY_ID,
+    query::{get_unclaimed_vsl_lp_fees, query_simulate_swap, validate_swap_pairs},
+    reply::{
+        mint_lp_tokens_msg, LIMIT_ORDER_EXECUTE_REPLY_ID, MULTI_VLP_INSTANTIATE_REPLY_ID,
+        VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID, VIRTUAL_BALANCE_BURN_REPLY_ID,
+        VLP_MIGRATE_REPLY_ID, VSL_ADD_LIQUIDITY_REPLY_ID, VSL_REMOVE_LIQUIDITY_REPLY_ID,
+    },
     state::{
-        CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID, DEREGISTERED_CHAINS, ESCROW_BALANCES, STATE,
+        PendingEscrowRelease, VlpMigration, CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID,
+        DEFAULT_FEE_RECIPIENTS, DEREGISTERED_CHAINS, ESCROW_BALANCES, ESCROW_RELEASES_IN_FLIGHT,
+        FAILED_LP_MINTS, LIMIT_ORDERS, LIMIT_ORDER_COUNT, LIQUIDITY_INTENTS, MULTI_ASSET_POOLS,
+        MULTI_ASSET_POOL_IDS, MULTI_VLP_CODE_ID, PENDING_ESCROW_RELEASES,
+        PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY, PENDING_VSL_ADD_LIQUIDITY, POOL_CREATION_MODES,
+        RETIRING_ESCROWS, STATE, SUNSET_VLPS, USER_LIMIT_ORDERS, VLPS, VLP_GENERATIONS,
+        VLP_MIGRATION, VLP_MIGRATION_QUEUE, VLP_MIGRATION_RESULTS, VSL_LP_FEE_CHECKPOINTS,
+        VSL_LP_SHARES, VSL_UNCLAIMED_LP_FEES,
     },
 };
 
@@ -36,6 +55,11 @@ pub fn execute_update_vlp_code_id(
 
     ensure!(info.sender == state.admin, ContractError::Unauthorized {});
 
+    // Results of the previous code id would be mistaken for the new one
+    if state.vlp_code_id != new_vlp_code_id {
+        VLP_MIGRATION_RESULTS.clear(deps.storage);
+        VLP_MIGRATION.remove(deps.storage);
+    }
     state.vlp_code_id = new_vlp_code_id;
 
     STATE.save(deps.storage, &state)?;
@@ -45,6 +69,107 @@ pub fn execute_update_vlp_code_id(
         .add_attribute("new_vlp_code_id", new_vlp_code_id.to_string()))
 }
 
+pub fn execute_migrate_vlps(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    // Start over whenever the code id has changed since the last migration
+    let mut migration = VLP_MIGRATION
+        .may_load(deps.storage)?
+        .filter(|migration| migration.code_id == state.vlp_code_id)
+        .unwrap_or(VlpMigration {
+            code_id: state.vlp_code_id,
+            last_pair: None,
+            finished: false,
+            failed: 0,
+        });
+    ensure!(
+        !migration.finished || migration.failed > 0,
+        ContractError::new("All vlps are already migrated to the current code id")
+    );
+    // Vlps that failed, for example because their admin wasn't handed over yet, are picked up by a new pass
+    if migration.finished {
+        migration.last_pair = None;
+        migration.finished = false;
+        migration.failed = 0;
+    }
+
+    let limit = limit.unwrap_or(10);
+    ensure!(limit > 0, ContractError::new("Limit cannot be zero"));
+    let start = migration.last_pair.clone().map(Bound::exclusive);
+    let mut vlps = VLPS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize + 1)
+        .map(|item| item.map(|(pair, info)| (pair, info.vlp)))
+        .collect::<StdResult<Vec<((Token, Token), String)>>>()?;
+
+    // One vlp past the batch tells whether anything is left after it
+    migration.finished = vlps.len() as u64 <= limit;
+    vlps.truncate(limit as usize);
+
+    let mut queue = VLP_MIGRATION_QUEUE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let mut migrate_msgs: Vec<SubMsg> = vec![];
+    for (pair, vlp) in vlps {
+        migration.last_pair = Some(pair);
+
+        let contract_info = deps.querier.query_wasm_contract_info(vlp.clone())?;
+        if contract_info.code_id == migration.code_id {
+            VLP_MIGRATION_RESULTS.save(
+                deps.storage,
+                vlp.clone(),
+                &VlpMigrationResult {
+                    vlp,
+                    code_id: migration.code_id,
+                    success: true,
+                    error: None,
+                },
+            )?;
+            continue;
+        }
+        // Vlps instantiated with the router admin as their wasm admin have to be handed over to
+        // the router with MsgUpdateAdmin first, the router can't migrate them until then
+        if contract_info.admin.as_deref() != Some(env.contract.address.as_str()) {
+            VLP_MIGRATION_RESULTS.save(
+                deps.storage,
+                vlp.clone(),
+                &VlpMigrationResult {
+                    vlp,
+                    code_id: migration.code_id,
+                    success: false,
+                    error: Some("Router is not the wasm admin of the vlp".to_string()),
+                },
+            )?;
+            migration.failed += 1;
+            continue;
+        }
+
+        let msg = WasmMsg::Migrate {
+            contract_addr: vlp.clone(),
+            new_code_id: migration.code_id,
+            msg: to_json_binary(&euclid::msgs::vlp::MigrateMsg {})?,
+        };
+        migrate_msgs.push(SubMsg::reply_always(msg, VLP_MIGRATE_REPLY_ID));
+        queue.push(vlp);
+    }
+
+    VLP_MIGRATION_QUEUE.save(deps.storage, &queue)?;
+    VLP_MIGRATION.save(deps.storage, &migration)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate_vlps")
+        .add_attribute("code_id", migration.code_id.to_string())
+        .add_attribute("migrations", migrate_msgs.len().to_string())
+        .add_attribute("finished", migration.finished.to_string())
+        .add_submessages(migrate_msgs))
+}
+
 pub fn execute_update_lock(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
     let mut state = STATE.load(deps.storage)?;
     ensure!(info.sender == state.admin, ContractError::Unauthorized {});
@@ -132,6 +257,10 @@ pub fn execute_register_factory(
         chain_uid != vsl_chain_uid,
         ContractError::new("Cannot use VSL chain uid")
     );
+    ensure!(
+        chain_uid != ChainUid::locked_liquidity_chain_uid()?,
+        ContractError::new("Cannot use locked liquidity chain uid")
+    );
 
     // TODO: Add check for existing chain ids
     let state = STATE.load(deps.storage)?;
@@ -207,6 +336,10 @@ pub fn execute_update_factory_channel(
         chain_uid != vsl_chain_uid,
         ContractError::new("Cannot use VSL chain uid")
     );
+    ensure!(
+        chain_uid != ChainUid::locked_liquidity_chain_uid()?,
+        ContractError::new("Cannot use locked liquidity chain uid")
+    );
 
     let response = Response::new()
         .add_event(tx_event(
@@ -262,6 +395,7 @@ pub fn execute_withdraw_voucher(
             cross_chain_addresses,
             timeout,
             tx_id: tx_id.clone(),
+            original_tx_id: None,
         })?,
         funds: vec![],
     });
@@ -271,6 +405,42 @@ pub fn execute_withdraw_voucher(
         .add_attribute("method", "withdraw_voucher"))
 }
 
+// Removes a retiring escrow once its balance is withdrawn and no release to the chain is pending,
+// returns the message that removes the escrow from the factory
+pub fn finalize_escrow_retirement(
+    deps: &mut DepsMut,
+    env: &Env,
+    token: &Token,
+    chain_uid: &ChainUid,
+) -> Result<Option<SubMsg>, ContractError> {
+    let escrow_key = (token.clone(), chain_uid.clone());
+    let drained = ESCROW_BALANCES
+        .may_load(deps.storage, escrow_key.clone())?
+        .is_some_and(|balance| balance.is_zero());
+    let in_flight = ESCROW_RELEASES_IN_FLIGHT
+        .may_load(deps.storage, escrow_key.clone())?
+        .unwrap_or_default();
+    if !RETIRING_ESCROWS.has(deps.storage, escrow_key.clone()) || !drained || in_flight > 0 {
+        return Ok(None);
+    }
+    ESCROW_BALANCES.remove(deps.storage, escrow_key.clone());
+    RETIRING_ESCROWS.remove(deps.storage, escrow_key.clone());
+    ESCROW_RELEASES_IN_FLIGHT.remove(deps.storage, escrow_key);
+
+    let chain = CHAIN_UID_TO_CHAIN.load(deps.storage, chain_uid.clone())?;
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: env.contract.address.to_string(),
+    };
+    let msg = HubIbcExecuteMsg::RetireEscrow {
+        chain_uid: chain_uid.clone(),
+        token: token.clone(),
+        tx_id: generate_tx(deps.branch(), env, &sender)?,
+    }
+    .to_msg(deps, env, chain, get_timeout(None)?)?;
+    Ok(Some(msg))
+}
+
 pub fn execute_release_escrow(
     deps: &mut DepsMut,
     env: Env,
@@ -282,6 +452,7 @@ pub fn execute_release_escrow(
     cross_chain_addresses: Vec<CrossChainUserWithLimit>,
     timeout: Option<u64>,
     tx_id: String,
+    original_tx_id: Option<String>,
 ) -> Result<Response, ContractError> {
     let state = STATE.load(deps.storage)?;
     ensure!(
@@ -318,7 +489,11 @@ pub fn execute_release_escrow(
             sender.address.as_str(),
             TxType::EscrowRelease,
         ))
-        .add_attribute("tx_id", tx_id);
+        .add_attribute("tx_id", tx_id.clone());
+    if let Some(original_tx_id) = &original_tx_id {
+        response = response.add_attribute("original_tx_id", original_tx_id);
+    }
+    let original_tx_id = original_tx_id.unwrap_or(tx_id);
 
     let timeout = get_timeout(timeout)?;
     let mut release_msgs: Vec<SubMsg> = vec![];
@@ -354,17 +529,35 @@ pub fn execute_release_escrow(
         }
 
         escrow_key.save(deps.storage, &escrow_balance.checked_sub(release_amount)?)?;
+        ESCROW_RELEASES_IN_FLIGHT.update(
+            deps.storage,
+            (token.clone(), cross_chain_address.user.chain_uid.clone()),
+            |count| -> StdResult<_> { Ok(count.unwrap_or_default() + 1) },
+        )?;
 
         transfer_amount = transfer_amount.checked_add(release_amount)?;
 
+        // We can't use same tx id because it might conflict with pending requests on receiving chain
+        let release_tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+        // Keep the remaining addresses so that a failed release can be retried on the next one
+        PENDING_ESCROW_RELEASES.save(
+            deps.storage,
+            release_tx_id.clone(),
+            &PendingEscrowRelease {
+                cross_chain_addresses: cross_chain_addresses_iterator.clone().collect(),
+                timeout,
+                original_tx_id: Some(original_tx_id.clone()),
+            },
+        )?;
+
         // Prepare IBC Release Message
         let send_msg = HubIbcExecuteMsg::ReleaseEscrow {
             sender: sender.clone(),
             amount: release_amount,
             token: token.clone(),
             to_address: cross_chain_address.user.address.clone(),
-            // We can't use same tx id because it might conflict with pending requests on receiving chain
-            tx_id: generate_tx(deps.branch(), &env, &sender)?,
+            tx_id: release_tx_id,
             chain_uid: cross_chain_address.user.chain_uid.clone(),
         }
         .to_msg(deps, &env, chain, timeout)?;
@@ -431,3 +624,1184 @@ pub fn execute_native_receive_callback(
     ensure!(chain.factory == info.sender, ContractError::Unauthorized {});
     receive::reusable_internal_call(deps, env, info, msg, chain_uid)
 }
+
+pub fn execute_sunset_vlp(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Pair,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    let vlp_address = VLPS
+        .load(deps.storage, pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+    ensure!(
+        !SUNSET_VLPS.has(deps.storage, vlp_address.clone()),
+        ContractError::PoolSunset {}
+    );
+    SUNSET_VLPS.save(deps.storage, vlp_address.clone(), &pair)?;
+
+    let sunset_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: vlp_address.clone(),
+        msg: to_json_binary(&euclid::msgs::vlp::ExecuteMsg::Sunset {})?,
+        funds: vec![],
+    });
+
+    let response = Response::new()
+        .add_message(sunset_msg)
+        .add_attribute("method", "sunset_vlp")
+        .add_attribute("vlp_address", vlp_address.clone());
+
+    // A pool without withdrawable liquidity can be delisted right away
+    let liquidity: euclid::msgs::vlp::GetLiquidityResponse = deps.querier.query_wasm_smart(
+        vlp_address.clone(),
+        &euclid::msgs::vlp::QueryMsg::Liquidity {},
+    )?;
+    if liquidity.total_lp_tokens > liquidity.locked_lp_tokens {
+        return Ok(response);
+    }
+    let delist_response = delist_vlp(deps, &env, vlp_address, pair)?;
+    Ok(response
+        .add_submessages(delist_response.messages)
+        .add_events(delist_response.events)
+        .add_attributes(delist_response.attributes))
+}
+
+// Removes a sunset vlp from the router and notifies every registered factory to drop the pool
+pub fn delist_vlp(
+    deps: &mut DepsMut,
+    env: &Env,
+    vlp_address: String,
+    pair: Pair,
+) -> Result<Response, ContractError> {
+    VLPS.remove(deps.storage, pair.get_tupple())?;
+    // The next vlp of the pair needs a new salt, the delisted vlp keeps its address
+    VLP_GENERATIONS.update(deps.storage, pair.get_tupple(), |generation| {
+        Ok::<_, ContractError>(generation.unwrap_or_default() + 1)
+    })?;
+    SUNSET_VLPS.remove(deps.storage, vlp_address.clone());
+
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: env.contract.address.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), env, &sender)?;
+
+    let chains = CHAIN_UID_TO_CHAIN
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(ChainUid, Chain)>>>()?;
+
+    let deregistered_chains = DEREGISTERED_CHAINS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    let timeout = get_timeout(None)?;
+    let mut delist_msgs: Vec<SubMsg> = vec![];
+    for (chain_uid, chain) in chains {
+        // Deregistered factories can't receive packets anymore
+        if deregistered_chains.contains(&chain_uid) {
+            continue;
+        }
+        // Each factory gets its own tx id so that acks don't conflict
+        let delist_tx_id = generate_tx(deps.branch(), env, &sender)?;
+        let msg = HubIbcExecuteMsg::DelistPool {
+            chain_uid,
+            pair: pair.clone(),
+            vlp_address: vlp_address.clone(),
+            tx_id: delist_tx_id,
+        }
+        .to_msg(deps, env, chain, timeout)?;
+        delist_msgs.push(msg);
+    }
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            env.contract.address.as_str(),
+            TxType::PoolDelisting,
+        ))
+        .add_attribute("delisted_vlp", vlp_address)
+        .add_attribute("delisted_pair", format!("{pair:?}"))
+        .add_submessages(delist_msgs))
+}
+
+pub fn execute_vsl_swap(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_in: Token,
+    amount_in: Uint128,
+    asset_out: Token,
+    min_amount_out: Uint128,
+    swaps: Vec<NextSwapPair>,
+) -> Result<Response, ContractError> {
+    let first_swap = swaps
+        .first()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+    let last_swap = swaps
+        .last()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+    ensure!(
+        first_swap.token_in == asset_in,
+        ContractError::new("Asset IN doen't match router")
+    );
+    ensure!(
+        last_swap.token_out == asset_out,
+        ContractError::new("Asset OUT doen't match router")
+    );
+
+    let swap_vlps = validate_swap_pairs(deps.as_ref(), &swaps)
+        .map_err(|_err| ContractError::new("VLPS listed in swaps are not registered"))?;
+    let (first_swap, next_swaps) = swap_vlps
+        .split_first()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    // Move funds from the sender to the first vlp, vlp credits the output back to the sender
+    let transfer_msg = asset_in.create_virtual_balance_transfer_msg(
+        virtual_balance_address,
+        amount_in,
+        sender.clone(),
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: first_swap.vlp_address.clone(),
+        },
+    )?;
+
+    let swap_msg = euclid::msgs::vlp::ExecuteMsg::Swap {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        asset_in: asset_in.clone(),
+        amount_in,
+        min_token_out: min_amount_out,
+        next_swaps: next_swaps.to_vec(),
+        test_fail: first_swap.test_fail,
+        asset_out: first_swap.asset_out.clone(),
+    };
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: first_swap.vlp_address.clone(),
+        msg: to_json_binary(&swap_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(&tx_id, &sender.to_sender_string(), TxType::Swap))
+        .add_attribute("method", "vsl_swap")
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("asset_in", asset_in.to_string())
+        .add_attribute("amount_in", amount_in)
+        .add_attribute("asset_out", asset_out.to_string())
+        .add_message(transfer_msg)
+        .add_message(swap_msg))
+}
+
+pub fn execute_vsl_add_liquidity(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Pair,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+) -> Result<Response, ContractError> {
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    let (vlp_address, liquidity_msgs) = vsl_add_liquidity_msgs(
+        deps,
+        sender.clone(),
+        sender.clone(),
+        pair,
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+        tx_id.clone(),
+        VSL_ADD_LIQUIDITY_REPLY_ID,
+    )?;
+    PENDING_VSL_ADD_LIQUIDITY.save(deps.storage, vlp_address, &info.sender)?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("method", "vsl_add_liquidity")
+        .add_attribute("tx_id", tx_id)
+        .add_submessages(liquidity_msgs))
+}
+
+// Moves the sender's virtual balance into the vlp, the lp shares are accounted to the lp owner's chain
+#[allow(clippy::too_many_arguments)]
+fn vsl_add_liquidity_msgs(
+    deps: &mut DepsMut,
+    sender: CrossChainUser,
+    lp_owner: CrossChainUser,
+    pair: Pair,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+    tx_id: String,
+    reply_id: u64,
+) -> Result<(String, Vec<SubMsg>), ContractError> {
+    ensure!(
+        !(token_1_liquidity.is_zero() || token_2_liquidity.is_zero()),
+        ContractError::ZeroAssetAmount {}
+    );
+    let vlp_address = VLPS
+        .load(deps.storage, pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+    ensure!(
+        !SUNSET_VLPS.has(deps.storage, vlp_address.clone()),
+        ContractError::PoolSunset {}
+    );
+    // Lp shares are withdrawn through the escrows of the lp owner's chain
+    for token in pair.get_vec_token() {
+        ensure!(
+            !RETIRING_ESCROWS.has(deps.storage, (token, lp_owner.chain_uid.clone())),
+            ContractError::EscrowRetiring {}
+        );
+    }
+
+    let pool_liquidity: euclid::msgs::vlp::GetLiquidityResponse = deps.querier.query_wasm_smart(
+        vlp_address.clone(),
+        &euclid::msgs::vlp::QueryMsg::Liquidity {},
+    )?;
+
+    // Liquidity amounts follow the token order of the vlp
+    let (token_1_liquidity, token_2_liquidity) = if pool_liquidity.pair.token_1 == pair.token_1 {
+        (token_1_liquidity, token_2_liquidity)
+    } else {
+        (token_2_liquidity, token_1_liquidity)
+    };
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+    let vlp = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: vlp_address.clone(),
+    };
+
+    let mut msgs = vec![];
+    // Register the lp owner's chain on the vlp on first use
+    let chain_pool: Result<euclid::msgs::vlp::PoolResponse, _> = deps.querier.query_wasm_smart(
+        vlp_address.clone(),
+        &euclid::msgs::vlp::QueryMsg::Pool {
+            chain_uid: lp_owner.chain_uid.clone(),
+        },
+    );
+    if chain_pool.is_err() {
+        msgs.push(SubMsg::new(WasmMsg::Execute {
+            contract_addr: vlp_address.clone(),
+            msg: to_json_binary(&euclid::msgs::vlp::ExecuteMsg::RegisterPool {
+                sender: lp_owner.clone(),
+                pair: pool_liquidity.pair.clone(),
+                tx_id: tx_id.clone(),
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    let token_1_transfer_msg = pool_liquidity
+        .pair
+        .token_1
+        .create_virtual_balance_transfer_msg(
+            virtual_balance_address.clone(),
+            token_1_liquidity,
+            sender.clone(),
+            vlp.clone(),
+        )?;
+    let token_2_transfer_msg = pool_liquidity
+        .pair
+        .token_2
+        .create_virtual_balance_transfer_msg(
+            virtual_balance_address,
+            token_2_liquidity,
+            sender,
+            vlp,
+        )?;
+
+    let add_liquidity_msg = euclid::msgs::vlp::ExecuteMsg::AddLiquidity {
+        sender: lp_owner,
+        tx_id,
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+    };
+    let add_liquidity_msg = WasmMsg::Execute {
+        contract_addr: vlp_address.clone(),
+        msg: to_json_binary(&add_liquidity_msg)?,
+        funds: vec![],
+    };
+
+    msgs.push(SubMsg::new(token_1_transfer_msg));
+    msgs.push(SubMsg::new(token_2_transfer_msg));
+    msgs.push(SubMsg::reply_on_success(add_liquidity_msg, reply_id));
+    Ok((vlp_address, msgs))
+}
+
+pub fn execute_vsl_remove_liquidity(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Pair,
+    lp_allocation: Uint128,
+) -> Result<Response, ContractError> {
+    let vlp_address = VLPS
+        .load(deps.storage, pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+
+    let shares_key = VSL_LP_SHARES.key((info.sender.clone(), vlp_address.clone()));
+    let shares = shares_key.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(
+        shares.ge(&lp_allocation),
+        ContractError::InsufficientFunds {}
+    );
+    settle_vsl_lp_fees(deps, &info.sender, &vlp_address)?;
+    let remaining_shares = shares.checked_sub(lp_allocation)?;
+    if remaining_shares.is_zero() {
+        shares_key.remove(deps.storage);
+    } else {
+        shares_key.save(deps.storage, &remaining_shares)?;
+    }
+
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    // Vlp transfers the released liquidity to the sender's virtual balance
+    let remove_liquidity_msg = euclid::msgs::vlp::ExecuteMsg::RemoveLiquidity {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        lp_allocation,
+    };
+    let remove_liquidity_msg = WasmMsg::Execute {
+        contract_addr: vlp_address,
+        msg: to_json_binary(&remove_liquidity_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::RemoveLiquidity,
+        ))
+        .add_attribute("method", "vsl_remove_liquidity")
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("lp_allocation", lp_allocation)
+        .add_submessage(SubMsg::reply_on_success(
+            remove_liquidity_msg,
+            VSL_REMOVE_LIQUIDITY_REPLY_ID,
+        )))
+}
+
+pub fn execute_refund_liquidity_intent(
+    deps: &mut DepsMut,
+    env: Env,
+    sender: CrossChainUser,
+    intent_id: String,
+) -> Result<Response, ContractError> {
+    let intent_key = LIQUIDITY_INTENTS.key((sender.to_sender_string(), intent_id.clone()));
+    let intent = intent_key
+        .load(deps.storage)
+        .map_err(|_err| ContractError::new("Liquidity intent doesn't exist"))?;
+    ensure!(
+        intent.deadline <= env.block.time.seconds(),
+        ContractError::new("Liquidity intent deadline has not passed")
+    );
+    intent_key.remove(deps.storage);
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    // Hand the held leg back to the depositor and release it from the escrow it came from
+    let transfer_msg = intent.token.create_virtual_balance_transfer_msg(
+        virtual_balance_address,
+        intent.amount,
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: env.contract.address.to_string(),
+        },
+        intent.sender.clone(),
+    )?;
+
+    let tx_id = generate_tx(deps.branch(), &env, &intent.sender)?;
+    let release_msg = ExecuteMsg::ReleaseEscrowInternal {
+        sender: intent.sender.clone(),
+        token: intent.token.clone(),
+        amount: Some(intent.amount),
+        cross_chain_addresses: vec![CrossChainUserWithLimit {
+            user: intent.sender.clone(),
+            limit: None,
+        }],
+        timeout: None,
+        tx_id: tx_id.clone(),
+        original_tx_id: None,
+    };
+    let release_msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_json_binary(&release_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &intent.sender.to_sender_string(),
+            TxType::EscrowRelease,
+        ))
+        .add_attribute("method", "refund_liquidity_intent")
+        .add_attribute("intent_id", intent_id)
+        .add_attribute("token", intent.token.to_string())
+        .add_attribute("amount", intent.amount)
+        .add_message(transfer_msg)
+        .add_message(release_msg))
+}
+
+// Anyone can retry, the lp tokens only go to the recipient of the failed mint
+pub fn execute_retry_lp_mint(
+    deps: &mut DepsMut,
+    env: Env,
+    recipient: CrossChainUser,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let failed_key = FAILED_LP_MINTS.key((
+        recipient.chain_uid.clone(),
+        recipient.address.clone(),
+        tx_id.clone(),
+    ));
+    let failed = failed_key
+        .load(deps.storage)
+        .map_err(|_err| ContractError::new("Failed lp mint doesn't exist"))?;
+    failed_key.remove(deps.storage);
+
+    let mint_msg = mint_lp_tokens_msg(
+        deps,
+        &env,
+        &failed.recipient,
+        failed.vlp_address.clone(),
+        failed.amount,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "retry_lp_mint")
+        .add_attribute("failed_tx_id", tx_id)
+        .add_attribute("recipient", failed.recipient.to_sender_string())
+        .add_attribute("vlp_address", failed.vlp_address)
+        .add_attribute("amount", failed.amount)
+        .add_submessage(mint_msg))
+}
+
+pub fn execute_add_liquidity_from_virtual_balance(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Pair,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+    recipient: CrossChainUser,
+) -> Result<Response, ContractError> {
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    add_liquidity_from_virtual_balance(
+        deps,
+        sender,
+        pair,
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+        recipient,
+        tx_id,
+    )
+}
+
+// Shared by the hub and factory paths, moves the sender's virtual balance into the vlp
+pub fn add_liquidity_from_virtual_balance(
+    deps: &mut DepsMut,
+    sender: CrossChainUser,
+    pair: Pair,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+    recipient: CrossChainUser,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        CHAIN_UID_TO_CHAIN.has(deps.storage, recipient.chain_uid.clone()),
+        ContractError::new("Recipient chain is not registered")
+    );
+
+    // Lp shares are accounted to the recipient chain on the vlp
+    let (vlp_address, liquidity_msgs) = vsl_add_liquidity_msgs(
+        deps,
+        sender.clone(),
+        recipient.clone(),
+        pair,
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+        tx_id.clone(),
+        VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID,
+    )?;
+    PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY.save(deps.storage, vlp_address, &recipient)?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("method", "add_liquidity_from_virtual_balance")
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("recipient", recipient.to_sender_string())
+        .add_submessages(liquidity_msgs))
+}
+
+pub fn execute_update_pool_creation_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    chain_uid: ChainUid,
+    mode: PoolCreationMode,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+    ensure!(
+        CHAIN_UID_TO_CHAIN.has(deps.storage, chain_uid.clone()),
+        ContractError::new("Chain is not registered")
+    );
+
+    let mode_name = match &mode {
+        PoolCreationMode::Open => {
+            POOL_CREATION_MODES.remove(deps.storage, chain_uid.clone());
+            "open"
+        }
+        PoolCreationMode::Allowlist { creators } => {
+            ensure!(
+                !creators.is_empty(),
+                ContractError::new("Allowlist should have at least one creator")
+            );
+            POOL_CREATION_MODES.save(deps.storage, chain_uid.clone(), &mode)?;
+            "allowlist"
+        }
+        PoolCreationMode::AdminOnly { admin } => {
+            ensure!(
+                !admin.is_empty(),
+                ContractError::new("Admin address cannot be empty")
+            );
+            POOL_CREATION_MODES.save(deps.storage, chain_uid.clone(), &mode)?;
+            "admin_only"
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "update_pool_creation_mode")
+        .add_attribute("chain_uid", chain_uid.to_string())
+        .add_attribute("mode", mode_name))
+}
+
+pub fn execute_update_default_fee_recipients(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipients: Option<Vec<FeeRecipient>>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    match &recipients {
+        Some(recipients) => {
+            validate_fee_recipients(recipients)?;
+            DEFAULT_FEE_RECIPIENTS.save(deps.storage, recipients)?;
+        }
+        None => DEFAULT_FEE_RECIPIENTS.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_default_fee_recipients")
+        .add_attribute(
+            "recipients",
+            recipients
+                .map_or(0, |recipients| recipients.len())
+                .to_string(),
+        ))
+}
+
+pub fn execute_claim_vsl_lp_fees(
+    deps: &mut DepsMut,
+    info: MessageInfo,
+    pair: Pair,
+) -> Result<Response, ContractError> {
+    let vlp_address = VLPS
+        .load(deps.storage, pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+
+    let (token_1_fees, token_2_fees) = settle_vsl_lp_fees(deps, &info.sender, &vlp_address)?;
+    ensure!(
+        !token_1_fees.is_zero() || !token_2_fees.is_zero(),
+        ContractError::new("No lp fees to claim")
+    );
+    VSL_UNCLAIMED_LP_FEES.remove(deps.storage, (info.sender.clone(), vlp_address.clone()));
+
+    let recipient = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let claim_msg = WasmMsg::Execute {
+        contract_addr: vlp_address.clone(),
+        msg: to_json_binary(&euclid::msgs::vlp::ExecuteMsg::ClaimLpFees {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            recipient,
+            amounts: Some((token_1_fees, token_2_fees)),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "claim_vsl_lp_fees")
+        .add_attribute("sender", info.sender)
+        .add_attribute("vlp_address", vlp_address)
+        .add_attribute("token_1_fees", token_1_fees)
+        .add_attribute("token_2_fees", token_2_fees)
+        .add_message(claim_msg))
+}
+
+// Moves what a hub lp's shares earned since the last settlement into its unclaimed fees and returns
+// them, has to run before its lp shares change
+pub fn settle_vsl_lp_fees(
+    deps: &mut DepsMut,
+    user: &Addr,
+    vlp_address: &str,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let (fee_growth, unclaimed) = get_unclaimed_vsl_lp_fees(deps.as_ref(), user, vlp_address)?;
+    let key = (user.clone(), vlp_address.to_string());
+    VSL_LP_FEE_CHECKPOINTS.save(
+        deps.storage,
+        key.clone(),
+        &(fee_growth.token_1_fee_growth, fee_growth.token_2_fee_growth),
+    )?;
+    VSL_UNCLAIMED_LP_FEES.save(deps.storage, key, &unclaimed)?;
+    Ok(unclaimed)
+}
+
+pub fn execute_place_limit_order(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_in: Token,
+    amount_in: Uint128,
+    asset_out: Token,
+    limit_price: Decimal,
+    swaps: Vec<NextSwapPair>,
+) -> Result<Response, ContractError> {
+    ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
+    ensure!(
+        !limit_price.is_zero(),
+        ContractError::new("Limit price cannot be zero")
+    );
+    let first_swap = swaps
+        .first()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+    let last_swap = swaps
+        .last()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+    ensure!(
+        first_swap.token_in == asset_in,
+        ContractError::new("Asset IN doen't match router")
+    );
+    ensure!(
+        last_swap.token_out == asset_out,
+        ContractError::new("Asset OUT doen't match router")
+    );
+    validate_swap_pairs(deps.as_ref(), &swaps)
+        .map_err(|_err| ContractError::new("VLPS listed in swaps are not registered"))?;
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    let order_id = LIMIT_ORDER_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(1)
+        .ok_or(ContractError::new("Limit order count overflow"))?;
+    LIMIT_ORDER_COUNT.save(deps.storage, &order_id)?;
+
+    let order = LimitOrder {
+        owner: info.sender.clone(),
+        asset_in: asset_in.clone(),
+        amount_in,
+        asset_out: asset_out.clone(),
+        limit_price,
+        swaps,
+        created_at: env.block.time.seconds(),
+    };
+    LIMIT_ORDERS.save(deps.storage, order_id, &order)?;
+    USER_LIMIT_ORDERS.save(deps.storage, (info.sender.clone(), order_id), &())?;
+
+    // Hold the order amount in the router until it is filled or cancelled
+    let escrow_msg = asset_in.create_virtual_balance_transfer_msg(
+        virtual_balance_address,
+        amount_in,
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: info.sender.to_string(),
+        },
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: env.contract.address.to_string(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "place_limit_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("owner", info.sender)
+        .add_attribute("asset_in", asset_in.to_string())
+        .add_attribute("amount_in", amount_in)
+        .add_attribute("asset_out", asset_out.to_string())
+        .add_attribute("limit_price", limit_price.to_string())
+        .add_message(escrow_msg))
+}
+
+pub fn execute_cancel_limit_order(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = LIMIT_ORDERS
+        .load(deps.storage, order_id)
+        .map_err(|_err| ContractError::new("Limit order doesn't exist"))?;
+    ensure!(order.owner == info.sender, ContractError::Unauthorized {});
+    LIMIT_ORDERS.remove(deps.storage, order_id);
+    USER_LIMIT_ORDERS.remove(deps.storage, (order.owner.clone(), order_id));
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    let refund_msg = order.asset_in.create_virtual_balance_transfer_msg(
+        virtual_balance_address,
+        order.amount_in,
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: env.contract.address.to_string(),
+        },
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: order.owner.to_string(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel_limit_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("owner", order.owner)
+        .add_attribute("refund_amount", order.amount_in)
+        .add_message(refund_msg))
+}
+
+pub fn execute_execute_orders(
+    deps: &mut DepsMut,
+    env: Env,
+    order_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut response = Response::new().add_attribute("method", "execute_orders");
+    let mut fillable = 0;
+    for order_id in order_ids {
+        // Orders already filled or cancelled by someone else are skipped instead of failing the batch
+        let Some(order) = LIMIT_ORDERS.may_load(deps.storage, order_id)? else {
+            response = response.add_attribute("skipped_order", order_id.to_string());
+            continue;
+        };
+        let simulate_msg = QuerySimulateSwap {
+            asset_in: order.asset_in.clone(),
+            amount_in: order.amount_in,
+            asset_out: order.asset_out.clone(),
+            min_amount_out: Uint128::zero(),
+            swaps: order.swaps.clone(),
+        };
+        let amount_out = query_simulate_swap(deps.as_ref(), simulate_msg)
+            .and_then(|res| Ok(from_json::<SimulateSwapResponse>(res)?.amount_out));
+        let fills = match amount_out {
+            Ok(amount_out) => amount_out >= order.min_amount_out()?,
+            Err(_) => false,
+        };
+        if !fills {
+            response = response.add_attribute("skipped_order", order_id.to_string());
+            continue;
+        }
+
+        let execute_msg = WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::ExecuteLimitOrderInternal { order_id })?,
+            funds: vec![],
+        };
+        fillable += 1;
+        response = response
+            .add_attribute("executed_order", order_id.to_string())
+            .add_submessage(SubMsg::reply_on_error(
+                execute_msg,
+                LIMIT_ORDER_EXECUTE_REPLY_ID,
+            ));
+    }
+    ensure!(
+        fillable > 0,
+        ContractError::new("None of the limit orders can be executed")
+    );
+    Ok(response)
+}
+
+pub fn execute_limit_order_internal(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    let order = LIMIT_ORDERS.load(deps.storage, order_id)?;
+    LIMIT_ORDERS.remove(deps.storage, order_id);
+    USER_LIMIT_ORDERS.remove(deps.storage, (order.owner.clone(), order_id));
+
+    let swap_vlps = validate_swap_pairs(deps.as_ref(), &order.swaps)?;
+    let (first_swap, next_swaps) = swap_vlps
+        .split_first()
+        .ok_or(ContractError::new("Swaps cannot be empty"))?;
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    let owner = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: order.owner.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &owner)?;
+
+    // Escrowed amount goes to the first vlp, the vlp credits the output to the order owner
+    let transfer_msg = order.asset_in.create_virtual_balance_transfer_msg(
+        virtual_balance_address,
+        order.amount_in,
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: env.contract.address.to_string(),
+        },
+        CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid()?,
+            address: first_swap.vlp_address.clone(),
+        },
+    )?;
+
+    let swap_msg = euclid::msgs::vlp::ExecuteMsg::Swap {
+        sender: owner.clone(),
+        tx_id: tx_id.clone(),
+        asset_in: order.asset_in.clone(),
+        amount_in: order.amount_in,
+        min_token_out: order.min_amount_out()?,
+        next_swaps: next_swaps.to_vec(),
+        test_fail: first_swap.test_fail,
+        asset_out: first_swap.asset_out.clone(),
+    };
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: first_swap.vlp_address.clone(),
+        msg: to_json_binary(&swap_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(&tx_id, &owner.to_sender_string(), TxType::Swap))
+        .add_attribute("method", "execute_limit_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("tx_id", tx_id)
+        .add_message(transfer_msg)
+        .add_message(swap_msg))
+}
+
+// Function to update the multi asset pool code ID
+pub fn execute_update_multi_vlp_code_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_code_id: u64,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    MULTI_VLP_CODE_ID.save(deps.storage, &new_code_id)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_multi_vlp_code_id")
+        .add_attribute("new_code_id", new_code_id.to_string()))
+}
+
+pub fn execute_create_multi_asset_pool(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tokens: Vec<Token>,
+) -> Result<Response, ContractError> {
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    create_multi_asset_pool(deps, env, sender, tokens, tx_id)
+}
+
+// Checks the pool creation mode of the sender's chain, shared by pair and multi asset pools
+pub fn ensure_pool_creation_allowed(
+    storage: &dyn Storage,
+    sender: &CrossChainUser,
+) -> Result<(), ContractError> {
+    let mode = POOL_CREATION_MODES
+        .may_load(storage, sender.chain_uid.clone())?
+        .unwrap_or_default();
+    ensure!(
+        mode.is_allowed(&sender.address),
+        ContractError::PoolCreationNotAllowed {
+            creator: sender.address.clone(),
+            chain_uid: sender.chain_uid.to_string(),
+        }
+    );
+    Ok(())
+}
+
+// Instantiates a multi asset pool, the pool is indexed for routing in the instantiate reply
+pub fn create_multi_asset_pool(
+    deps: &mut DepsMut,
+    env: Env,
+    sender: CrossChainUser,
+    tokens: Vec<Token>,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    ensure_pool_creation_allowed(deps.storage, &sender)?;
+    let tokens = sort_pool_tokens(tokens)?;
+    ensure!(
+        !MULTI_ASSET_POOL_IDS.has(deps.storage, get_pool_id(&tokens)),
+        ContractError::new("Multi asset pool already exists")
+    );
+    // Liquidity comes from virtual balances, so every token needs an escrow on some chain
+    for token in tokens.iter() {
+        let has_escrow = ESCROW_BALANCES
+            .prefix(token.clone())
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        ensure!(
+            has_escrow,
+            ContractError::new("Multi asset pool tokens need to be registered first")
+        );
+    }
+
+    let state = STATE.load(deps.storage)?;
+    let code_id = MULTI_VLP_CODE_ID
+        .may_load(deps.storage)?
+        .ok_or(ContractError::new("Multi asset pool code id is not set"))?;
+    let instantiate_msg = euclid::msgs::multi_vlp::InstantiateMsg {
+        router: env.contract.address.to_string(),
+        virtual_balance: state
+            .virtual_balance_address
+            .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+            .into_string(),
+        tokens,
+        fee: Fee {
+            lp_fee_bps: 10,
+            euclid_fee_bps: 10,
+            recipient: CrossChainUser {
+                address: state.admin.clone(),
+                chain_uid: ChainUid::vsl_chain_uid()?,
+            },
+        },
+        admin: state.admin,
+    };
+    // Router is the wasm admin, same as for two token vlps
+    let msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id,
+        msg: to_json_binary(&instantiate_msg)?,
+        funds: vec![],
+        label: "Multi Asset VLP".to_string(),
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::PoolCreation,
+        ))
+        .add_attribute("method", "create_multi_asset_pool")
+        .add_attribute("tx_id", tx_id)
+        .add_submessage(SubMsg::reply_on_success(
+            msg,
+            MULTI_VLP_INSTANTIATE_REPLY_ID,
+        )))
+}
+
+pub fn execute_vsl_add_multi_asset_liquidity(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vlp_address: String,
+    liquidity: Vec<(Token, Uint128)>,
+    min_lp_allocation: Uint128,
+) -> Result<Response, ContractError> {
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    add_multi_asset_liquidity(
+        deps,
+        sender,
+        vlp_address,
+        liquidity,
+        min_lp_allocation,
+        tx_id,
+    )
+}
+
+// Moves the liquidity from the sender's virtual balance to the pool, the pool holds the lp shares
+pub fn add_multi_asset_liquidity(
+    deps: &mut DepsMut,
+    sender: CrossChainUser,
+    vlp_address: String,
+    liquidity: Vec<(Token, Uint128)>,
+    min_lp_allocation: Uint128,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MULTI_ASSET_POOLS.has(deps.storage, vlp_address.clone()),
+        ContractError::PoolDoesNotExists {}
+    );
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+    let vlp = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: vlp_address.clone(),
+    };
+
+    let mut response = Response::new();
+    for (token, amount) in liquidity.iter() {
+        response = response.add_message(token.create_virtual_balance_transfer_msg(
+            virtual_balance_address.clone(),
+            *amount,
+            sender.clone(),
+            vlp.clone(),
+        )?);
+    }
+
+    let add_liquidity_msg = euclid::msgs::multi_vlp::ExecuteMsg::AddLiquidity {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        liquidity,
+        min_lp_allocation,
+    };
+
+    Ok(response
+        .add_message(WasmMsg::Execute {
+            contract_addr: vlp_address.clone(),
+            msg: to_json_binary(&add_liquidity_msg)?,
+            funds: vec![],
+        })
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("method", "add_multi_asset_liquidity")
+        .add_attribute("vlp", vlp_address)
+        .add_attribute("tx_id", tx_id))
+}
+
+pub fn execute_vsl_remove_multi_asset_liquidity(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vlp_address: String,
+    lp_allocation: Uint128,
+) -> Result<Response, ContractError> {
+    let sender = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: info.sender.to_string(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    remove_multi_asset_liquidity(deps, sender, vlp_address, lp_allocation, tx_id)
+}
+
+// The pool burns the sender's lp shares and releases the liquidity to the sender's virtual balance
+pub fn remove_multi_asset_liquidity(
+    deps: &mut DepsMut,
+    sender: CrossChainUser,
+    vlp_address: String,
+    lp_allocation: Uint128,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MULTI_ASSET_POOLS.has(deps.storage, vlp_address.clone()),
+        ContractError::PoolDoesNotExists {}
+    );
+    let remove_liquidity_msg = euclid::msgs::multi_vlp::ExecuteMsg::RemoveLiquidity {
+        sender: sender.clone(),
+        tx_id: tx_id.clone(),
+        lp_allocation,
+    };
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: vlp_address.clone(),
+            msg: to_json_binary(&remove_liquidity_msg)?,
+            funds: vec![],
+        })
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::RemoveLiquidity,
+        ))
+        .add_attribute("method", "remove_multi_asset_liquidity")
+        .add_attribute("vlp", vlp_address)
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("lp_allocation", lp_allocation))
+}
@@ -7,17 +7,28 @@ use cosmwasm_std::{
 use cosmwasm_std::{to_json_binary, IbcAcknowledgement};
 use euclid::chain::{Chain, ChainType, ChainUid, CrossChainUser};
 use euclid::error::ContractError;
+use euclid::escrow::FailedEscrowRelease;
 use euclid::events::{tx_event, TxType};
-use euclid::msgs::factory::{RegisterFactoryResponse, ReleaseEscrowResponse};
+use euclid::liquidity::FailedLpMint;
+use euclid::msgs::factory::{
+    DelistPoolResponse, MintLpTokensResponse, RegisterFactoryResponse, ReleaseEscrowResponse,
+};
 use euclid::msgs::router::ExecuteMsg;
 use euclid::msgs::virtual_balance::{ExecuteMint, ExecuteMsg as VirtualBalanceExecuteMsg};
+use euclid::pool::EscrowRetirementResponse;
 use euclid::token::Token;
+use euclid::utils::generate_tx;
 use euclid::virtual_balance::BalanceKey;
 use euclid_ibc::ack::AcknowledgementMsg;
 use euclid_ibc::msg::HubIbcExecuteMsg;
 
-use crate::reply::IBC_ACK_AND_TIMEOUT_REPLY_ID;
-use crate::state::{CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID, ESCROW_BALANCES, STATE};
+use crate::execute::finalize_escrow_retirement;
+use crate::reply::{ESCROW_RELEASE_RETRY_REPLY_ID, IBC_ACK_AND_TIMEOUT_REPLY_ID};
+use crate::state::{
+    CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID, ESCROW_BALANCES, ESCROW_RELEASES_IN_FLIGHT,
+    FAILED_ESCROW_RELEASES, FAILED_LP_MINTS, PENDING_ESCROW_RELEASES, PENDING_ESCROW_RELEASE_RETRY,
+    RETIRING_ESCROWS, STATE,
+};
 
 use super::channel::TIMEOUT_COUNTS;
 
@@ -82,15 +93,53 @@ pub fn reusable_internal_ack_call(
             tx_id,
             sender,
             chain_uid,
-            ..
+            to_address,
         } => {
             let res = from_json(ack)?;
-            ibc_ack_release_escrow(deps, env, chain_uid, sender, amount, token, res, tx_id)
+            ibc_ack_release_escrow(
+                deps, env, chain_uid, sender, amount, token, to_address, res, tx_id,
+            )
         }
         HubIbcExecuteMsg::UpdateFactoryChannel { chain_uid, tx_id } => {
             let res = from_json(ack)?;
             ibc_ack_update_factory_channel(deps, env, chain_uid, chain_type, res, tx_id)
         }
+        HubIbcExecuteMsg::DelistPool {
+            chain_uid,
+            vlp_address,
+            tx_id,
+            ..
+        } => {
+            let res = from_json(ack)?;
+            ibc_ack_delist_pool(env, chain_uid, vlp_address, res, tx_id)
+        }
+        HubIbcExecuteMsg::MintLpTokens {
+            chain_uid,
+            recipient,
+            vlp_address,
+            amount,
+            tx_id,
+        } => {
+            let res = from_json(ack)?;
+            ibc_ack_mint_lp_tokens(
+                deps,
+                env,
+                chain_uid,
+                recipient,
+                vlp_address,
+                amount,
+                res,
+                tx_id,
+            )
+        }
+        HubIbcExecuteMsg::RetireEscrow {
+            chain_uid,
+            token,
+            tx_id,
+        } => {
+            let res = from_json(ack)?;
+            ibc_ack_retire_escrow(deps, env, chain_uid, token, res, tx_id)
+        }
     }
 }
 
@@ -225,12 +274,13 @@ pub fn ibc_ack_update_factory_channel(
 }
 
 pub fn ibc_ack_release_escrow(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     chain_uid: ChainUid,
     sender: CrossChainUser,
     amount: Uint128,
     token: Token,
+    to_address: String,
     res: AcknowledgementMsg<ReleaseEscrowResponse>,
     tx_id: String,
 ) -> Result<Response, ContractError> {
@@ -239,8 +289,20 @@ pub fn ibc_ack_release_escrow(
         sender.address.as_str(),
         TxType::EscrowRelease,
     ));
+    let pending_release = PENDING_ESCROW_RELEASES.may_load(deps.storage, tx_id.clone())?;
+    PENDING_ESCROW_RELEASES.remove(deps.storage, tx_id.clone());
+    // Releases sent before the in flight count existed are not counted
+    ESCROW_RELEASES_IN_FLIGHT.update(
+        deps.storage,
+        (token.clone(), chain_uid.clone()),
+        |count| -> StdResult<_> { Ok(count.unwrap_or_default().saturating_sub(1)) },
+    )?;
+
     match res {
         AcknowledgementMsg::Ok(data) => Ok(response
+            .add_submessages(finalize_escrow_retirement(
+                &mut deps, &env, &token, &chain_uid,
+            )?)
             .add_attribute("method", "release_escrow_success")
             .add_attribute("factory_chain", data.chain_id)
             .add_attribute("factory_address", data.factory_address)
@@ -263,7 +325,7 @@ pub fn ibc_ack_release_escrow(
             let mint_msg = VirtualBalanceExecuteMsg::Mint(ExecuteMint {
                 amount,
                 balance_key: BalanceKey {
-                    cross_chain_user: sender,
+                    cross_chain_user: sender.clone(),
                     token_id: token.to_string(),
                 },
             });
@@ -274,17 +336,185 @@ pub fn ibc_ack_release_escrow(
             });
 
             // Escrow release is failed, add the old escrow balance again
-            let escrow_key = ESCROW_BALANCES.key((token, chain_uid));
-            let new_balance = escrow_key.load(deps.storage)?.checked_add(amount)?;
+            let escrow_key = ESCROW_BALANCES.key((token.clone(), chain_uid.clone()));
+            let new_balance = escrow_key
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                .checked_add(amount)?;
             escrow_key.save(deps.storage, &new_balance)?;
 
+            // Retry the release on the next addresses from the original list, the virtual balance is minted first so release can burn it again.
+            // The retry gets its own tx id so the escrow and pending txs can tell it apart from the failed release
+            let mut retry_msg = None;
+            let mut retry_tx_id = None;
+            if let Some(pending) =
+                pending_release.filter(|pending| !pending.cross_chain_addresses.is_empty())
+            {
+                let new_tx_id = generate_tx(deps.branch(), &env, &sender)?;
+                let msg = ExecuteMsg::ReleaseEscrowInternal {
+                    sender: sender.clone(),
+                    token: token.clone(),
+                    amount: Some(amount),
+                    cross_chain_addresses: pending.cross_chain_addresses,
+                    timeout: Some(pending.timeout),
+                    tx_id: new_tx_id.clone(),
+                    original_tx_id: Some(pending.original_tx_id.unwrap_or(tx_id.clone())),
+                };
+                let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&msg)?,
+                    funds: vec![],
+                });
+                // Retry failure shouldn't revert the re-mint of virtual balance
+                retry_msg = Some(SubMsg::reply_always(msg, ESCROW_RELEASE_RETRY_REPLY_ID));
+                retry_tx_id = Some(new_tx_id);
+            }
+            let retried = retry_msg.is_some();
+
+            let failed_key = (
+                sender.chain_uid.clone(),
+                sender.address.clone(),
+                tx_id.clone(),
+            );
+            if retried {
+                PENDING_ESCROW_RELEASE_RETRY.save(deps.storage, &failed_key)?;
+            }
+            FAILED_ESCROW_RELEASES.save(
+                deps.storage,
+                failed_key,
+                &FailedEscrowRelease {
+                    tx_id: tx_id.clone(),
+                    sender: sender.clone(),
+                    token: token.clone(),
+                    amount,
+                    chain_uid: chain_uid.clone(),
+                    to_address,
+                    error: err.clone(),
+                    retried,
+                    retry_tx_id: retry_tx_id.clone(),
+                },
+            )?;
+
             // Even if its a native chain, we can't reject via Err because other escrow release will also be rejected
             Ok(response
                 .add_message(msg)
+                .add_submessages(retry_msg)
                 .add_attribute("method", "escrow_release_ack")
                 .add_attribute("error", err)
-                .add_attribute("mint_amount", "value")
-                .add_attribute("balance_key", "balance_key"))
+                .add_attribute("mint_amount", amount)
+                .add_attribute("sender", sender.to_sender_string())
+                .add_attribute("token", token.to_string())
+                .add_attribute("chain_uid", chain_uid.to_string())
+                .add_attribute("retried", retried.to_string())
+                .add_attribute("retry_tx_id", retry_tx_id.unwrap_or_default()))
+        }
+    }
+}
+
+pub fn ibc_ack_delist_pool(
+    env: Env,
+    chain_uid: ChainUid,
+    vlp_address: String,
+    res: AcknowledgementMsg<DelistPoolResponse>,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            env.contract.address.as_str(),
+            TxType::PoolDelisting,
+        ))
+        .add_attribute("chain_uid", chain_uid.to_string())
+        .add_attribute("vlp_address", vlp_address);
+    match res {
+        AcknowledgementMsg::Ok(data) => Ok(response
+            .add_attribute("method", "delist_pool_ack_success")
+            .add_attribute("factory_address", data.factory_address)),
+        // The vlp is already removed on the router so swaps and liquidity requests for a stale
+        // factory entry are rejected anyway, no need to revert here
+        AcknowledgementMsg::Error(err) => Ok(response
+            .add_attribute("method", "delist_pool_ack_error")
+            .add_attribute("error", err)),
+    }
+}
+
+pub fn ibc_ack_retire_escrow(
+    deps: DepsMut,
+    env: Env,
+    chain_uid: ChainUid,
+    token: Token,
+    res: AcknowledgementMsg<EscrowRetirementResponse>,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            env.contract.address.as_str(),
+            TxType::EscrowRetirement,
+        ))
+        .add_attribute("chain_uid", chain_uid.to_string())
+        .add_attribute("token", token.to_string());
+    match res {
+        AcknowledgementMsg::Ok(_) => {
+            Ok(response.add_attribute("method", "retire_escrow_ack_success"))
+        }
+        // Put the drained escrow back into retirement so the factory admin can request it again
+        AcknowledgementMsg::Error(err) => {
+            let escrow_key = (token, chain_uid);
+            if !ESCROW_BALANCES.has(deps.storage, escrow_key.clone()) {
+                ESCROW_BALANCES.save(deps.storage, escrow_key.clone(), &Uint128::zero())?;
+                RETIRING_ESCROWS.save(deps.storage, escrow_key, &true)?;
+            }
+            Ok(response
+                .add_attribute("method", "retire_escrow_ack_error")
+                .add_attribute("error", err))
+        }
+    }
+}
+
+pub fn ibc_ack_mint_lp_tokens(
+    deps: DepsMut,
+    env: Env,
+    chain_uid: ChainUid,
+    recipient: String,
+    vlp_address: String,
+    amount: Uint128,
+    res: AcknowledgementMsg<MintLpTokensResponse>,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            env.contract.address.as_str(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("chain_uid", chain_uid.to_string())
+        .add_attribute("recipient", recipient.clone())
+        .add_attribute("vlp_address", vlp_address.clone())
+        .add_attribute("amount", amount);
+    match res {
+        AcknowledgementMsg::Ok(data) => Ok(response
+            .add_attribute("method", "mint_lp_tokens_ack_success")
+            .add_attribute("factory_address", data.factory_address)),
+        // Liquidity is already added on the vlp for the recipient chain, keep it so the mint can be retried
+        AcknowledgementMsg::Error(err) => {
+            FAILED_LP_MINTS.save(
+                deps.storage,
+                (chain_uid.clone(), recipient.clone(), tx_id.clone()),
+                &FailedLpMint {
+                    tx_id,
+                    recipient: CrossChainUser {
+                        chain_uid,
+                        address: recipient,
+                    },
+                    vlp_address,
+                    amount,
+                    error: err.clone(),
+                },
+            )?;
+            Ok(response
+                .add_attribute("method", "mint_lp_tokens_ack_error")
+                .add_attribute("error", err))
         }
     }
 }
@@ -9,26 +9,43 @@ use euclid::{
     error::ContractError,
     events::{tx_event, TxType},
     fee::Fee,
-    msgs::{self, router::ExecuteMsg, virtual_balance::ExecuteMint},
-    pool::EscrowCreationResponse,
+    liquidity::{
+        AddLiquidityLegResponse, InitialLiquidity, LiquidityIntent, MultiAssetLiquidityResponse,
+    },
+    msgs::{
+        self,
+        router::{ExecuteMsg, VlpMetadata},
+        virtual_balance::ExecuteMint,
+    },
+    pool::{EscrowCreationResponse, EscrowRetirementResponse},
     swap::WithdrawResponse,
     token::{Pair, Token},
     virtual_balance::BalanceKey,
 };
 use euclid_ibc::{
     ack::{make_ack_fail, AcknowledgementMsg},
-    msg::{ChainIbcExecuteMsg, ChainIbcRemoveLiquidityExecuteMsg, ChainIbcSwapExecuteMsg},
+    msg::{
+        ChainIbcAddLiquidityLegExecuteMsg, ChainIbcExecuteMsg, ChainIbcRemoveLiquidityExecuteMsg,
+        ChainIbcSwapExecuteMsg,
+    },
 };
 
 use crate::{
-    query::validate_swap_pairs,
+    execute::{
+        add_liquidity_from_virtual_balance, add_multi_asset_liquidity, create_multi_asset_pool,
+        ensure_pool_creation_allowed, remove_multi_asset_liquidity,
+    },
+    query::{predict_vlp_address, validate_swap_pairs},
     reply::{
-        ADD_LIQUIDITY_REPLY_ID, IBC_RECEIVE_REPLY_ID, REMOVE_LIQUIDITY_REPLY_ID, SWAP_REPLY_ID,
+        ADD_LIQUIDITY_LEG_REPLY_ID, ADD_LIQUIDITY_REPLY_ID, IBC_RECEIVE_REPLY_ID,
+        POOL_CREATION_ADD_LIQUIDITY_REPLY_ID, REMOVE_LIQUIDITY_REPLY_ID, SWAP_REPLY_ID,
         VIRTUAL_BALANCE_MINT_REPLY_ID, VLP_INSTANTIATE_REPLY_ID, VLP_POOL_REGISTER_REPLY_ID,
     },
     state::{
-        CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID, DEREGISTERED_CHAINS, ESCROW_BALANCES,
-        PENDING_REMOVE_LIQUIDITY, STATE, SWAP_ID_TO_MSG, VLPS,
+        VlpInfo, CHAIN_UID_TO_CHAIN, CHANNEL_TO_CHAIN_UID, DEREGISTERED_CHAINS, ESCROW_BALANCES,
+        ESCROW_RELEASES_IN_FLIGHT, LIQUIDITY_INTENTS, PENDING_LIQUIDITY_INTENT_COMPLETIONS,
+        PENDING_REMOVE_LIQUIDITY, PENDING_VLP_INSTANTIATE, RETIRING_ESCROWS, STATE, SWAP_ID_TO_MSG,
+        VLPS,
     },
 };
 
@@ -98,12 +115,20 @@ pub fn reusable_internal_call(
             pair,
             sender,
             tx_id,
+            initial_liquidity,
         } => {
             ensure!(
                 sender.chain_uid == chain_uid,
                 ContractError::new("Chain UID mismatch")
             );
-            execute_request_pool_creation(deps.branch(), env, sender, pair, tx_id)
+            execute_request_pool_creation(
+                deps.branch(),
+                env,
+                sender,
+                pair,
+                tx_id,
+                initial_liquidity,
+            )
         }
         ChainIbcExecuteMsg::RequestEscrowCreation {
             token,
@@ -116,6 +141,17 @@ pub fn reusable_internal_call(
             );
             execute_request_escrow_creation(deps.branch(), env, sender, token, tx_id)
         }
+        ChainIbcExecuteMsg::RequestEscrowRetirement {
+            token,
+            sender,
+            tx_id,
+        } => {
+            ensure!(
+                sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            execute_request_escrow_retirement(deps.branch(), env, sender, token, tx_id)
+        }
         ChainIbcExecuteMsg::AddLiquidity {
             token_1_liquidity,
             token_2_liquidity,
@@ -140,6 +176,77 @@ pub fn reusable_internal_call(
                 tx_id,
             )
         }
+        ChainIbcExecuteMsg::AddLiquidityLeg(msg) => {
+            ensure!(
+                msg.sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            ibc_execute_add_liquidity_leg(deps, env, msg)
+        }
+        ChainIbcExecuteMsg::AddLiquidityFromVirtualBalance(msg) => {
+            ensure!(
+                msg.sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            add_liquidity_from_virtual_balance(
+                deps,
+                msg.sender,
+                msg.pair,
+                msg.token_1_liquidity,
+                msg.token_2_liquidity,
+                msg.slippage_tolerance,
+                msg.recipient,
+                msg.tx_id,
+            )
+        }
+        ChainIbcExecuteMsg::RequestMultiAssetPoolCreation {
+            sender,
+            tx_id,
+            tokens,
+        } => {
+            ensure!(
+                sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            create_multi_asset_pool(deps, env, sender, tokens, tx_id)
+        }
+        ChainIbcExecuteMsg::AddMultiAssetLiquidity(msg) => {
+            ensure!(
+                msg.sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            let ack = AcknowledgementMsg::Ok(MultiAssetLiquidityResponse {
+                vlp_address: msg.vlp_address.clone(),
+                tx_id: msg.tx_id.clone(),
+            });
+            Ok(add_multi_asset_liquidity(
+                deps,
+                msg.sender,
+                msg.vlp_address,
+                msg.liquidity,
+                msg.min_lp_allocation,
+                msg.tx_id,
+            )?
+            .set_data(to_json_binary(&ack)?))
+        }
+        ChainIbcExecuteMsg::RemoveMultiAssetLiquidity(msg) => {
+            ensure!(
+                msg.sender.chain_uid == chain_uid,
+                ContractError::new("Chain UID mismatch")
+            );
+            let ack = AcknowledgementMsg::Ok(MultiAssetLiquidityResponse {
+                vlp_address: msg.vlp_address.clone(),
+                tx_id: msg.tx_id.clone(),
+            });
+            Ok(remove_multi_asset_liquidity(
+                deps,
+                msg.sender,
+                msg.vlp_address,
+                msg.lp_allocation,
+                msg.tx_id,
+            )?
+            .set_data(to_json_binary(&ack)?))
+        }
         ChainIbcExecuteMsg::RemoveLiquidity(msg) => {
             ensure!(
                 msg.sender.chain_uid == chain_uid,
@@ -167,6 +274,7 @@ pub fn reusable_internal_call(
                 cross_chain_addresses: msg.cross_chain_addresses,
                 timeout: msg.timeout,
                 tx_id: msg.tx_id.clone(),
+                original_tx_id: None,
             };
 
             Ok(Response::new()
@@ -189,11 +297,22 @@ fn execute_request_pool_creation(
     sender: CrossChainUser,
     pair: Pair,
     tx_id: String,
+    initial_liquidity: Option<InitialLiquidity>,
 ) -> Result<Response, ContractError> {
     pair.validate()?;
+    // Initial liquidity amounts follow the pair order
+    if initial_liquidity.is_some() {
+        ensure!(
+            pair.get_tupple() == (pair.token_1.clone(), pair.token_2.clone()),
+            ContractError::new("Pair should be sorted")
+        );
+    }
     let state = STATE.load(deps.storage)?;
+    ensure_pool_creation_allowed(deps.storage, &sender)?;
 
-    let vlp = VLPS.may_load(deps.storage, pair.get_tupple())?;
+    let vlp = VLPS
+        .may_load(deps.storage, pair.get_tupple())?
+        .map(|info| info.vlp);
 
     let register_msg = msgs::vlp::ExecuteMsg::RegisterPool {
         sender: sender.clone(),
@@ -207,10 +326,14 @@ fn execute_request_pool_creation(
             &sender.to_sender_string(),
             TxType::PoolCreation,
         ))
-        .add_attribute("tx_id", tx_id)
+        .add_attribute("tx_id", tx_id.clone())
         .add_attribute("method", "request_pool_creation");
 
     for token in pair.get_vec_token() {
+        ensure!(
+            !RETIRING_ESCROWS.has(deps.storage, (token.clone(), sender.chain_uid.clone())),
+            ContractError::EscrowRetiring {}
+        );
         let token_exists =
             ESCROW_BALANCES.has(deps.storage, (token.clone(), sender.clone().chain_uid));
         let range =
@@ -227,14 +350,39 @@ fn execute_request_pool_creation(
     }
 
     // If vlp is already there, send execute msg to it to register the pool, else create a new pool with register msg attached to instantiate msg
-    if vlp.is_some() {
+    let (vlp_address, response) = if let Some(vlp_address) = vlp {
         let msg = WasmMsg::Execute {
-            contract_addr: vlp.unwrap(),
+            contract_addr: vlp_address.clone(),
             msg: to_json_binary(&register_msg)?,
             funds: vec![],
         };
-        Ok(response.add_submessage(SubMsg::reply_always(msg, VLP_POOL_REGISTER_REPLY_ID)))
+        (
+            vlp_address,
+            response.add_submessage(SubMsg::reply_always(msg, VLP_POOL_REGISTER_REPLY_ID)),
+        )
     } else {
+        let fee = Fee {
+            lp_fee_bps: 10,
+            euclid_fee_bps: 10,
+            recipient: CrossChainUser {
+                address: state.admin.clone(),
+                chain_uid: ChainUid::vsl_chain_uid()?,
+            },
+        };
+        let (vlp_address, salt) = predict_vlp_address(deps.as_ref(), &env, &pair)?;
+        PENDING_VLP_INSTANTIATE.save(
+            deps.storage,
+            &VlpInfo {
+                vlp: vlp_address.to_string(),
+                pair: pair.clone(),
+                metadata: Some(VlpMetadata {
+                    created_at: env.block.time,
+                    creator_chain: sender.chain_uid.clone(),
+                    lp_fee_bps: fee.lp_fee_bps,
+                    euclid_fee_bps: fee.euclid_fee_bps,
+                }),
+            },
+        )?;
         let instantiate_msg = msgs::vlp::InstantiateMsg {
             router: env.contract.address.to_string(),
             virtual_balance: state
@@ -243,27 +391,43 @@ fn execute_request_pool_creation(
                     err: "virtual balance not instantiated".to_string(),
                 })?
                 .to_string(),
-            pair,
-            fee: Fee {
-                lp_fee_bps: 10,
-                euclid_fee_bps: 10,
-                recipient: CrossChainUser {
-                    address: state.admin.clone(),
-                    chain_uid: ChainUid::vsl_chain_uid()?,
-                },
-            },
+            pair: pair.clone(),
+            fee,
             execute: Some(register_msg),
             admin: state.admin.clone(),
         };
-        let msg = WasmMsg::Instantiate {
-            admin: Some(state.admin),
+        // Router is the wasm admin so that it can migrate vlps in batches
+        let msg = WasmMsg::Instantiate2 {
+            admin: Some(env.contract.address.to_string()),
             code_id: state.vlp_code_id,
+            label: "VLP".to_string(),
             msg: to_json_binary(&instantiate_msg)?,
             funds: vec![],
-            label: "VLP".to_string(),
+            salt,
         };
-        Ok(response.add_submessage(SubMsg::reply_always(msg, VLP_INSTANTIATE_REPLY_ID)))
-    }
+        (
+            vlp_address.to_string(),
+            response.add_submessage(SubMsg::reply_always(msg, VLP_INSTANTIATE_REPLY_ID)),
+        )
+    };
+
+    // The predicted address lets the liquidity follow the instantiation in the same transaction,
+    // a failure reverts the pool creation as well
+    let Some(initial_liquidity) = initial_liquidity else {
+        return Ok(response);
+    };
+    let liquidity_msgs = add_chain_liquidity_msgs(
+        deps,
+        vlp_address,
+        pair,
+        sender,
+        initial_liquidity.token_1_liquidity,
+        initial_liquidity.token_2_liquidity,
+        initial_liquidity.slippage_tolerance,
+        tx_id,
+        POOL_CREATION_ADD_LIQUIDITY_REPLY_ID,
+    )?;
+    Ok(response.add_submessages(liquidity_msgs))
 }
 
 fn execute_request_escrow_creation(
@@ -296,6 +460,53 @@ fn execute_request_escrow_creation(
         .set_data(to_json_binary(&ack)?))
 }
 
+fn execute_request_escrow_retirement(
+    deps: DepsMut,
+    _env: Env,
+    sender: CrossChainUser,
+    token: Token,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    token.validate()?;
+
+    let escrow_key = (token.clone(), sender.chain_uid.clone());
+    let remaining_balance = ESCROW_BALANCES
+        .may_load(deps.storage, escrow_key.clone())?
+        .ok_or(ContractError::EscrowDoesNotExist {})?;
+
+    // Escrow can only be removed once everything is withdrawn, till then it only blocks new deposits.
+    // Releases that are still in flight can be refunded to the escrow, so they have to settle first
+    let releases_in_flight = ESCROW_RELEASES_IN_FLIGHT
+        .may_load(deps.storage, escrow_key.clone())?
+        .unwrap_or_default();
+    let retired = remaining_balance.is_zero() && releases_in_flight == 0;
+    if retired {
+        ESCROW_BALANCES.remove(deps.storage, escrow_key.clone());
+        RETIRING_ESCROWS.remove(deps.storage, escrow_key);
+    } else {
+        RETIRING_ESCROWS.save(deps.storage, escrow_key, &true)?;
+    }
+
+    let ack = AcknowledgementMsg::Ok(EscrowRetirementResponse {
+        token: token.clone(),
+        remaining_balance,
+        retired,
+    });
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &sender.to_sender_string(),
+            TxType::EscrowRetirement,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "request_escrow_retirement")
+        .add_attribute("token", token.to_string())
+        .add_attribute("chain_uid", sender.chain_uid.to_string())
+        .add_attribute("remaining_balance", remaining_balance)
+        .add_attribute("retired", retired.to_string())
+        .set_data(to_json_binary(&ack)?))
+}
+
 fn ibc_execute_add_liquidity(
     deps: DepsMut,
     _env: Env,
@@ -306,21 +517,56 @@ fn ibc_execute_add_liquidity(
     pair: Pair,
     tx_id: String,
 ) -> Result<Response, ContractError> {
-    let vlp_address = VLPS.load(deps.storage, pair.get_tupple())?;
+    let vlp_address = VLPS.load(deps.storage, pair.get_tupple())?.vlp;
     let pool_liquidity: euclid::msgs::vlp::GetLiquidityResponse = deps.querier.query_wasm_smart(
         vlp_address.clone(),
         &euclid::msgs::vlp::QueryMsg::Liquidity {},
     )?;
 
-    let mut response = Response::new().add_event(
-        tx_event(&tx_id, &sender.to_sender_string(), TxType::AddLiquidity)
-            .add_attribute("tx_id", tx_id.clone()),
-    );
+    let liquidity_msgs = add_chain_liquidity_msgs(
+        deps,
+        vlp_address,
+        pool_liquidity.pair,
+        sender.clone(),
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+        tx_id.clone(),
+        ADD_LIQUIDITY_REPLY_ID,
+    )?;
+
+    Ok(Response::new()
+        .add_event(
+            tx_event(&tx_id, &sender.to_sender_string(), TxType::AddLiquidity)
+                .add_attribute("tx_id", tx_id.clone()),
+        )
+        .add_submessages(liquidity_msgs))
+}
+
+// Escrow accounting, virtual balance mints and the vlp call for liquidity deposited on a chain
+#[allow(clippy::too_many_arguments)]
+fn add_chain_liquidity_msgs(
+    deps: DepsMut,
+    vlp_address: String,
+    pair: Pair,
+    sender: CrossChainUser,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+    tx_id: String,
+    reply_id: u64,
+) -> Result<Vec<SubMsg>, ContractError> {
+    // Retiring escrows can't take new deposits
+    for token in pair.get_vec_token() {
+        ensure!(
+            !RETIRING_ESCROWS.has(deps.storage, (token, sender.chain_uid.clone())),
+            ContractError::EscrowRetiring {}
+        );
+    }
+
+    let mut msgs = vec![];
     // Increase token 1 escrow balance
-    let token_1_escrow_key = (
-        pool_liquidity.pair.token_1.clone(),
-        sender.chain_uid.clone(),
-    );
+    let token_1_escrow_key = (pair.token_1.clone(), sender.chain_uid.clone());
     let token_1_escrow_balance = ESCROW_BALANCES
         .may_load(deps.storage, token_1_escrow_key.clone())?
         .unwrap_or(Uint128::zero());
@@ -332,10 +578,7 @@ fn ibc_execute_add_liquidity(
     )?;
 
     // Increase token 2 escrow balance
-    let token_2_escrow_key = (
-        pool_liquidity.pair.token_2.clone(),
-        sender.chain_uid.clone(),
-    );
+    let token_2_escrow_key = (pair.token_2.clone(), sender.chain_uid.clone());
     let token_2_escrow_balance = ESCROW_BALANCES
         .may_load(deps.storage, token_2_escrow_key.clone())?
         .unwrap_or(Uint128::zero());
@@ -360,7 +603,7 @@ fn ibc_execute_add_liquidity(
                 address: vlp_address.to_string(),
                 chain_uid: ChainUid::vsl_chain_uid()?,
             },
-            token_id: pool_liquidity.pair.token_1.to_string(),
+            token_id: pair.token_1.to_string(),
         },
     });
 
@@ -370,7 +613,7 @@ fn ibc_execute_add_liquidity(
         funds: vec![],
     };
 
-    response = response.add_submessage(SubMsg::reply_on_error(
+    msgs.push(SubMsg::reply_on_error(
         mint_virtual_balance_msg,
         VIRTUAL_BALANCE_MINT_REPLY_ID,
     ));
@@ -382,7 +625,7 @@ fn ibc_execute_add_liquidity(
                 address: vlp_address.to_string(),
                 chain_uid: ChainUid::vsl_chain_uid()?,
             },
-            token_id: pool_liquidity.pair.token_2.to_string(),
+            token_id: pair.token_2.to_string(),
         },
     });
 
@@ -392,7 +635,7 @@ fn ibc_execute_add_liquidity(
         funds: vec![],
     };
 
-    response = response.add_submessage(SubMsg::reply_on_error(
+    msgs.push(SubMsg::reply_on_error(
         mint_virtual_balance_msg,
         VIRTUAL_BALANCE_MINT_REPLY_ID,
     ));
@@ -411,7 +654,189 @@ fn ibc_execute_add_liquidity(
         funds: vec![],
     };
 
-    Ok(response.add_submessage(SubMsg::reply_always(msg, ADD_LIQUIDITY_REPLY_ID)))
+    msgs.push(SubMsg::reply_always(msg, reply_id));
+    Ok(msgs)
+}
+
+fn ibc_execute_add_liquidity_leg(
+    deps: &mut DepsMut,
+    env: Env,
+    msg: ChainIbcAddLiquidityLegExecuteMsg,
+) -> Result<Response, ContractError> {
+    let vlp_address = VLPS
+        .load(deps.storage, msg.pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+    ensure!(
+        msg.pair.get_vec_token().contains(&msg.token),
+        ContractError::new("Token is not part of the pair")
+    );
+    ensure!(
+        !RETIRING_ESCROWS.has(
+            deps.storage,
+            (msg.token.clone(), msg.sender.chain_uid.clone())
+        ),
+        ContractError::EscrowRetiring {}
+    );
+    ensure!(
+        CHAIN_UID_TO_CHAIN.has(deps.storage, msg.recipient.chain_uid.clone()),
+        ContractError::new("Recipient chain is not registered")
+    );
+
+    // The factory sends the tokens to escrow once this leg is acknowledged
+    let escrow_key = ESCROW_BALANCES.key((msg.token.clone(), msg.sender.chain_uid.clone()));
+    let escrow_balance = escrow_key.may_load(deps.storage)?.unwrap_or_default();
+    escrow_key.save(deps.storage, &escrow_balance.checked_add(msg.amount)?)?;
+
+    let virtual_balance_address = STATE
+        .load(deps.storage)?
+        .virtual_balance_address
+        .ok_or(ContractError::new("virtual balance address doesn't exist"))?
+        .into_string();
+
+    let response = Response::new()
+        .add_event(tx_event(
+            &msg.tx_id,
+            &msg.sender.to_sender_string(),
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("tx_id", msg.tx_id.clone())
+        .add_attribute("intent_id", msg.intent_id.clone());
+
+    // Router holds the first leg as its own virtual balance until the second leg arrives
+    let holder = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: env.contract.address.to_string(),
+    };
+
+    // Intents are keyed by the sender of the held leg, so the other leg is looked up under its counterparty
+    let intent_key =
+        LIQUIDITY_INTENTS.key((msg.counterparty.to_sender_string(), msg.intent_id.clone()));
+    let Some(intent) = intent_key.may_load(deps.storage)? else {
+        ensure!(
+            msg.deadline > env.block.time.seconds(),
+            ContractError::new("Liquidity intent deadline has passed")
+        );
+        let own_key = LIQUIDITY_INTENTS.key((msg.sender.to_sender_string(), msg.intent_id.clone()));
+        ensure!(
+            !own_key.has(deps.storage),
+            ContractError::new("Liquidity intent already exists")
+        );
+        own_key.save(
+            deps.storage,
+            &LiquidityIntent {
+                sender: msg.sender,
+                token: msg.token.clone(),
+                amount: msg.amount,
+                pair: msg.pair,
+                slippage_tolerance: msg.slippage_tolerance,
+                counterparty: msg.counterparty,
+                recipient: msg.recipient,
+                deadline: msg.deadline,
+            },
+        )?;
+
+        let mint_msg = euclid::msgs::virtual_balance::ExecuteMsg::Mint(ExecuteMint {
+            amount: msg.amount,
+            balance_key: BalanceKey {
+                cross_chain_user: holder,
+                token_id: msg.token.to_string(),
+            },
+        });
+        let mint_msg = WasmMsg::Execute {
+            contract_addr: virtual_balance_address,
+            msg: to_json_binary(&mint_msg)?,
+            funds: vec![],
+        };
+
+        let ack = AcknowledgementMsg::Ok(AddLiquidityLegResponse {
+            intent_id: msg.intent_id,
+            completed: false,
+        });
+        return Ok(response
+            .add_attribute("method", "add_liquidity_leg_held")
+            .add_message(mint_msg)
+            .set_data(to_json_binary(&ack)?));
+    };
+
+    ensure!(
+        intent.counterparty == msg.sender,
+        ContractError::new("Liquidity leg is not from the intent counterparty")
+    );
+    ensure!(
+        intent.pair.get_tupple() == msg.pair.get_tupple()
+            && intent.token != msg.token
+            && intent.recipient == msg.recipient
+            && intent.slippage_tolerance == msg.slippage_tolerance,
+        ContractError::new("Liquidity leg doesn't match the intent")
+    );
+    ensure!(
+        intent.deadline > env.block.time.seconds(),
+        ContractError::new("Liquidity intent deadline has passed")
+    );
+    intent_key.remove(deps.storage);
+
+    let vlp = CrossChainUser {
+        chain_uid: ChainUid::vsl_chain_uid()?,
+        address: vlp_address.clone(),
+    };
+    let transfer_msg = intent.token.create_virtual_balance_transfer_msg(
+        virtual_balance_address.clone(),
+        intent.amount,
+        holder,
+        vlp.clone(),
+    )?;
+    let mint_msg = euclid::msgs::virtual_balance::ExecuteMsg::Mint(ExecuteMint {
+        amount: msg.amount,
+        balance_key: BalanceKey {
+            cross_chain_user: vlp,
+            token_id: msg.token.to_string(),
+        },
+    });
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: virtual_balance_address,
+        msg: to_json_binary(&mint_msg)?,
+        funds: vec![],
+    };
+
+    let pool_liquidity: euclid::msgs::vlp::GetLiquidityResponse = deps.querier.query_wasm_smart(
+        vlp_address.clone(),
+        &euclid::msgs::vlp::QueryMsg::Liquidity {},
+    )?;
+    let (token_1_liquidity, token_2_liquidity) = if pool_liquidity.pair.token_1 == intent.token {
+        (intent.amount, msg.amount)
+    } else {
+        (msg.amount, intent.amount)
+    };
+
+    // Lp shares are accounted to the recipient chain on the vlp
+    let add_liquidity_msg = msgs::vlp::ExecuteMsg::AddLiquidity {
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance: intent.slippage_tolerance,
+        sender: intent.recipient.clone(),
+        tx_id: msg.tx_id,
+    };
+    let add_liquidity_msg = WasmMsg::Execute {
+        contract_addr: vlp_address.clone(),
+        msg: to_json_binary(&add_liquidity_msg)?,
+        funds: vec![],
+    };
+
+    PENDING_LIQUIDITY_INTENT_COMPLETIONS.save(
+        deps.storage,
+        vlp_address,
+        &(msg.intent_id, intent.recipient),
+    )?;
+
+    Ok(response
+        .add_attribute("method", "add_liquidity_leg_completed")
+        .add_message(transfer_msg)
+        .add_message(mint_msg)
+        .add_submessage(SubMsg::reply_on_success(
+            add_liquidity_msg,
+            ADD_LIQUIDITY_LEG_REPLY_ID,
+        )))
 }
 
 fn ibc_execute_remove_liquidity(
@@ -419,7 +844,7 @@ fn ibc_execute_remove_liquidity(
     _env: Env,
     msg: ChainIbcRemoveLiquidityExecuteMsg,
 ) -> Result<Response, ContractError> {
-    let vlp_address = VLPS.load(deps.storage, msg.pair.get_tupple())?;
+    let vlp_address = VLPS.load(deps.storage, msg.pair.get_tupple())?.vlp;
     let response = Response::new()
         .add_event(tx_event(
             &msg.tx_id,
@@ -477,6 +902,14 @@ fn ibc_execute_swap(
         ContractError::new("Asset OUT doen't match router")
     );
 
+    ensure!(
+        !RETIRING_ESCROWS.has(
+            deps.storage,
+            (msg.asset_in.clone(), msg.sender.chain_uid.clone())
+        ),
+        ContractError::EscrowRetiring {}
+    );
+
     let req_key = (
         msg.sender.chain_uid.clone(),
         msg.sender.address.clone(),
@@ -561,6 +994,7 @@ fn ibc_execute_swap(
         next_swaps: next_swaps.to_vec(),
         tx_id: msg.tx_id.clone(),
         test_fail: first_swap.test_fail,
+        asset_out: first_swap.asset_out.clone(),
     };
 
     let msg = WasmMsg::Execute {
@@ -1,9 +1,75 @@
-use cosmwasm_std::{entry_point, DepsMut, Env, Response};
-use euclid::{error::ContractError, msgs::vlp::MigrateMsg};
+use cosmwasm_std::{entry_point, DepsMut, Env, Order, Response, StdResult};
+use cw_storage_plus::Map;
+use euclid::{
+    error::ContractError,
+    migrate::ensure_migration_version,
+    msgs::router::MigrateMsg,
+    token::{Pair, Token},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    state::{VlpInfo, STATE, VLPS},
+};
+
+// Vlps used to be stored as a plain address under the same namespace, so an entry is either
+// a legacy plain address or an already indexed `VlpInfo`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum LegacyVlp {
+    Address(String),
+    Indexed(VlpInfo),
+}
+
+const LEGACY_VLPS: Map<(Token, Token), LegacyVlp> = Map::new("vlps");
 
 /// This is the migrate entry point for the contract.
-/// Currently, it does not perform any migration logic and simply returns an empty response.
+/// It refuses migrations from a different contract or a newer version.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Router used to be stored under the factory contract name
+    let previous = ensure_migration_version(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &["crates.io:factory"],
+    )?;
+
+    // Legacy name is shared with the factory, so make sure this is actually a router state
+    STATE
+        .load(deps.storage)
+        .map_err(|_err| ContractError::CannotMigrate {
+            previous_contract: previous.contract.clone(),
+        })?;
+
+    // Entries already stored as vlp info are left alone, anything else that can't be read fails
+    let legacy_vlps: Vec<_> = LEGACY_VLPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((key, LegacyVlp::Address(vlp))) => Some(Ok((key, vlp))),
+            Ok((_, LegacyVlp::Indexed(_))) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<_>>()?;
+    for ((token_1, token_2), vlp) in legacy_vlps.iter().cloned() {
+        // Saving reads the previous value to update the indexes, so drop the legacy entry first
+        LEGACY_VLPS.remove(deps.storage, (token_1.clone(), token_2.clone()));
+        VLPS.save(
+            deps.storage,
+            (token_1.clone(), token_2.clone()),
+            &VlpInfo {
+                vlp,
+                pair: Pair { token_1, token_2 },
+                metadata: None,
+            },
+        )?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("indexed_vlps", legacy_vlps.len().to_string())
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
 }
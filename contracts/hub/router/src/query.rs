@@ -1,19 +1,36 @@
-use cosmwasm_std::{ensure, to_json_binary, Binary, Deps, Order, Uint128};
-use cw_storage_plus::{Bound, PrefixBound};
+use cosmwasm_std::{
+    ensure, instantiate2_address, to_json_binary, Addr, Binary, Deps, Env, Order, StdError,
+    StdResult, Uint128, Uint256,
+};
+use cw_storage_plus::{Bound, PrefixBound, PrimaryKey};
 use euclid::{
-    chain::{ChainUid, CrossChainUserWithLimit},
+    chain::{ChainUid, CrossChainUser, CrossChainUserWithLimit},
     error::ContractError,
     msgs::router::{
-        AllChainResponse, AllTokensResponse, AllVlpResponse, ChainResponse, QuerySimulateSwap,
-        SimulateEscrowReleaseResponse, SimulateSwapResponse, StateResponse,
-        TokenEscrowChainResponse, TokenEscrowsResponse, TokenResponse, VlpResponse,
+        AllChainResponse, AllMultiAssetVlpResponse, AllTokensResponse, AllVlpResponse,
+        AllVlpVolumeResponse, ChainResponse, FailedEscrowReleasesResponse, FailedLpMintsResponse,
+        FeeRecipientsResponse, LimitOrderResponse, LimitOrdersResponse, LiquidityDepthResponse,
+        LiquidityIntentResponse, MultiAssetVlpResponse, PoolCreationModeResponse, PoolQueryError,
+        PredictVlpAddressResponse, QuerySimulateSwap, RetiringEscrowResponse,
+        RetiringEscrowsResponse, SimulateEscrowReleaseResponse, SimulateSwapResponse,
+        StateResponse, TokenEscrowChainResponse, TokenEscrowsResponse, TokenResponse, TokenVolume,
+        UnclaimedVslLpFeesResponse, VlpMigrationStatusResponse, VlpResponse, VlpVolumeResponse,
+        VslLpShareResponse, VslLpSharesResponse,
     },
-    swap::{NextSwapPair, NextSwapVlp},
+    msgs::vlp::FeeGrowthResponse,
+    pool::{get_pool_id, get_vlp_salt, sort_pool_tokens},
+    swap::{DepthTarget, NextSwapPair, NextSwapVlp},
     token::{Pair, Token},
     utils::Pagination,
 };
 
-use crate::state::{CHAIN_UID_TO_CHAIN, ESCROW_BALANCES, STATE, VLPS};
+use crate::state::{
+    VlpInfo, CHAIN_UID_TO_CHAIN, DEFAULT_FEE_RECIPIENTS, ESCROW_BALANCES, FAILED_ESCROW_RELEASES,
+    FAILED_LP_MINTS, LIMIT_ORDERS, LIQUIDITY_INTENTS, MULTI_ASSET_POOLS, MULTI_ASSET_POOL_IDS,
+    MULTI_ASSET_VLPS, POOL_CREATION_MODES, RETIRING_ESCROWS, STATE, SUNSET_VLPS, USER_LIMIT_ORDERS,
+    VLPS, VLP_GENERATIONS, VLP_MIGRATION, VLP_MIGRATION_RESULTS, VSL_LP_FEE_CHECKPOINTS,
+    VSL_LP_SHARES, VSL_UNCLAIMED_LP_FEES,
+};
 
 pub fn query_state(deps: Deps) -> Result<Binary, ContractError> {
     let state = STATE.load(deps.storage)?;
@@ -40,15 +57,420 @@ pub fn query_all_vlps(
     let end = end.map(Bound::exclusive);
 
     let vlps: Result<_, ContractError> = VLPS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| Ok(vlp_response(v?.1)))
+        .collect();
+
+    Ok(to_json_binary(&AllVlpResponse { vlps: vlps? })?)
+}
+
+pub fn query_vlps_by_token(
+    deps: Deps,
+    token: Token,
+    pagination: Pagination<(Token, Token)>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+    let skip = skip.unwrap_or(0) as usize;
+    let limit = limit.unwrap_or(10) as usize;
+
+    // The token can be on either side of a pair, so page through both indexes and merge them in the
+    // raw storage order of the pair keys, which is the order the bounds are applied in
+    let mut vlps = vec![];
+    for index in [&VLPS.idx.token_1, &VLPS.idx.token_2] {
+        let page = index
+            .prefix(token.clone())
+            .range(
+                deps.storage,
+                start.clone().map(Bound::inclusive),
+                end.clone().map(Bound::exclusive),
+                Order::Ascending,
+            )
+            .take(skip + limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        vlps.extend(page);
+    }
+    vlps.sort_by_key(|(key, _)| PrimaryKey::joined_key(key));
+
+    let vlps = vlps
+        .into_iter()
+        .skip(skip)
+        .take(limit)
+        .map(|(_, info)| vlp_response(info))
+        .collect();
+
+    Ok(to_json_binary(&AllVlpResponse { vlps })?)
+}
+
+pub fn query_volume(
+    deps: Deps,
+    chain_uid: Option<ChainUid>,
+    pagination: Pagination<(Token, Token)>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let vlps = VLPS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| Ok(v?.1))
+        .collect::<StdResult<Vec<VlpInfo>>>()?;
+
+    let volume_msg = euclid::msgs::vlp::QueryMsg::Volume {
+        chain_uid: chain_uid.clone(),
+    };
+    let mut response = AllVlpVolumeResponse {
+        vlps: vec![],
+        totals: vec![],
+        failed_vlps: vec![],
+    };
+    // Vlps from before the volume query can't answer it, they are reported instead of failing the page
+    for info in vlps {
+        let volume: euclid::msgs::vlp::VolumeResponse =
+            match deps.querier.query_wasm_smart(info.vlp.clone(), &volume_msg) {
+                Ok(volume) => volume,
+                Err(err) => {
+                    response.failed_vlps.push(PoolQueryError {
+                        vlp_address: info.vlp,
+                        error: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+        for (token, amount) in [
+            (&info.pair.token_1, volume.token_1_volume),
+            (&info.pair.token_2, volume.token_2_volume),
+        ] {
+            match response
+                .totals
+                .iter_mut()
+                .find(|total| total.token == *token)
+            {
+                Some(total) => total.volume = total.volume.checked_add(amount)?,
+                None => response.totals.push(TokenVolume {
+                    token: token.clone(),
+                    volume: amount,
+                }),
+            }
+        }
+        response.vlps.push(VlpVolumeResponse {
+            vlp: info.vlp,
+            token_1: info.pair.token_1,
+            token_2: info.pair.token_2,
+            token_1_volume: volume.token_1_volume,
+            token_2_volume: volume.token_2_volume,
+        });
+    }
+    Ok(to_json_binary(&response)?)
+}
+
+fn vlp_response(info: VlpInfo) -> VlpResponse {
+    VlpResponse {
+        vlp: info.vlp,
+        token_1: info.pair.token_1,
+        token_2: info.pair.token_2,
+        metadata: info.metadata,
+    }
+}
+
+pub fn query_multi_asset_vlp(deps: Deps, tokens: Vec<Token>) -> Result<Binary, ContractError> {
+    let tokens = sort_pool_tokens(tokens)?;
+    let vlp = MULTI_ASSET_POOL_IDS
+        .load(deps.storage, get_pool_id(&tokens))
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?;
+    Ok(to_json_binary(&MultiAssetVlpResponse { vlp, tokens })?)
+}
+
+pub fn query_all_multi_asset_vlps(
+    deps: Deps,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let vlps: Result<_, ContractError> = MULTI_ASSET_POOLS
         .range(deps.storage, start, end, Order::Ascending)
         .skip(skip.unwrap_or(0) as usize)
         .take(limit.unwrap_or(10) as usize)
         .map(|v| {
-            let v = v?;
+            let (vlp, tokens) = v?;
+            Ok(MultiAssetVlpResponse { vlp, tokens })
+        })
+        .collect();
+
+    Ok(to_json_binary(&AllMultiAssetVlpResponse { vlps: vlps? })?)
+}
+
+pub fn query_vlp_migration_status(
+    deps: Deps,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let results = VLP_MIGRATION_RESULTS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| Ok(v?.1))
+        .collect::<Result<_, ContractError>>()?;
+
+    let response = match VLP_MIGRATION.may_load(deps.storage)? {
+        Some(migration) => VlpMigrationStatusResponse {
+            code_id: migration.code_id,
+            last_pair: migration.last_pair,
+            finished: migration.finished,
+            results,
+        },
+        None => VlpMigrationStatusResponse {
+            code_id: STATE.load(deps.storage)?.vlp_code_id,
+            last_pair: None,
+            finished: false,
+            results,
+        },
+    };
+
+    Ok(to_json_binary(&response)?)
+}
+
+pub fn query_liquidity_intent(
+    deps: Deps,
+    sender: CrossChainUser,
+    intent_id: String,
+) -> Result<Binary, ContractError> {
+    let intent = LIQUIDITY_INTENTS
+        .load(deps.storage, (sender.to_sender_string(), intent_id.clone()))
+        .map_err(|_err| ContractError::new("Liquidity intent doesn't exist"))?;
+    Ok(to_json_binary(&LiquidityIntentResponse {
+        intent_id,
+        intent,
+    })?)
+}
+
+pub fn query_limit_order(deps: Deps, order_id: u64) -> Result<Binary, ContractError> {
+    let order = LIMIT_ORDERS
+        .load(deps.storage, order_id)
+        .map_err(|_err| ContractError::new("Limit order doesn't exist"))?;
+    Ok(to_json_binary(&LimitOrderResponse { order_id, order })?)
+}
+
+pub fn query_limit_orders(
+    deps: Deps,
+    pagination: Pagination<u64>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let orders: Result<_, ContractError> = LIMIT_ORDERS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| {
+            let (order_id, order) = v?;
+            Ok(LimitOrderResponse { order_id, order })
+        })
+        .collect();
+
+    Ok(to_json_binary(&LimitOrdersResponse { orders: orders? })?)
+}
+
+pub fn query_user_limit_orders(
+    deps: Deps,
+    user: String,
+    pagination: Pagination<u64>,
+) -> Result<Binary, ContractError> {
+    let user = deps.api.addr_validate(&user)?;
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let orders: Result<_, ContractError> = USER_LIMIT_ORDERS
+        .prefix(user)
+        .keys(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|order_id| {
+            let order_id = order_id?;
+            let order = LIMIT_ORDERS.load(deps.storage, order_id)?;
+            Ok(LimitOrderResponse { order_id, order })
+        })
+        .collect();
+
+    Ok(to_json_binary(&LimitOrdersResponse { orders: orders? })?)
+}
+
+pub fn query_vsl_lp_shares(
+    deps: Deps,
+    user: String,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let user = deps.api.addr_validate(&user)?;
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let shares: Result<_, ContractError> = VSL_LP_SHARES
+        .prefix(user)
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| {
+            let (vlp, lp_shares) = v?;
+            Ok(VslLpShareResponse { vlp, lp_shares })
+        })
+        .collect();
+
+    Ok(to_json_binary(&VslLpSharesResponse { shares: shares? })?)
+}
+
+pub fn query_default_fee_recipients(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_json_binary(&FeeRecipientsResponse {
+        recipients: DEFAULT_FEE_RECIPIENTS.may_load(deps.storage)?,
+    })?)
+}
+
+pub fn query_pool_creation_mode(deps: Deps, chain_uid: ChainUid) -> Result<Binary, ContractError> {
+    let mode = POOL_CREATION_MODES
+        .may_load(deps.storage, chain_uid.clone())?
+        .unwrap_or_default();
+    Ok(to_json_binary(&PoolCreationModeResponse {
+        chain_uid,
+        mode,
+    })?)
+}
+
+pub fn query_unclaimed_vsl_lp_fees(
+    deps: Deps,
+    user: String,
+    pair: Pair,
+) -> Result<Binary, ContractError> {
+    let user = deps.api.addr_validate(&user)?;
+    let vlp = VLPS
+        .load(deps.storage, pair.get_tupple())
+        .map_err(|_err| ContractError::PoolDoesNotExists {})?
+        .vlp;
+    let (_, (token_1_fees, token_2_fees)) = get_unclaimed_vsl_lp_fees(deps, &user, &vlp)?;
+
+    Ok(to_json_binary(&UnclaimedVslLpFeesResponse {
+        vlp,
+        token_1_fees,
+        token_2_fees,
+    })?)
+}
+
+// Claimable lp fees of a hub lp in a vlp, the settled fees plus what its lp shares earned since the
+// last settlement. Also returns the current fee growth of the vlp
+pub fn get_unclaimed_vsl_lp_fees(
+    deps: Deps,
+    user: &Addr,
+    vlp: &str,
+) -> Result<(FeeGrowthResponse, (Uint128, Uint128)), ContractError> {
+    let key = (user.clone(), vlp.to_string());
+    let fee_growth: FeeGrowthResponse = deps
+        .querier
+        .query_wasm_smart(vlp, &euclid::msgs::vlp::QueryMsg::FeeGrowth {})?;
+    let (checkpoint_1, checkpoint_2) = VSL_LP_FEE_CHECKPOINTS
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    let (unclaimed_1, unclaimed_2) = VSL_UNCLAIMED_LP_FEES
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    let shares = Uint256::from(
+        VSL_LP_SHARES
+            .may_load(deps.storage, key)?
+            .unwrap_or_default(),
+    );
+
+    let pending_1 =
+        shares.checked_mul_floor(fee_growth.token_1_fee_growth.checked_sub(checkpoint_1)?)?;
+    let pending_2 =
+        shares.checked_mul_floor(fee_growth.token_2_fee_growth.checked_sub(checkpoint_2)?)?;
+
+    Ok((
+        fee_growth,
+        (
+            unclaimed_1.checked_add(Uint128::try_from(pending_1).map_err(StdError::from)?)?,
+            unclaimed_2.checked_add(Uint128::try_from(pending_2).map_err(StdError::from)?)?,
+        ),
+    ))
+}
+
+pub fn query_sunset_vlps(
+    deps: Deps,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let vlps: Result<_, ContractError> = SUNSET_VLPS
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| {
+            let (vlp, pair) = v?;
+            // Sunset vlps stay registered until they are delisted
+            let metadata = VLPS
+                .may_load(deps.storage, pair.get_tupple())?
+                .and_then(|info| info.metadata);
             Ok(VlpResponse {
-                vlp: v.1,
-                token_1: v.0 .0,
-                token_2: v.0 .1,
+                vlp,
+                token_1: pair.token_1,
+                token_2: pair.token_2,
+                metadata,
             })
         })
         .collect();
@@ -56,17 +478,55 @@ pub fn query_all_vlps(
     Ok(to_json_binary(&AllVlpResponse { vlps: vlps? })?)
 }
 
-pub fn query_vlp(deps: Deps, pair: Pair) -> Result<Binary, ContractError> {
-    let key = pair.get_tupple();
-    let vlp = VLPS.load(deps.storage, key.clone())?;
-
-    Ok(to_json_binary(&VlpResponse {
-        vlp,
-        token_1: key.0,
-        token_2: key.1,
+pub fn query_predict_vlp_address(
+    deps: Deps,
+    env: Env,
+    pair: Pair,
+) -> Result<Binary, ContractError> {
+    pair.validate()?;
+    if let Some(info) = VLPS.may_load(deps.storage, pair.get_tupple())? {
+        return Ok(to_json_binary(&PredictVlpAddressResponse {
+            vlp_address: Addr::unchecked(info.vlp),
+            registered: true,
+        })?);
+    }
+    let (vlp_address, _) = predict_vlp_address(deps, &env, &pair)?;
+    Ok(to_json_binary(&PredictVlpAddressResponse {
+        vlp_address,
+        registered: false,
     })?)
 }
 
+// Instantiate2 address and salt of the next vlp of the pair with the current vlp code id
+pub fn predict_vlp_address(
+    deps: Deps,
+    env: &Env,
+    pair: &Pair,
+) -> Result<(Addr, Binary), ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let generation = VLP_GENERATIONS
+        .may_load(deps.storage, pair.get_tupple())?
+        .unwrap_or_default();
+    let salt = get_vlp_salt(pair, generation);
+
+    let checksum = deps
+        .querier
+        .query_wasm_code_info(state.vlp_code_id)?
+        .checksum;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let address = instantiate2_address(checksum.as_slice(), &creator, &salt).map_err(|err| {
+        ContractError::Generic {
+            err: err.to_string(),
+        }
+    })?;
+    Ok((deps.api.addr_humanize(&address)?, salt))
+}
+
+pub fn query_vlp(deps: Deps, pair: Pair) -> Result<Binary, ContractError> {
+    let info = VLPS.load(deps.storage, pair.get_tupple())?;
+    Ok(to_json_binary(&vlp_response(info))?)
+}
+
 pub fn query_all_chains(deps: Deps) -> Result<Binary, ContractError> {
     let chains: Result<_, ContractError> = CHAIN_UID_TO_CHAIN
         .range(deps.storage, None, None, Order::Ascending)
@@ -123,6 +583,7 @@ pub fn query_simulate_swap(deps: Deps, msg: QuerySimulateSwap) -> Result<Binary,
         asset: msg.asset_in,
         asset_amount: msg.amount_in,
         swaps: next_swaps.to_vec(),
+        asset_out: first_swap.asset_out.clone(),
     };
 
     let simulate_res: euclid::msgs::vlp::GetSwapResponse = deps
@@ -137,9 +598,70 @@ pub fn query_simulate_swap(deps: Deps, msg: QuerySimulateSwap) -> Result<Binary,
     Ok(to_json_binary(&SimulateSwapResponse {
         amount_out: simulate_res.amount_out,
         asset_out: simulate_res.asset_out,
+        hops: simulate_res.hops,
+        execution_price: simulate_res.execution_price,
+        price_impact_bps: simulate_res.price_impact_bps,
+        lp_fee_bps: simulate_res.lp_fee_bps,
+        euclid_fee_bps: simulate_res.euclid_fee_bps,
     })?)
 }
 
+pub fn query_liquidity_depth(
+    deps: Deps,
+    asset_in: Token,
+    asset_out: Token,
+    price_move_bps: u64,
+) -> Result<Binary, ContractError> {
+    let pair = Pair::new(asset_in.clone(), asset_out.clone())?;
+
+    let mut vlps = vec![];
+    if let Some(info) = VLPS.may_load(deps.storage, pair.get_tupple())? {
+        // Sunset vlps don't take swaps anymore
+        if !SUNSET_VLPS.has(deps.storage, info.vlp.clone()) {
+            vlps.push(info.vlp);
+        }
+    }
+    // There are only a few multi asset pools, so all of them are checked
+    for item in MULTI_ASSET_POOLS.range(deps.storage, None, None, Order::Ascending) {
+        let (vlp, tokens) = item?;
+        if tokens.contains(&asset_in) && tokens.contains(&asset_out) {
+            vlps.push(vlp);
+        }
+    }
+
+    let depth_msg = euclid::msgs::vlp::QueryMsg::LiquidityDepth {
+        asset_in: asset_in.clone(),
+        target: DepthTarget::PriceMoveBps(price_move_bps),
+        asset_out: Some(asset_out.clone()),
+    };
+    let mut response = LiquidityDepthResponse {
+        asset_in,
+        asset_out,
+        pools: vec![],
+        total_amount_in: Uint128::zero(),
+        total_amount_out: Uint128::zero(),
+        failed_pools: vec![],
+    };
+    // A pool that can't answer, like a vlp from before the depth query, doesn't fail the others
+    for vlp in vlps {
+        let depth: euclid::msgs::vlp::LiquidityDepthResponse =
+            match deps.querier.query_wasm_smart(vlp.clone(), &depth_msg) {
+                Ok(depth) => depth,
+                Err(err) => {
+                    response.failed_pools.push(PoolQueryError {
+                        vlp_address: vlp,
+                        error: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+        response.total_amount_in = response.total_amount_in.checked_add(depth.amount_in)?;
+        response.total_amount_out = response.total_amount_out.checked_add(depth.amount_out)?;
+        response.pools.push(depth);
+    }
+    Ok(to_json_binary(&response)?)
+}
+
 pub fn query_simulate_escrow_release(
     deps: Deps,
     token: Token,
@@ -183,10 +705,32 @@ pub fn validate_swap_pairs(
         .iter()
         .map(|swap| -> Result<_, ContractError> {
             let pair = Pair::new(swap.token_in.clone(), swap.token_out.clone())?;
-            let vlp_address = VLPS.load(deps.storage, pair.get_tupple())?;
+            // Two token vlps are preferred, multi asset pools need to be told the token to return.
+            // Sunset vlps reject swaps so they are skipped
+            let vlp = VLPS.may_load(deps.storage, pair.get_tupple())?;
+            let sunset = vlp
+                .as_ref()
+                .map(|info| SUNSET_VLPS.has(deps.storage, info.vlp.clone()))
+                .unwrap_or(false);
+            if let Some(info) = vlp.filter(|_| !sunset) {
+                return Ok(NextSwapVlp {
+                    vlp_address: info.vlp,
+                    test_fail: swap.test_fail,
+                    asset_out: None,
+                });
+            }
+            let Some(vlp_address) = MULTI_ASSET_VLPS.may_load(deps.storage, pair.get_tupple())?
+            else {
+                return Err(if sunset {
+                    ContractError::PoolSunset {}
+                } else {
+                    ContractError::PoolDoesNotExists {}
+                });
+            };
             Ok(NextSwapVlp {
                 vlp_address,
                 test_fail: swap.test_fail,
+                asset_out: Some(swap.token_out.clone()),
             })
         })
         .collect();
@@ -253,3 +797,93 @@ pub fn query_all_tokens(
 
     Ok(to_json_binary(&AllTokensResponse { tokens: tokens? })?)
 }
+
+pub fn query_failed_escrow_releases(
+    deps: Deps,
+    user: CrossChainUser,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let releases: Result<_, ContractError> = FAILED_ESCROW_RELEASES
+        .prefix((user.chain_uid, user.address))
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| Ok(v?.1))
+        .collect();
+
+    Ok(to_json_binary(&FailedEscrowReleasesResponse {
+        releases: releases?,
+    })?)
+}
+
+pub fn query_failed_lp_mints(
+    deps: Deps,
+    user: CrossChainUser,
+    pagination: Pagination<String>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let mints: Result<_, ContractError> = FAILED_LP_MINTS
+        .prefix((user.chain_uid, user.address))
+        .range(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|v| Ok(v?.1))
+        .collect();
+
+    Ok(to_json_binary(&FailedLpMintsResponse { mints: mints? })?)
+}
+
+pub fn query_retiring_escrows(
+    deps: Deps,
+    pagination: Pagination<(Token, ChainUid)>,
+) -> Result<Binary, ContractError> {
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+
+    let start = start.map(Bound::inclusive);
+    let end = end.map(Bound::exclusive);
+
+    let escrows: Result<_, ContractError> = RETIRING_ESCROWS
+        .keys(deps.storage, start, end, Order::Ascending)
+        .skip(skip.unwrap_or(0) as usize)
+        .take(limit.unwrap_or(10) as usize)
+        .map(|key| {
+            let (token, chain_uid) = key?;
+            let balance = ESCROW_BALANCES
+                .may_load(deps.storage, (token.clone(), chain_uid.clone()))?
+                .unwrap_or_default();
+            Ok(RetiringEscrowResponse {
+                token,
+                chain_uid,
+                balance,
+            })
+        })
+        .collect();
+
+    Ok(to_json_binary(&RetiringEscrowsResponse {
+        escrows: escrows?,
+    })?)
+}
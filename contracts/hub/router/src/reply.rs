@@ -1,29 +1,39 @@
 use cosmwasm_std::{
-    ensure, from_json, to_json_binary, CosmosMsg, DepsMut, Env, Reply, Response, SubMsgResult,
-    WasmMsg,
+    ensure, from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Env, Reply, Response, SubMsg,
+    SubMsgResult, Uint128, WasmMsg,
 };
 use cw_utils::{
     parse_execute_response_data, parse_reply_execute_data, parse_reply_instantiate_data,
 };
 use euclid::{
+    chain::CrossChainUser,
     error::ContractError,
-    liquidity::{AddLiquidityResponse, RemoveLiquidityResponse},
+    liquidity::{AddLiquidityLegResponse, AddLiquidityResponse, RemoveLiquidityResponse},
     msgs::{
         self,
-        router::ExecuteMsg,
+        router::{ExecuteMsg, VlpMigrationResult},
         vlp::{VlpRemoveLiquidityResponse, VlpSwapResponse},
     },
-    pool::PoolCreationResponse,
+    pool::{get_pool_id, get_pool_pairs, PoolCreationResponse},
     swap::SwapResponse,
+    timeout::get_timeout,
+    utils::generate_tx,
 };
 use euclid_ibc::{
     ack::{make_ack_fail, AcknowledgementMsg},
-    msg::HUB_IBC_EXECUTE_MSG_QUEUE,
+    msg::{HubIbcExecuteMsg, HUB_IBC_EXECUTE_MSG_QUEUE},
 };
 
 use crate::{
+    execute::{delist_vlp, settle_vsl_lp_fees},
     ibc,
-    state::{PENDING_REMOVE_LIQUIDITY, STATE, SWAP_ID_TO_MSG, VLPS},
+    state::{
+        CHAIN_UID_TO_CHAIN, FAILED_ESCROW_RELEASES, MULTI_ASSET_POOLS, MULTI_ASSET_POOL_IDS,
+        MULTI_ASSET_VLPS, PENDING_ESCROW_RELEASE_RETRY, PENDING_LIQUIDITY_INTENT_COMPLETIONS,
+        PENDING_REMOVE_LIQUIDITY, PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY, PENDING_VLP_INSTANTIATE,
+        PENDING_VSL_ADD_LIQUIDITY, STATE, SUNSET_VLPS, SWAP_ID_TO_MSG, VLPS, VLP_MIGRATION,
+        VLP_MIGRATION_QUEUE, VLP_MIGRATION_RESULTS, VSL_LP_SHARES,
+    },
 };
 
 pub const VLP_INSTANTIATE_REPLY_ID: u64 = 1;
@@ -42,6 +52,16 @@ pub const VIRTUAL_BALANCE_TRANSFER_REPLY_ID: u64 = 10;
 pub const IBC_RECEIVE_REPLY_ID: u64 = 11;
 pub const IBC_ACK_AND_TIMEOUT_REPLY_ID: u64 = 12;
 
+pub const ESCROW_RELEASE_RETRY_REPLY_ID: u64 = 13;
+pub const VLP_MIGRATE_REPLY_ID: u64 = 14;
+pub const VSL_ADD_LIQUIDITY_REPLY_ID: u64 = 15;
+pub const VSL_REMOVE_LIQUIDITY_REPLY_ID: u64 = 16;
+pub const ADD_LIQUIDITY_LEG_REPLY_ID: u64 = 17;
+pub const VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID: u64 = 18;
+pub const LIMIT_ORDER_EXECUTE_REPLY_ID: u64 = 19;
+pub const MULTI_VLP_INSTANTIATE_REPLY_ID: u64 = 20;
+pub const POOL_CREATION_ADD_LIQUIDITY_REPLY_ID: u64 = 21;
+
 pub fn on_vlp_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
     match msg.result.clone() {
         SubMsgResult::Err(err) => Err(ContractError::InstantiateError { err }),
@@ -53,15 +73,17 @@ pub fn on_vlp_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, C
 
             let vlp_address = instantiate_data.contract_address;
 
-            let liquidity: msgs::vlp::GetLiquidityResponse = deps
-                .querier
-                .query_wasm_smart(vlp_address.clone(), &msgs::vlp::QueryMsg::Liquidity {})?;
-
-            VLPS.save(
-                deps.storage,
-                (liquidity.pair.token_1, liquidity.pair.token_2),
-                &vlp_address,
-            )?;
+            // Pair and address were known before instantiating, only make sure the prediction held
+            let vlp = PENDING_VLP_INSTANTIATE.load(deps.storage)?;
+            PENDING_VLP_INSTANTIATE.remove(deps.storage);
+            ensure!(
+                vlp.vlp == vlp_address,
+                ContractError::Instantiate2AddressMismatch {
+                    expected: Addr::unchecked(vlp.vlp),
+                    received: Addr::unchecked(vlp_address),
+                }
+            );
+            VLPS.save(deps.storage, vlp.pair.get_tupple(), &vlp)?;
 
             let pool_creation_response =
                 from_json::<PoolCreationResponse>(instantiate_data.data.unwrap_or_default());
@@ -84,6 +106,45 @@ pub fn on_vlp_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, C
     }
 }
 
+pub fn on_multi_vlp_instantiate_reply(
+    deps: DepsMut,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.result.clone() {
+        SubMsgResult::Err(err) => Err(ContractError::InstantiateError { err }),
+        SubMsgResult::Ok(..) => {
+            let instantiate_data =
+                parse_reply_instantiate_data(msg).map_err(|res| ContractError::Generic {
+                    err: res.to_string(),
+                })?;
+            let vlp_address = instantiate_data.contract_address;
+
+            let state: msgs::multi_vlp::StateResponse = deps
+                .querier
+                .query_wasm_smart(vlp_address.clone(), &msgs::multi_vlp::QueryMsg::State {})?;
+
+            MULTI_ASSET_POOLS.save(deps.storage, vlp_address.clone(), &state.tokens)?;
+            MULTI_ASSET_POOL_IDS.save(deps.storage, get_pool_id(&state.tokens), &vlp_address)?;
+            // Pairs already served by another multi asset pool keep routing to it
+            for pair in get_pool_pairs(&state.tokens)? {
+                if !MULTI_ASSET_VLPS.has(deps.storage, pair.get_tupple()) {
+                    MULTI_ASSET_VLPS.save(deps.storage, pair.get_tupple(), &vlp_address)?;
+                }
+            }
+
+            // Acknowledgement for pools requested by a factory, ignored for hub requests
+            let ack = AcknowledgementMsg::Ok(PoolCreationResponse {
+                vlp_contract: vlp_address.clone(),
+                mint_lp_tokens: None,
+            });
+            Ok(Response::new()
+                .add_attribute("action", "reply_multi_vlp_instantiate")
+                .add_attribute("vlp", vlp_address)
+                .set_data(to_json_binary(&ack)?))
+        }
+    }
+}
+
 pub fn on_pool_register_reply(_deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
     match msg.result.clone() {
         SubMsgResult::Err(err) => Err(ContractError::Generic { err }),
@@ -128,8 +189,37 @@ pub fn on_add_liquidity_reply(_deps: DepsMut, msg: Reply) -> Result<Response, Co
     }
 }
 
+// Initial liquidity of a pool creation request, acknowledged as the pool creation itself
+pub fn on_pool_creation_add_liquidity_reply(
+    _deps: DepsMut,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.result.clone() {
+        SubMsgResult::Err(err) => Err(ContractError::Generic { err }),
+        SubMsgResult::Ok(..) => {
+            let execute_data =
+                parse_reply_execute_data(msg).map_err(|res| ContractError::Generic {
+                    err: res.to_string(),
+                })?;
+            let liquidity_response: AddLiquidityResponse =
+                from_json(execute_data.data.unwrap_or_default())?;
+
+            let ack = AcknowledgementMsg::Ok(PoolCreationResponse {
+                vlp_contract: liquidity_response.vlp_address.clone(),
+                mint_lp_tokens: Some(liquidity_response.mint_lp_tokens),
+            });
+
+            Ok(Response::new()
+                .add_attribute("action", "reply_pool_creation_add_liquidity")
+                .add_attribute("vlp", liquidity_response.vlp_address)
+                .add_attribute("mint_lp_tokens", liquidity_response.mint_lp_tokens)
+                .set_data(to_json_binary(&ack)?))
+        }
+    }
+}
+
 pub fn on_remove_liquidity_reply(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     msg: Reply,
 ) -> Result<Response, ContractError> {
@@ -151,36 +241,44 @@ pub fn on_remove_liquidity_reply(
             let remove_liquidity_tx = req_key.load(deps.storage)?;
             req_key.remove(deps.storage);
 
-            let token_1_escrow_release_msg =
-                euclid::msgs::router::ExecuteMsg::ReleaseEscrowInternal {
-                    sender: remove_liquidity_tx.sender.clone(),
-                    token: remove_liquidity_tx.pair.token_1.clone(),
-                    amount: Some(vlp_liquidity_response.token_1_liquidity_released),
-                    cross_chain_addresses: remove_liquidity_tx.cross_chain_addresses.clone(),
-                    timeout: None,
-                    tx_id: vlp_liquidity_response.tx_id.clone(),
-                };
-
-            let token_1_escrow_release_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: env.contract.address.to_string(),
-                msg: to_json_binary(&token_1_escrow_release_msg)?,
-                funds: vec![],
-            });
-
-            let token_2_escrow_release_msg =
-                euclid::msgs::router::ExecuteMsg::ReleaseEscrowInternal {
-                    sender: remove_liquidity_tx.sender,
-                    token: remove_liquidity_tx.pair.token_2,
-                    amount: Some(vlp_liquidity_response.token_2_liquidity_released),
-                    cross_chain_addresses: remove_liquidity_tx.cross_chain_addresses,
-                    timeout: None,
-                    tx_id: vlp_liquidity_response.tx_id,
-                };
-            let token_2_escrow_release_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: env.contract.address.to_string(),
-                msg: to_json_binary(&token_2_escrow_release_msg)?,
-                funds: vec![],
-            });
+            // Vlp already credited the released tokens to the sender's virtual balance
+            let mut release_msgs: Vec<CosmosMsg> = vec![];
+            if !remove_liquidity_tx.keep_in_virtual_balance {
+                let token_1_escrow_release_msg =
+                    euclid::msgs::router::ExecuteMsg::ReleaseEscrowInternal {
+                        sender: remove_liquidity_tx.sender.clone(),
+                        token: remove_liquidity_tx.pair.token_1.clone(),
+                        amount: Some(vlp_liquidity_response.token_1_liquidity_released),
+                        cross_chain_addresses: remove_liquidity_tx.cross_chain_addresses.clone(),
+                        timeout: None,
+                        tx_id: vlp_liquidity_response.tx_id.clone(),
+                        original_tx_id: None,
+                    };
+
+                let token_1_escrow_release_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&token_1_escrow_release_msg)?,
+                    funds: vec![],
+                });
+
+                let token_2_escrow_release_msg =
+                    euclid::msgs::router::ExecuteMsg::ReleaseEscrowInternal {
+                        sender: remove_liquidity_tx.sender.clone(),
+                        token: remove_liquidity_tx.pair.token_2.clone(),
+                        amount: Some(vlp_liquidity_response.token_2_liquidity_released),
+                        cross_chain_addresses: remove_liquidity_tx.cross_chain_addresses.clone(),
+                        timeout: None,
+                        tx_id: vlp_liquidity_response.tx_id.clone(),
+                        original_tx_id: None,
+                    };
+                let token_2_escrow_release_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&token_2_escrow_release_msg)?,
+                    funds: vec![],
+                });
+                release_msgs.push(token_1_escrow_release_msg);
+                release_msgs.push(token_2_escrow_release_msg);
+            }
 
             let liquidity_response = RemoveLiquidityResponse {
                 token_1_liquidity: vlp_liquidity_response.token_1_liquidity_released,
@@ -191,16 +289,188 @@ pub fn on_remove_liquidity_reply(
 
             let ack = AcknowledgementMsg::Ok(liquidity_response.clone());
 
+            let delist_response =
+                delist_drained_vlp(&mut deps, &env, liquidity_response.vlp_address.clone())?;
+
             Ok(Response::new()
                 .add_attribute("action", "reply_remove_liquidity")
                 .add_attribute("liquidity", format!("{liquidity_response:?}"))
-                .add_message(token_1_escrow_release_msg)
-                .add_message(token_2_escrow_release_msg)
+                .add_attribute(
+                    "keep_in_virtual_balance",
+                    remove_liquidity_tx.keep_in_virtual_balance.to_string(),
+                )
+                .add_messages(release_msgs)
+                .add_submessages(delist_response.messages)
+                .add_events(delist_response.events)
+                .add_attributes(delist_response.attributes)
                 .set_data(to_json_binary(&ack)?))
         }
     }
 }
 
+// Delist a sunset vlp once the last of its liquidity is withdrawn
+fn delist_drained_vlp(
+    deps: &mut DepsMut,
+    env: &Env,
+    vlp_address: String,
+) -> Result<Response, ContractError> {
+    let Some(pair) = SUNSET_VLPS.may_load(deps.storage, vlp_address.clone())? else {
+        return Ok(Response::new());
+    };
+    let liquidity: msgs::vlp::GetLiquidityResponse = deps
+        .querier
+        .query_wasm_smart(vlp_address.clone(), &msgs::vlp::QueryMsg::Liquidity {})?;
+    // Lp tokens locked by the first deposit can never be withdrawn
+    if liquidity.total_lp_tokens > liquidity.locked_lp_tokens {
+        return Ok(Response::new());
+    }
+    delist_vlp(deps, env, vlp_address, pair)
+}
+
+pub fn on_add_liquidity_leg_reply(
+    mut deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let execute_data = parse_reply_execute_data(msg).map_err(|res| ContractError::Generic {
+        err: res.to_string(),
+    })?;
+    let liquidity_response: AddLiquidityResponse =
+        from_json(execute_data.data.unwrap_or_default())?;
+
+    let completion_key =
+        PENDING_LIQUIDITY_INTENT_COMPLETIONS.key(liquidity_response.vlp_address.clone());
+    let (intent_id, recipient) = completion_key.load(deps.storage)?;
+    completion_key.remove(deps.storage);
+
+    // Lp tokens are minted on the recipient chain, which can differ from both deposit chains
+    let mint_msg = mint_lp_tokens_msg(
+        &mut deps,
+        &env,
+        &recipient,
+        liquidity_response.vlp_address.clone(),
+        liquidity_response.mint_lp_tokens,
+    )?;
+
+    let ack = AcknowledgementMsg::Ok(AddLiquidityLegResponse {
+        intent_id: intent_id.clone(),
+        completed: true,
+    });
+
+    Ok(Response::new()
+        .add_attribute("action", "reply_add_liquidity_leg")
+        .add_attribute("intent_id", intent_id)
+        .add_attribute("recipient", recipient.to_sender_string())
+        .add_attribute("mint_lp_tokens", liquidity_response.mint_lp_tokens)
+        .add_submessage(mint_msg)
+        .set_data(to_json_binary(&ack)?))
+}
+
+pub fn on_virtual_balance_add_liquidity_reply(
+    mut deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let execute_data = parse_reply_execute_data(msg).map_err(|res| ContractError::Generic {
+        err: res.to_string(),
+    })?;
+    let liquidity_response: AddLiquidityResponse =
+        from_json(execute_data.data.unwrap_or_default())?;
+
+    let recipient_key =
+        PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY.key(liquidity_response.vlp_address.clone());
+    let recipient = recipient_key.load(deps.storage)?;
+    recipient_key.remove(deps.storage);
+
+    let mint_msg = mint_lp_tokens_msg(
+        &mut deps,
+        &env,
+        &recipient,
+        liquidity_response.vlp_address.clone(),
+        liquidity_response.mint_lp_tokens,
+    )?;
+
+    let ack = AcknowledgementMsg::Ok(liquidity_response.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "reply_virtual_balance_add_liquidity")
+        .add_attribute("recipient", recipient.to_sender_string())
+        .add_attribute("mint_lp_tokens", liquidity_response.mint_lp_tokens)
+        .add_submessage(mint_msg)
+        .set_data(to_json_binary(&ack)?))
+}
+
+pub fn mint_lp_tokens_msg(
+    deps: &mut DepsMut,
+    env: &Env,
+    recipient: &CrossChainUser,
+    vlp_address: String,
+    amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+    let chain = CHAIN_UID_TO_CHAIN.load(deps.storage, recipient.chain_uid.clone())?;
+    let tx_id = generate_tx(deps.branch(), env, recipient)?;
+    HubIbcExecuteMsg::MintLpTokens {
+        chain_uid: recipient.chain_uid.clone(),
+        recipient: recipient.address.clone(),
+        vlp_address,
+        amount,
+        tx_id,
+    }
+    .to_msg(deps, env, chain, get_timeout(None)?)
+}
+
+pub fn on_vsl_add_liquidity_reply(
+    mut deps: DepsMut,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let execute_data = parse_reply_execute_data(msg).map_err(|res| ContractError::Generic {
+        err: res.to_string(),
+    })?;
+    let liquidity_response: AddLiquidityResponse =
+        from_json(execute_data.data.unwrap_or_default())?;
+
+    let sender =
+        PENDING_VSL_ADD_LIQUIDITY.load(deps.storage, liquidity_response.vlp_address.clone())?;
+    PENDING_VSL_ADD_LIQUIDITY.remove(deps.storage, liquidity_response.vlp_address.clone());
+
+    settle_vsl_lp_fees(&mut deps, &sender, &liquidity_response.vlp_address)?;
+    let shares_key = VSL_LP_SHARES.key((sender.clone(), liquidity_response.vlp_address.clone()));
+    let shares = shares_key.may_load(deps.storage)?.unwrap_or_default();
+    shares_key.save(
+        deps.storage,
+        &shares.checked_add(liquidity_response.mint_lp_tokens)?,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reply_vsl_add_liquidity")
+        .add_attribute("sender", sender)
+        .add_attribute("vlp_address", liquidity_response.vlp_address)
+        .add_attribute("mint_lp_tokens", liquidity_response.mint_lp_tokens))
+}
+
+pub fn on_vsl_remove_liquidity_reply(
+    mut deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let execute_data = parse_reply_execute_data(msg).map_err(|res| ContractError::Generic {
+        err: res.to_string(),
+    })?;
+    let liquidity_response: VlpRemoveLiquidityResponse =
+        from_json(execute_data.data.unwrap_or_default())?;
+
+    let delist_response =
+        delist_drained_vlp(&mut deps, &env, liquidity_response.vlp_address.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reply_vsl_remove_liquidity")
+        .add_attribute("vlp_address", liquidity_response.vlp_address)
+        .add_attribute("burn_lp_tokens", liquidity_response.burn_lp_tokens)
+        .add_submessages(delist_response.messages)
+        .add_events(delist_response.events)
+        .add_attributes(delist_response.attributes))
+}
+
 pub fn on_swap_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.result.clone() {
         SubMsgResult::Err(err) => Err(ContractError::Generic { err }),
@@ -247,6 +517,7 @@ pub fn on_swap_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, Co
                 cross_chain_addresses: swap_msg.cross_chain_addresses,
                 timeout: None,
                 tx_id: swap_msg.tx_id,
+                original_tx_id: None,
             };
 
             Ok(Response::new()
@@ -347,6 +618,83 @@ pub fn on_ibc_ack_and_timeout_reply(_deps: DepsMut, msg: Reply) -> Result<Respon
     }
 }
 
+pub fn on_escrow_release_retry_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let failed_key = PENDING_ESCROW_RELEASE_RETRY.may_load(deps.storage)?;
+    PENDING_ESCROW_RELEASE_RETRY.remove(deps.storage);
+    match msg.result.clone() {
+        // Virtual balance is already minted back to the user, so a failed retry only has to be
+        // unmarked on the failed release so that it isn't reported as in flight
+        SubMsgResult::Err(err) => {
+            if let Some(failed_key) = failed_key {
+                FAILED_ESCROW_RELEASES.update(
+                    deps.storage,
+                    failed_key,
+                    |release| -> Result<_, ContractError> {
+                        let mut release =
+                            release.ok_or(ContractError::new("Failed escrow release not found"))?;
+                        release.retried = false;
+                        release.retry_tx_id = None;
+                        Ok(release)
+                    },
+                )?;
+            }
+            Ok(Response::new()
+                .add_attribute("action", "reply_escrow_release_retry")
+                .add_attribute("retry_success", "false")
+                .add_attribute("error", err))
+        }
+        SubMsgResult::Ok(..) => Ok(Response::new()
+            .add_attribute("action", "reply_escrow_release_retry")
+            .add_attribute("retry_success", "true")),
+    }
+}
+
+pub fn on_limit_order_execute_reply(_deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        // The failed fill is reverted as a whole, so the order keeps resting with its escrowed amount
+        SubMsgResult::Err(err) => Ok(Response::new()
+            .add_attribute("action", "reply_limit_order_execute")
+            .add_attribute("filled", "false")
+            .add_attribute("error", err)),
+        SubMsgResult::Ok(..) => Ok(Response::new()
+            .add_attribute("action", "reply_limit_order_execute")
+            .add_attribute("filled", "true")),
+    }
+}
+
+pub fn on_vlp_migrate_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let mut queue = VLP_MIGRATION_QUEUE.load(deps.storage)?;
+    ensure!(
+        !queue.is_empty(),
+        ContractError::new("Vlp migration queue is empty")
+    );
+    let vlp = queue.remove(0);
+    VLP_MIGRATION_QUEUE.save(deps.storage, &queue)?;
+
+    let mut migration = VLP_MIGRATION.load(deps.storage)?;
+    let error = match msg.result {
+        SubMsgResult::Ok(..) => None,
+        SubMsgResult::Err(err) => Some(err),
+    };
+    if error.is_some() {
+        migration.failed += 1;
+        VLP_MIGRATION.save(deps.storage, &migration)?;
+    }
+    let result = VlpMigrationResult {
+        vlp: vlp.clone(),
+        code_id: migration.code_id,
+        success: error.is_none(),
+        error,
+    };
+    VLP_MIGRATION_RESULTS.save(deps.storage, vlp.clone(), &result)?;
+
+    // Failed migrations are only recorded so that the rest of the batch can continue
+    Ok(Response::new()
+        .add_attribute("action", "reply_vlp_migrate")
+        .add_attribute("vlp", vlp)
+        .add_attribute("success", result.success.to_string()))
+}
+
 pub fn on_ibc_receive_reply(_deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
     match msg.result.clone() {
         SubMsgResult::Err(err) => Ok(Response::new()
@@ -1,9 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal256, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use euclid::{
-    chain::{Chain, ChainUid},
-    token::Token,
+    chain::{Chain, ChainUid, CrossChainUser, CrossChainUserWithLimit},
+    escrow::FailedEscrowRelease,
+    fee::FeeRecipient,
+    liquidity::{FailedLpMint, LiquidityIntent},
+    msgs::router::{VlpMetadata, VlpMigrationResult},
+    pool::PoolCreationMode,
+    swap::LimitOrder,
+    token::{Pair, Token},
 };
 use euclid_ibc::msg::{ChainIbcRemoveLiquidityExecuteMsg, ChainIbcSwapExecuteMsg};
 
@@ -19,12 +25,95 @@ pub struct State {
 
 pub const STATE: Item<State> = Item::new("state");
 
-// Convert it to multi index map?
-pub const VLPS: Map<(Token, Token), String> = Map::new("vlps");
+#[cw_serde]
+pub struct VlpInfo {
+    pub vlp: String,
+    pub pair: Pair,
+    // Unset for vlps created before pool metadata was recorded
+    pub metadata: Option<VlpMetadata>,
+}
+
+pub struct VlpIndexes<'a> {
+    pub token_1: MultiIndex<'a, Token, VlpInfo, (Token, Token)>,
+    pub token_2: MultiIndex<'a, Token, VlpInfo, (Token, Token)>,
+}
+
+impl<'a> IndexList<VlpInfo> for VlpIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<VlpInfo>> + '_> {
+        let v: Vec<&dyn Index<VlpInfo>> = vec![&self.token_1, &self.token_2];
+        Box::new(v.into_iter())
+    }
+}
+
+// Vlp of each pair, indexed by both tokens so that all pools of a token can be listed
+pub const VLPS: IndexedMap<(Token, Token), VlpInfo, VlpIndexes> = IndexedMap::new(
+    "vlps",
+    VlpIndexes {
+        token_1: MultiIndex::new(
+            |_pk, info| info.pair.token_1.clone(),
+            "vlps",
+            "vlps__token_1",
+        ),
+        token_2: MultiIndex::new(
+            |_pk, info| info.pair.token_2.clone(),
+            "vlps",
+            "vlps__token_2",
+        ),
+    },
+);
+
+// Vlp being instantiated with its predicted address, read back in the instantiate reply
+pub const PENDING_VLP_INSTANTIATE: Item<VlpInfo> = Item::new("pending_vlp_instantiate");
+
+// Number of times the vlp of a pair was delisted, part of the instantiate2 salt of the next vlp
+pub const VLP_GENERATIONS: Map<(Token, Token), u64> = Map::new("vlp_generations");
+
+// Code id used to instantiate multi asset pools, unset until the admin uploads one
+pub const MULTI_VLP_CODE_ID: Item<u64> = Item::new("multi_vlp_code_id");
+
+// Sorted tokens of every multi asset pool, keyed by pool address
+pub const MULTI_ASSET_POOLS: Map<String, Vec<Token>> = Map::new("multi_asset_pools");
+
+// Pool address keyed by the pool id of its token set, prevents duplicate pools
+pub const MULTI_ASSET_POOL_IDS: Map<String, String> = Map::new("multi_asset_pool_ids");
+
+// Swap route index of multi asset pools, every token pair of a pool points to it.
+// A pair keeps the first pool that contained it, two token vlps take priority when routing
+pub const MULTI_ASSET_VLPS: Map<(Token, Token), String> = Map::new("multi_asset_vlps");
+
+// Pool creation mode of each chain, chains without an entry are open
+pub const POOL_CREATION_MODES: Map<ChainUid, PoolCreationMode> = Map::new("pool_creation_modes");
+
+// Euclid fee split used by vlps that don't set their own fee recipients
+pub const DEFAULT_FEE_RECIPIENTS: Item<Vec<FeeRecipient>> = Item::new("default_fee_recipients");
+
+// Vlps that only allow liquidity withdrawals, delisted once all liquidity is removed
+pub const SUNSET_VLPS: Map<String, Pair> = Map::new("sunset_vlps");
+
+// LP shares of users that provide liquidity from their virtual balance on the hub, (User, VLP)
+pub const VSL_LP_SHARES: Map<(Addr, String), Uint128> = Map::new("vsl_lp_shares");
+
+// Vlp fee growth of (token_1, token_2) at the last settlement of a hub lp's shares, (User, VLP)
+pub const VSL_LP_FEE_CHECKPOINTS: Map<(Addr, String), (Decimal256, Decimal256)> =
+    Map::new("vsl_lp_fee_checkpoints");
+
+// Claimable lp fees of (token_1, token_2) settled to a hub lp's shares, (User, VLP)
+pub const VSL_UNCLAIMED_LP_FEES: Map<(Addr, String), (Uint128, Uint128)> =
+    Map::new("vsl_unclaimed_lp_fees");
+
+// Hub user waiting for the add liquidity reply of a vlp
+pub const PENDING_VSL_ADD_LIQUIDITY: Map<String, Addr> = Map::new("pending_vsl_add_liquidity");
 
 // Token escrow balance on each chain
 pub const ESCROW_BALANCES: Map<(Token, ChainUid), Uint128> = Map::new("escrow_balances");
 
+// Token escrows that don't accept new deposits and are removed once their balance is withdrawn
+pub const RETIRING_ESCROWS: Map<(Token, ChainUid), bool> = Map::new("retiring_escrows");
+
+// Escrow releases sent to a chain that are not acknowledged yet, a retiring escrow is only removed once none are left
+pub const ESCROW_RELEASES_IN_FLIGHT: Map<(Token, ChainUid), u64> =
+    Map::new("escrow_releases_in_flight");
+
 pub const CHAIN_UID_TO_CHAIN: Map<ChainUid, Chain> = Map::new("chain_uid_to_chain");
 pub const CHANNEL_TO_CHAIN_UID: Map<String, ChainUid> = Map::new("channel_to_chain_uid");
 pub const DEREGISTERED_CHAINS: Item<Vec<ChainUid>> = Item::new("deregistered_chains");
@@ -38,3 +127,66 @@ pub const PENDING_REMOVE_LIQUIDITY: Map<
     (ChainUid, String, String),
     ChainIbcRemoveLiquidityExecuteMsg,
 > = Map::new("pending_remove_liquidity");
+
+#[cw_serde]
+pub struct PendingEscrowRelease {
+    // Addresses that come after the release address in the original list, used for retrying a failed release
+    pub cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+    pub timeout: u64,
+    // Tx id of the escrow release request, kept the same through retries
+    pub original_tx_id: Option<String>,
+}
+
+// Map for release TX ID to the pending escrow release
+pub const PENDING_ESCROW_RELEASES: Map<String, PendingEscrowRelease> =
+    Map::new("pending_escrow_releases");
+
+// Map for (Sender ChainUID, Sender, TX ID)
+pub const FAILED_ESCROW_RELEASES: Map<(ChainUid, String, String), FailedEscrowRelease> =
+    Map::new("failed_escrow_releases");
+
+// Key of the failed escrow release whose retry is executing, cleared by the retry reply
+pub const PENDING_ESCROW_RELEASE_RETRY: Item<(ChainUid, String, String)> =
+    Item::new("pending_escrow_release_retry");
+
+// Map for (Recipient ChainUID, Recipient, TX ID)
+pub const FAILED_LP_MINTS: Map<(ChainUid, String, String), FailedLpMint> =
+    Map::new("failed_lp_mints");
+
+#[cw_serde]
+pub struct VlpMigration {
+    // Code id the vlps are being migrated to
+    pub code_id: u64,
+    pub last_pair: Option<(Token, Token)>,
+    pub finished: bool,
+    // Vlps that failed to migrate in the current pass, a finished pass with failures can be run again
+    #[serde(default)]
+    pub failed: u64,
+}
+
+pub const VLP_MIGRATION: Item<VlpMigration> = Item::new("vlp_migration");
+
+// Vlps waiting for their migrate reply, replies come back in the same order as the messages
+pub const VLP_MIGRATION_QUEUE: Item<Vec<String>> = Item::new("vlp_migration_queue");
+
+// Result of the last migration attempt for each vlp
+pub const VLP_MIGRATION_RESULTS: Map<String, VlpMigrationResult> =
+    Map::new("vlp_migration_results");
+
+// Liquidity intents waiting for their second leg, keyed by (sender of the held leg, intent id)
+pub const LIQUIDITY_INTENTS: Map<(String, String), LiquidityIntent> = Map::new("liquidity_intents");
+
+// Intent id and lp recipient of a completed intent, read back in the add liquidity reply
+pub const PENDING_LIQUIDITY_INTENT_COMPLETIONS: Map<String, (String, CrossChainUser)> =
+    Map::new("pending_liquidity_intent_completions");
+
+// Lp recipient of liquidity added from virtual balance, read back in the add liquidity reply
+pub const PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY: Map<String, CrossChainUser> =
+    Map::new("pending_virtual_balance_add_liquidity");
+
+// Resting limit orders, the order amount is held in the router's virtual balance
+pub const LIMIT_ORDERS: Map<u64, LimitOrder> = Map::new("limit_orders");
+pub const LIMIT_ORDER_COUNT: Item<u64> = Item::new("limit_order_count");
+
+// Order ids placed by each hub user, (Owner, Order ID)
+pub const USER_LIMIT_ORDERS: Map<(Addr, u64), ()> = Map::new("user_limit_orders");
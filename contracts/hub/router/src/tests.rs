@@ -1,13 +1,52 @@
 #[cfg(test)]
 mod tests {
-    use crate::contract::{execute, instantiate};
-    use crate::state::{State, CHAIN_UID_TO_CHAIN, STATE};
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{from_json, CosmosMsg, DepsMut, IbcMsg, MessageInfo, Response};
-    use euclid::chain::{Chain, ChainUid, IbcChain};
+    use crate::contract::{execute, instantiate, query, reply};
+    use crate::ibc::ack_and_timeout::{ibc_ack_mint_lp_tokens, ibc_ack_release_escrow};
+    use crate::ibc::receive::reusable_internal_call;
+    use crate::migrate::migrate;
+    use crate::query::validate_swap_pairs;
+    use crate::reply::{
+        ADD_LIQUIDITY_LEG_REPLY_ID, ESCROW_RELEASE_RETRY_REPLY_ID, LIMIT_ORDER_EXECUTE_REPLY_ID,
+        POOL_CREATION_ADD_LIQUIDITY_REPLY_ID, REMOVE_LIQUIDITY_REPLY_ID,
+        VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID, VLP_MIGRATE_REPLY_ID,
+        VSL_REMOVE_LIQUIDITY_REPLY_ID,
+    };
+    use crate::state::{
+        PendingEscrowRelease, State, VlpInfo, CHAIN_UID_TO_CHAIN, ESCROW_BALANCES,
+        ESCROW_RELEASES_IN_FLIGHT, FAILED_ESCROW_RELEASES, LIMIT_ORDERS, LIQUIDITY_INTENTS,
+        MULTI_ASSET_POOLS, MULTI_ASSET_VLPS, PENDING_ESCROW_RELEASES, PENDING_ESCROW_RELEASE_RETRY,
+        PENDING_REMOVE_LIQUIDITY, PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY, RETIRING_ESCROWS, STATE,
+        SUNSET_VLPS, USER_LIMIT_ORDERS, VLPS, VSL_LP_SHARES,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockQuerier};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, Binary, ContractInfoResponse, ContractResult, CosmosMsg,
+        Decimal, Decimal256, DepsMut, IbcMsg, MessageInfo, OwnedDeps, Reply, Response, StdError,
+        StdResult, Storage, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128,
+        WasmMsg, WasmQuery,
+    };
+    use cw_storage_plus::Map;
+    use euclid::chain::{Chain, ChainUid, CrossChainUser, CrossChainUserWithLimit, IbcChain};
     use euclid::error::ContractError;
-    use euclid::msgs::router::{ExecuteMsg, InstantiateMsg, RegisterFactoryChainNative};
-    use euclid_ibc::msg::HubIbcExecuteMsg;
+    use euclid::liquidity::{AddLiquidityLegResponse, AddLiquidityResponse};
+    use euclid::msgs::factory::ReleaseEscrowResponse;
+    use euclid::msgs::router::{
+        AllVlpResponse, ExecuteMsg, FailedEscrowReleasesResponse, FailedLpMintsResponse,
+        InstantiateMsg, LimitOrdersResponse, MigrateMsg, PoolCreationModeResponse, QueryMsg,
+        RegisterFactoryChainNative, UnclaimedVslLpFeesResponse, VlpMigrationResult,
+        VlpMigrationStatusResponse, VlpResponse, VslLpShareResponse, VslLpSharesResponse,
+    };
+    use euclid::msgs::vlp::FeeGrowthResponse;
+    use euclid::msgs::vlp::{GetSwapResponse, VlpRemoveLiquidityResponse};
+    use euclid::pool::{EscrowRetirementResponse, PoolCreationMode, PoolCreationResponse};
+    use euclid::swap::NextSwapPair;
+    use euclid::token::{Pair, Token};
+    use euclid::utils::Pagination;
+    use euclid_ibc::ack::AcknowledgementMsg;
+    use euclid_ibc::msg::{
+        ChainIbcAddLiquidityLegExecuteMsg, ChainIbcExecuteMsg, ChainIbcRemoveLiquidityExecuteMsg,
+        ChainIbcSwapExecuteMsg, HubIbcExecuteMsg,
+    };
 
     struct TestExecuteMsg {
         name: &'static str,
@@ -23,6 +62,19 @@ mod tests {
         instantiate(deps, mock_env(), info, msg).unwrap()
     }
 
+    fn save_vlp(storage: &mut dyn Storage, pair: &Pair, vlp: &str) {
+        VLPS.save(
+            storage,
+            pair.get_tupple(),
+            &VlpInfo {
+                vlp: vlp.to_string(),
+                pair: pair.clone(),
+                metadata: None,
+            },
+        )
+        .unwrap();
+    }
+
     #[test]
     fn test_instantiate() {
         let mut deps = mock_dependencies();
@@ -235,4 +287,1815 @@ mod tests {
             .unwrap();
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
     }
+
+    #[test]
+    fn test_ibc_ack_release_escrow_failure() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let mut state = STATE.load(deps.as_ref().storage).unwrap();
+        state.virtual_balance_address = Some(Addr::unchecked("virtual_balance"));
+        STATE.save(deps.as_mut().storage, &state).unwrap();
+
+        let sender = CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+            address: "user".to_string(),
+        };
+        let chain_uid = ChainUid::create("chaina".to_string()).unwrap();
+        let token = Token::create("eucl".to_string()).unwrap();
+        let next_address = CrossChainUserWithLimit {
+            user: CrossChainUser {
+                chain_uid: ChainUid::create("chainb".to_string()).unwrap(),
+                address: "user_b".to_string(),
+            },
+            limit: None,
+        };
+
+        // Release with a fallback address is retried after the re-mint
+        PENDING_ESCROW_RELEASES
+            .save(
+                deps.as_mut().storage,
+                "tx_1".to_string(),
+                &PendingEscrowRelease {
+                    cross_chain_addresses: vec![next_address.clone()],
+                    timeout: 60,
+                    original_tx_id: Some("tx_0".to_string()),
+                },
+            )
+            .unwrap();
+        let res = ibc_ack_release_escrow(
+            deps.as_mut(),
+            env.clone(),
+            chain_uid.clone(),
+            sender.clone(),
+            Uint128::new(100),
+            token.clone(),
+            "user_a".to_string(),
+            AcknowledgementMsg::Error("Timeout".to_string()),
+            "tx_1".to_string(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[1].msg else {
+            panic!("Expected retry message");
+        };
+        // The retry runs under a new tx id linked to the original request
+        let ExecuteMsg::ReleaseEscrowInternal {
+            cross_chain_addresses,
+            timeout,
+            tx_id,
+            original_tx_id,
+            ..
+        } = from_json::<ExecuteMsg>(msg).unwrap()
+        else {
+            panic!("Expected escrow release");
+        };
+        assert_eq!(cross_chain_addresses, vec![next_address]);
+        assert_eq!(timeout, Some(60));
+        assert_ne!(tx_id, "tx_1");
+        assert_eq!(original_tx_id, Some("tx_0".to_string()));
+        assert!(!PENDING_ESCROW_RELEASES.has(deps.as_ref().storage, "tx_1".to_string()));
+
+        // Release without a fallback address only re-mints
+        let res = ibc_ack_release_escrow(
+            deps.as_mut(),
+            env.clone(),
+            chain_uid.clone(),
+            sender.clone(),
+            Uint128::new(50),
+            token.clone(),
+            "user_a".to_string(),
+            AcknowledgementMsg::Error("Timeout".to_string()),
+            "tx_2".to_string(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        // Escrow balance is restored for both failed releases
+        let escrow_balance = ESCROW_BALANCES
+            .load(deps.as_ref().storage, (token, chain_uid))
+            .unwrap();
+        assert_eq!(escrow_balance, Uint128::new(150));
+
+        let res: FailedEscrowReleasesResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetFailedEscrowReleases {
+                    user: sender,
+                    pagination: Pagination::new(None, None, None, None),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.releases.len(), 2);
+        assert!(res.releases[0].retried);
+        assert_eq!(res.releases[0].retry_tx_id, Some(tx_id));
+        assert!(!res.releases[1].retried);
+        assert!(res.releases[1].retry_tx_id.is_none());
+        assert_eq!(res.releases[1].amount, Uint128::new(50));
+
+        // A retry that fails is no longer reported as in flight
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: ESCROW_RELEASE_RETRY_REPLY_ID,
+                result: SubMsgResult::Err("Escrow not found".to_string()),
+            },
+        )
+        .unwrap();
+        let release = FAILED_ESCROW_RELEASES
+            .load(
+                deps.as_ref().storage,
+                (
+                    ChainUid::vsl_chain_uid().unwrap(),
+                    "user".to_string(),
+                    "tx_1".to_string(),
+                ),
+            )
+            .unwrap();
+        assert!(!release.retried);
+        assert!(release.retry_tx_id.is_none());
+        assert!(!PENDING_ESCROW_RELEASE_RETRY.exists(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn test_retry_failed_lp_mint() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let chain_uid = ChainUid::create("chaina".to_string()).unwrap();
+        CHAIN_UID_TO_CHAIN
+            .save(
+                deps.as_mut().storage,
+                chain_uid.clone(),
+                &Chain {
+                    factory_chain_id: "1".to_string(),
+                    factory: "factory".to_string(),
+                    chain_type: euclid::chain::ChainType::Native {},
+                },
+            )
+            .unwrap();
+        let recipient = CrossChainUser {
+            chain_uid: chain_uid.clone(),
+            address: "lp_owner".to_string(),
+        };
+
+        // A rejected mint is kept so the lp tokens can be sent again
+        ibc_ack_mint_lp_tokens(
+            deps.as_mut(),
+            env.clone(),
+            chain_uid,
+            recipient.address.clone(),
+            "vlp".to_string(),
+            Uint128::new(100),
+            AcknowledgementMsg::Error("Timeout".to_string()),
+            "tx_1".to_string(),
+        )
+        .unwrap();
+        let failed_mints = |deps: &OwnedDeps<_, _, _>| -> FailedLpMintsResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::GetFailedLpMints {
+                        user: recipient.clone(),
+                        pagination: Pagination::new(None, None, None, None),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let res = failed_mints(&deps);
+        assert_eq!(res.mints.len(), 1);
+        assert_eq!(res.mints[0].vlp_address, "vlp");
+        assert_eq!(res.mints[0].amount, Uint128::new(100));
+
+        let retry_msg = ExecuteMsg::RetryLpMint {
+            recipient: recipient.clone(),
+            tx_id: "tx_1".to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            retry_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[0].msg else {
+            panic!("Expected mint message");
+        };
+        let euclid::msgs::factory::ExecuteMsg::NativeReceiveCallback { msg } =
+            from_json(msg).unwrap()
+        else {
+            panic!("Expected native callback");
+        };
+        let HubIbcExecuteMsg::MintLpTokens {
+            recipient: mint_recipient,
+            vlp_address,
+            amount,
+            tx_id,
+            ..
+        } = from_json(msg).unwrap()
+        else {
+            panic!("Expected lp token mint");
+        };
+        assert_eq!(mint_recipient, "lp_owner");
+        assert_eq!(vlp_address, "vlp");
+        assert_eq!(amount, Uint128::new(100));
+        assert_ne!(tx_id, "tx_1");
+        assert!(failed_mints(&deps).mints.is_empty());
+
+        let err = execute(deps.as_mut(), env, mock_info("anyone", &[]), retry_msg).unwrap_err();
+        assert_eq!(err, ContractError::new("Failed lp mint doesn't exist"));
+    }
+
+    #[test]
+    fn test_request_escrow_retirement() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("factory", &[]);
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let chain_uid = ChainUid::create("chaina".to_string()).unwrap();
+        let token = Token::create("eucl".to_string()).unwrap();
+        let sender = CrossChainUser {
+            chain_uid: chain_uid.clone(),
+            address: "admin".to_string(),
+        };
+        let retire_msg = ChainIbcExecuteMsg::RequestEscrowRetirement {
+            sender: sender.clone(),
+            tx_id: "tx_1".to_string(),
+            token: token.clone(),
+        };
+
+        // Escrow must exist on the chain
+        let err = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            retire_msg.clone(),
+            chain_uid.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EscrowDoesNotExist {});
+
+        // Escrow with balance is only marked as retiring
+        ESCROW_BALANCES
+            .save(
+                deps.as_mut().storage,
+                (token.clone(), chain_uid.clone()),
+                &Uint128::new(100),
+            )
+            .unwrap();
+        let res = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            retire_msg.clone(),
+            chain_uid.clone(),
+        )
+        .unwrap();
+        let ack: AcknowledgementMsg<EscrowRetirementResponse> =
+            from_json(res.data.unwrap()).unwrap();
+        assert_eq!(
+            ack,
+            AcknowledgementMsg::Ok(EscrowRetirementResponse {
+                token: token.clone(),
+                remaining_balance: Uint128::new(100),
+                retired: false,
+            })
+        );
+        assert!(RETIRING_ESCROWS
+            .load(deps.as_ref().storage, (token.clone(), chain_uid.clone()))
+            .unwrap());
+
+        // New deposits into the retiring escrow are rejected
+        let swap_msg = ChainIbcExecuteMsg::Swap(ChainIbcSwapExecuteMsg {
+            sender: sender.clone(),
+            asset_in: token.clone(),
+            amount_in: Uint128::new(10),
+            asset_out: Token::create("usdc".to_string()).unwrap(),
+            min_amount_out: Uint128::one(),
+            swaps: vec![NextSwapPair {
+                token_in: token.clone(),
+                token_out: Token::create("usdc".to_string()).unwrap(),
+                test_fail: None,
+            }],
+            cross_chain_addresses: vec![],
+            tx_id: "tx_2".to_string(),
+        });
+        let err = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            swap_msg,
+            chain_uid.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EscrowRetiring {});
+
+        // The last release is still in flight, it could be refunded to the escrow
+        ESCROW_BALANCES
+            .save(
+                deps.as_mut().storage,
+                (token.clone(), chain_uid.clone()),
+                &Uint128::zero(),
+            )
+            .unwrap();
+        ESCROW_RELEASES_IN_FLIGHT
+            .save(
+                deps.as_mut().storage,
+                (token.clone(), chain_uid.clone()),
+                &1,
+            )
+            .unwrap();
+        let res = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info,
+            retire_msg,
+            chain_uid.clone(),
+        )
+        .unwrap();
+        let ack: AcknowledgementMsg<EscrowRetirementResponse> =
+            from_json(res.data.unwrap()).unwrap();
+        assert_eq!(
+            ack,
+            AcknowledgementMsg::Ok(EscrowRetirementResponse {
+                token: token.clone(),
+                remaining_balance: Uint128::zero(),
+                retired: false,
+            })
+        );
+
+        // Once the last release is acknowledged the escrow is removed without another request
+        CHAIN_UID_TO_CHAIN
+            .save(
+                deps.as_mut().storage,
+                chain_uid.clone(),
+                &Chain {
+                    factory_chain_id: "chaina".to_string(),
+                    factory: "factory".to_string(),
+                    chain_type: euclid::chain::ChainType::Ibc(IbcChain {
+                        from_hub_channel: "5".to_string(),
+                        from_factory_channel: "6".to_string(),
+                    }),
+                },
+            )
+            .unwrap();
+        let res = ibc_ack_release_escrow(
+            deps.as_mut(),
+            env,
+            chain_uid.clone(),
+            sender,
+            Uint128::new(100),
+            token.clone(),
+            "admin".to_string(),
+            AcknowledgementMsg::Ok(ReleaseEscrowResponse {
+                factory_address: "factory".to_string(),
+                chain_id: "chaina".to_string(),
+                amount: Uint128::new(100),
+                token: token.clone(),
+                to_address: "admin".to_string(),
+            }),
+            "tx_3".to_string(),
+        )
+        .unwrap();
+        let CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) = &res.messages[0].msg else {
+            panic!("Expected escrow retirement packet");
+        };
+        let HubIbcExecuteMsg::RetireEscrow {
+            token: retired_token,
+            ..
+        } = from_json(data).unwrap()
+        else {
+            panic!("Expected escrow retirement");
+        };
+        assert_eq!(retired_token, token);
+        assert!(!ESCROW_BALANCES.has(deps.as_ref().storage, (token.clone(), chain_uid.clone())));
+        assert!(!RETIRING_ESCROWS.has(deps.as_ref().storage, (token, chain_uid)));
+    }
+
+    #[test]
+    fn test_vlps_by_token() {
+        let mut deps = mock_dependencies();
+        let owner = mock_info("owner", &[]);
+        init(deps.as_mut(), owner);
+
+        for (token_1, token_2) in [("a", "usdc"), ("b", "usdc"), ("usdc", "z"), ("a", "b")] {
+            let pair = Pair::new(
+                Token::create(token_1.to_string()).unwrap(),
+                Token::create(token_2.to_string()).unwrap(),
+            )
+            .unwrap();
+            save_vlp(
+                deps.as_mut().storage,
+                &pair,
+                &format!("vlp_{token_1}_{token_2}"),
+            );
+        }
+
+        let query_vlps = |deps: &OwnedDeps<_, _, MockQuerier>, token: &str, min, skip, limit| {
+            let res: AllVlpResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::GetVlpsByToken {
+                        token: Token::create(token.to_string()).unwrap(),
+                        pagination: Pagination {
+                            min,
+                            max: None,
+                            skip,
+                            limit,
+                        },
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.vlps.into_iter().map(|vlp| vlp.vlp).collect::<Vec<_>>()
+        };
+
+        // Usdc is token_2 of the first two pools and token_1 of the last one
+        assert_eq!(
+            query_vlps(&deps, "usdc", None, None, None),
+            vec!["vlp_a_usdc", "vlp_b_usdc", "vlp_usdc_z"]
+        );
+        assert_eq!(
+            query_vlps(&deps, "usdc", None, Some(1), Some(1)),
+            vec!["vlp_b_usdc"]
+        );
+        assert_eq!(
+            query_vlps(&deps, "a", None, None, None),
+            vec!["vlp_a_b", "vlp_a_usdc"]
+        );
+        assert!(query_vlps(&deps, "eth", None, None, None).is_empty());
+
+        // Vlps stored as a plain address before the index existed are picked up on migration
+        let legacy: Map<(Token, Token), String> = Map::new("vlps");
+        let eth = Token::create("eth".to_string()).unwrap();
+        legacy
+            .save(
+                deps.as_mut().storage,
+                (eth.clone(), Token::create("usdc".to_string()).unwrap()),
+                &"vlp_eth_usdc".to_string(),
+            )
+            .unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            query_vlps(&deps, "usdc", None, None, None),
+            vec!["vlp_a_usdc", "vlp_b_usdc", "vlp_eth_usdc", "vlp_usdc_z"]
+        );
+        let res: VlpResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVlp {
+                    pair: Pair::new(eth, Token::create("usdc".to_string()).unwrap()).unwrap(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.vlp, "vlp_eth_usdc");
+        assert_eq!(res.metadata, None);
+
+        // Pages follow the storage order of the pair keys, where a shorter first token sorts first
+        let ab_usdc = Pair::new(
+            Token::create("ab".to_string()).unwrap(),
+            Token::create("usdc".to_string()).unwrap(),
+        )
+        .unwrap();
+        save_vlp(deps.as_mut().storage, &ab_usdc, "vlp_ab_usdc");
+        assert_eq!(
+            query_vlps(&deps, "usdc", None, None, None),
+            vec![
+                "vlp_a_usdc",
+                "vlp_b_usdc",
+                "vlp_ab_usdc",
+                "vlp_eth_usdc",
+                "vlp_usdc_z"
+            ]
+        );
+        let min = (
+            Token::create("b".to_string()).unwrap(),
+            Token::create("usdc".to_string()).unwrap(),
+        );
+        assert_eq!(
+            query_vlps(&deps, "usdc", Some(min), None, Some(2)),
+            vec!["vlp_b_usdc", "vlp_ab_usdc"]
+        );
+
+        // Entries that are neither an address nor vlp info fail the migration
+        let corrupt: Map<(Token, Token), u64> = Map::new("vlps");
+        corrupt
+            .save(
+                deps.as_mut().storage,
+                (
+                    Token::create("c".to_string()).unwrap(),
+                    Token::create("usdc".to_string()).unwrap(),
+                ),
+                &1,
+            )
+            .unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::ParseErr { .. })));
+    }
+
+    #[test]
+    fn test_sunset_vlp_routing() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let token_a = Token::create("a".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        let pair = Pair::new(token_a.clone(), usdc.clone()).unwrap();
+        save_vlp(deps.as_mut().storage, &pair, "vlp_a_usdc");
+        SUNSET_VLPS
+            .save(deps.as_mut().storage, "vlp_a_usdc".to_string(), &pair)
+            .unwrap();
+
+        let swaps = vec![NextSwapPair {
+            token_in: token_a,
+            token_out: usdc,
+            test_fail: None,
+        }];
+        let err = validate_swap_pairs(deps.as_ref(), &swaps).unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+
+        // A multi asset pool holding the pair takes over the route
+        MULTI_ASSET_VLPS
+            .save(
+                deps.as_mut().storage,
+                pair.get_tupple(),
+                &"multi_vlp".to_string(),
+            )
+            .unwrap();
+        let vlps = validate_swap_pairs(deps.as_ref(), &swaps).unwrap();
+        assert_eq!(vlps[0].vlp_address, "multi_vlp");
+    }
+
+    // Vlp messages from before multi asset pools, cw_serde denies unknown fields
+    mod old_vlp {
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::Uint128;
+        use euclid::chain::CrossChainUser;
+        use euclid::token::Token;
+
+        #[cw_serde]
+        pub struct NextSwapVlp {
+            pub vlp_address: String,
+            pub test_fail: Option<bool>,
+        }
+
+        #[cw_serde]
+        pub enum ExecuteMsg {
+            Swap {
+                sender: CrossChainUser,
+                tx_id: String,
+                asset_in: Token,
+                amount_in: Uint128,
+                min_token_out: Uint128,
+                next_swaps: Vec<NextSwapVlp>,
+                test_fail: Option<bool>,
+            },
+        }
+
+        #[cw_serde]
+        pub enum QueryMsg {
+            SimulateSwap {
+                asset: Token,
+                asset_amount: Uint128,
+                swaps: Vec<NextSwapVlp>,
+            },
+        }
+    }
+
+    #[test]
+    fn test_swap_msgs_accepted_by_old_vlps() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut(), mock_info("owner", &[]));
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.virtual_balance_address = Some(Addr::unchecked("virtual_balance"));
+                Ok(state)
+            })
+            .unwrap();
+
+        let eth = Token::create("eth".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        let atom = Token::create("atom".to_string()).unwrap();
+        save_vlp(
+            deps.as_mut().storage,
+            &Pair::new(eth.clone(), usdc.clone()).unwrap(),
+            "vlp_eth_usdc",
+        );
+        save_vlp(
+            deps.as_mut().storage,
+            &Pair::new(usdc.clone(), atom.clone()).unwrap(),
+            "vlp_usdc_atom",
+        );
+        let swaps = vec![
+            NextSwapPair {
+                token_in: eth.clone(),
+                token_out: usdc.clone(),
+                test_fail: None,
+            },
+            NextSwapPair {
+                token_in: usdc,
+                token_out: atom.clone(),
+                test_fail: None,
+            },
+        ];
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("trader", &[]),
+            ExecuteMsg::VslSwap {
+                asset_in: eth.clone(),
+                amount_in: Uint128::new(100),
+                asset_out: atom.clone(),
+                min_amount_out: Uint128::zero(),
+                swaps: swaps.clone(),
+            },
+        )
+        .unwrap();
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[1].msg else {
+            panic!("Expected the vlp swap");
+        };
+        let old_vlp::ExecuteMsg::Swap { next_swaps, .. } = from_json(msg).unwrap();
+        assert_eq!(next_swaps[0].vlp_address, "vlp_usdc_atom");
+
+        let swap_response = GetSwapResponse {
+            amount_out: Uint128::new(90),
+            asset_out: atom.clone(),
+            lp_fee_bps: 0,
+            euclid_fee_bps: 0,
+            hops: vec![],
+            execution_price: Decimal::zero(),
+            price_impact_bps: 0,
+        };
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { msg, .. } => match from_json::<old_vlp::QueryMsg>(msg) {
+                Ok(_) => {
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&swap_response).unwrap()))
+                }
+                Err(err) => SystemResult::Ok(ContractResult::Err(err.to_string())),
+            },
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+        let res: euclid::msgs::router::SimulateSwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateSwap(euclid::msgs::router::QuerySimulateSwap {
+                    asset_in: eth,
+                    amount_in: Uint128::new(100),
+                    asset_out: atom,
+                    min_amount_out: Uint128::zero(),
+                    swaps,
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.amount_out, Uint128::new(90));
+    }
+
+    #[test]
+    fn test_liquidity_depth_skips_failed_pools() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let eth = Token::create("eth".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        save_vlp(
+            deps.as_mut().storage,
+            &Pair::new(eth.clone(), usdc.clone()).unwrap(),
+            "vlp_eth_usdc",
+        );
+        MULTI_ASSET_POOLS
+            .save(
+                deps.as_mut().storage,
+                "multi_vlp".to_string(),
+                &vec![
+                    Token::create("atom".to_string()).unwrap(),
+                    eth.clone(),
+                    usdc.clone(),
+                ],
+            )
+            .unwrap();
+
+        // The multi asset pool fails its query, the pair vlp still counts
+        let depth = euclid::msgs::vlp::LiquidityDepthResponse {
+            vlp_address: "vlp_eth_usdc".to_string(),
+            asset_in: eth.clone(),
+            asset_out: usdc.clone(),
+            amount_in: Uint128::new(100),
+            amount_out: Uint128::new(200),
+            spot_price: Decimal::from_ratio(2u128, 1u128),
+            target_price: Decimal::from_ratio(198u128, 100u128),
+        };
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "vlp_eth_usdc" => {
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&depth).unwrap()))
+            }
+            WasmQuery::Smart { .. } => {
+                SystemResult::Ok(ContractResult::Err("Unknown query".to_string()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let res: euclid::msgs::router::LiquidityDepthResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetLiquidityDepth {
+                    asset_in: eth,
+                    asset_out: usdc,
+                    price_move_bps: 100,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.pools.len(), 1);
+        assert_eq!(res.total_amount_in, Uint128::new(100));
+        assert_eq!(res.total_amount_out, Uint128::new(200));
+        assert_eq!(res.failed_pools.len(), 1);
+        assert_eq!(res.failed_pools[0].vlp_address, "multi_vlp");
+    }
+
+    #[test]
+    fn test_volume_skips_failed_vlps() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        for (token, vlp) in [("eth", "vlp_eth_usdc"), ("atom", "old_vlp")] {
+            let pair = Pair::new(Token::create(token.to_string()).unwrap(), usdc.clone()).unwrap();
+            save_vlp(deps.as_mut().storage, &pair, vlp);
+        }
+
+        // The old vlp doesn't know the volume query
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "vlp_eth_usdc" => {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&euclid::msgs::vlp::VolumeResponse {
+                        chain_uid: None,
+                        token_1_volume: Uint128::new(10),
+                        token_2_volume: Uint128::new(20),
+                    })
+                    .unwrap(),
+                ))
+            }
+            WasmQuery::Smart { .. } => {
+                SystemResult::Ok(ContractResult::Err("Unknown query".to_string()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let res: euclid::msgs::router::AllVlpVolumeResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVolume {
+                    chain_uid: None,
+                    pagination: Pagination {
+                        min: None,
+                        max: None,
+                        skip: None,
+                        limit: None,
+                    },
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.vlps.len(), 1);
+        assert_eq!(res.vlps[0].vlp, "vlp_eth_usdc");
+        assert_eq!(res.totals.len(), 2);
+        assert_eq!(res.failed_vlps.len(), 1);
+        assert_eq!(res.failed_vlps[0].vlp_address, "old_vlp");
+    }
+
+    #[test]
+    fn test_migrate_vlps() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        init(deps.as_mut(), owner.clone());
+
+        // vlp_a is already on the new code id, vlp_c still has the router admin as its wasm admin
+        let set_contract_info = |deps: &mut OwnedDeps<_, _, MockQuerier>, handed_over: bool| {
+            let router = mock_env().contract.address.to_string();
+            deps.querier.update_wasm(move |query| match query {
+                WasmQuery::ContractInfo { contract_addr } => {
+                    let mut contract_info = ContractInfoResponse::default();
+                    contract_info.code_id = if contract_addr == "vlp_a" { 2 } else { 1 };
+                    contract_info.admin = if contract_addr == "vlp_c" && !handed_over {
+                        Some("owner".to_string())
+                    } else {
+                        Some(router.clone())
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&contract_info).unwrap()))
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            });
+        };
+        set_contract_info(&mut deps, false);
+
+        for (token, vlp) in [("a", "vlp_a"), ("b", "vlp_b"), ("c", "vlp_c")] {
+            let pair = Pair {
+                token_1: Token::create(token.to_string()).unwrap(),
+                token_2: Token::create("usdc".to_string()).unwrap(),
+            };
+            save_vlp(deps.as_mut().storage, &pair, vlp);
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            owner.clone(),
+            ExecuteMsg::UpdateVLPCodeId { new_vlp_code_id: 2 },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("not_owner", &[]),
+            ExecuteMsg::MigrateVlps { limit: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let migrate_vlps = |deps: &mut OwnedDeps<_, _, MockQuerier>, limit| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::MigrateVlps { limit },
+            )
+        };
+        let migrate_reply = |deps: &mut OwnedDeps<_, _, MockQuerier>, result| {
+            reply(
+                deps.as_mut(),
+                mock_env(),
+                Reply {
+                    id: VLP_MIGRATE_REPLY_ID,
+                    result,
+                },
+            )
+            .unwrap();
+        };
+        let migration_status = |deps: &OwnedDeps<_, _, MockQuerier>| -> VlpMigrationStatusResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::GetVlpMigrationStatus {
+                        pagination: Pagination::new(None, None, None, None),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let migrated = |vlp: &str| VlpMigrationResult {
+            vlp: vlp.to_string(),
+            code_id: 2,
+            success: true,
+            error: None,
+        };
+
+        let res = migrate_vlps(&mut deps, Some(2)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: "vlp_b".to_string(),
+                new_code_id: 2,
+                msg: to_json_binary(&euclid::msgs::vlp::MigrateMsg {}).unwrap(),
+            })
+        );
+        assert_eq!(res.messages.len(), 1);
+        assert!(!migration_status(&deps).finished);
+        migrate_reply(&mut deps, SubMsgResult::Err("migrate failed".to_string()));
+
+        // vlp_c is not queued while the router isn't its admin, the pass ends with the last vlp
+        let res = migrate_vlps(&mut deps, Some(1)).unwrap();
+        assert!(res.messages.is_empty());
+        let status = migration_status(&deps);
+        assert!(status.finished);
+        assert_eq!(status.code_id, 2);
+        assert_eq!(
+            status.results,
+            vec![
+                migrated("vlp_a"),
+                VlpMigrationResult {
+                    vlp: "vlp_b".to_string(),
+                    code_id: 2,
+                    success: false,
+                    error: Some("migrate failed".to_string()),
+                },
+                VlpMigrationResult {
+                    vlp: "vlp_c".to_string(),
+                    code_id: 2,
+                    success: false,
+                    error: Some("Router is not the wasm admin of the vlp".to_string()),
+                },
+            ]
+        );
+
+        // Once vlp_c is handed over, a new pass retries the failed vlps
+        set_contract_info(&mut deps, true);
+        let res = migrate_vlps(&mut deps, None).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        for _ in 0..2 {
+            migrate_reply(
+                &mut deps,
+                SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            );
+        }
+        let status = migration_status(&deps);
+        assert!(status.finished);
+        assert_eq!(
+            status.results,
+            vec![migrated("vlp_a"), migrated("vlp_b"), migrated("vlp_c")]
+        );
+
+        let err = migrate_vlps(&mut deps, None).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("All vlps are already migrated to the current code id")
+        );
+
+        // A new code id starts without the results of the previous one
+        execute(
+            deps.as_mut(),
+            env,
+            owner,
+            ExecuteMsg::UpdateVLPCodeId { new_vlp_code_id: 3 },
+        )
+        .unwrap();
+        let status = migration_status(&deps);
+        assert_eq!(status.code_id, 3);
+        assert!(!status.finished);
+        assert!(status.results.is_empty());
+    }
+
+    #[test]
+    fn test_vsl_remove_liquidity() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = mock_info("owner", &[]);
+        init(deps.as_mut(), owner);
+
+        let pair = Pair {
+            token_1: Token::create("eth".to_string()).unwrap(),
+            token_2: Token::create("usdc".to_string()).unwrap(),
+        };
+        save_vlp(deps.as_mut().storage, &pair, "vlp");
+        VSL_LP_SHARES
+            .save(
+                deps.as_mut().storage,
+                (Addr::unchecked("user"), "vlp".to_string()),
+                &Uint128::new(100),
+            )
+            .unwrap();
+        // The vlp accrued 0.1 claimable token_1 fees per lp token
+        let fee_growth = FeeGrowthResponse {
+            token_1_fee_growth: Decimal256::percent(10),
+            token_2_fee_growth: Decimal256::zero(),
+        };
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { .. } => {
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&fee_growth).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::VslRemoveLiquidity {
+                pair: pair.clone(),
+                lp_allocation: Uint128::new(101),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::VslRemoveLiquidity {
+                pair: pair.clone(),
+                lp_allocation: Uint128::new(40),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, VSL_REMOVE_LIQUIDITY_REPLY_ID);
+
+        let shares: VslLpSharesResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetVslLpShares {
+                    user: "user".to_string(),
+                    pagination: Pagination::new(None, None, None, None),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            shares.shares,
+            vec![VslLpShareResponse {
+                vlp: "vlp".to_string(),
+                lp_shares: Uint128::new(60),
+            }]
+        );
+
+        // Fees earned before the withdrawal were settled on all 100 shares
+        let fees: UnclaimedVslLpFeesResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetUnclaimedVslLpFees {
+                    user: "user".to_string(),
+                    pair: pair.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(fees.token_1_fees, Uint128::new(10));
+        assert!(fees.token_2_fees.is_zero());
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::ClaimVslLpFees { pair: pair.clone() },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "vlp".to_string(),
+                msg: to_json_binary(&euclid::msgs::vlp::ExecuteMsg::ClaimLpFees {
+                    chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                    recipient: CrossChainUser {
+                        chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                        address: "user".to_string(),
+                    },
+                    amounts: Some((Uint128::new(10), Uint128::zero())),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info("user", &[]),
+            ExecuteMsg::ClaimVslLpFees { pair },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("No lp fees to claim"));
+    }
+
+    #[test]
+    fn test_liquidity_intent() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info("factory", &[]);
+        init(deps.as_mut(), mock_info("owner", &[]));
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.virtual_balance_address = Some(Addr::unchecked("virtual_balance"));
+                Ok(state)
+            })
+            .unwrap();
+
+        let chain_a = ChainUid::create("chaina".to_string()).unwrap();
+        let chain_b = ChainUid::create("chainb".to_string()).unwrap();
+        for chain_uid in [chain_a.clone(), chain_b.clone()] {
+            CHAIN_UID_TO_CHAIN
+                .save(
+                    deps.as_mut().storage,
+                    chain_uid,
+                    &Chain {
+                        factory_chain_id: "1".to_string(),
+                        factory: "factory".to_string(),
+                        chain_type: euclid::chain::ChainType::Native {},
+                    },
+                )
+                .unwrap();
+        }
+
+        let eth = Token::create("eth".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        let pair = Pair {
+            token_1: eth.clone(),
+            token_2: usdc.clone(),
+        };
+        save_vlp(deps.as_mut().storage, &pair, "vlp");
+        let liquidity = euclid::msgs::vlp::GetLiquidityResponse {
+            pair: pair.clone(),
+            token_1_reserve: Uint128::zero(),
+            token_2_reserve: Uint128::zero(),
+            total_lp_tokens: Uint128::zero(),
+            locked_lp_tokens: Uint128::zero(),
+        };
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { .. } => {
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&liquidity).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let recipient = CrossChainUser {
+            chain_uid: chain_b.clone(),
+            address: "lp_owner".to_string(),
+        };
+        let user_a = CrossChainUser {
+            chain_uid: chain_a.clone(),
+            address: "user".to_string(),
+        };
+        let user_b = CrossChainUser {
+            chain_uid: chain_b.clone(),
+            address: "user".to_string(),
+        };
+        let leg = |sender: &CrossChainUser,
+                   counterparty: &CrossChainUser,
+                   token: &Token,
+                   amount: u128,
+                   intent_id: &str| {
+            ChainIbcExecuteMsg::AddLiquidityLeg(ChainIbcAddLiquidityLegExecuteMsg {
+                sender: sender.clone(),
+                intent_id: intent_id.to_string(),
+                pair: pair.clone(),
+                counterparty: counterparty.clone(),
+                token: token.clone(),
+                amount: Uint128::new(amount),
+                slippage_tolerance: 5,
+                recipient: recipient.clone(),
+                deadline: env.block.time.seconds() + 100,
+                tx_id: format!("{intent_id}_{token}"),
+            })
+        };
+
+        // First leg is held by the router
+        let res = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            leg(&user_a, &user_b, &eth, 100, "intent_1"),
+            chain_a.clone(),
+        )
+        .unwrap();
+        let ack: AcknowledgementMsg<AddLiquidityLegResponse> =
+            from_json(res.data.unwrap()).unwrap();
+        assert_eq!(
+            ack,
+            AcknowledgementMsg::Ok(AddLiquidityLegResponse {
+                intent_id: "intent_1".to_string(),
+                completed: false,
+            })
+        );
+        assert_eq!(
+            ESCROW_BALANCES
+                .load(deps.as_ref().storage, (eth.clone(), chain_a.clone()))
+                .unwrap(),
+            Uint128::new(100)
+        );
+
+        // Only the counterparty can deposit the other leg
+        let stranger = CrossChainUser {
+            chain_uid: chain_b.clone(),
+            address: "stranger".to_string(),
+        };
+        let err = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            leg(&stranger, &user_a, &usdc, 200, "intent_1"),
+            chain_b.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Liquidity leg is not from the intent counterparty")
+        );
+
+        // A leg with the same token can't complete the intent
+        let err = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            leg(&user_b, &user_a, &eth, 100, "intent_1"),
+            chain_b.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Liquidity leg doesn't match the intent")
+        );
+
+        // Second leg adds liquidity for the recipient
+        let res = reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            leg(&user_b, &user_a, &usdc, 200, "intent_1"),
+            chain_b.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(res.messages[2].id, ADD_LIQUIDITY_LEG_REPLY_ID);
+        assert!(!LIQUIDITY_INTENTS.has(
+            deps.as_ref().storage,
+            (user_a.to_sender_string(), "intent_1".to_string())
+        ));
+
+        // Held leg is refunded only after the deadline
+        reusable_internal_call(
+            &mut deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            leg(&user_a, &user_b, &eth, 50, "intent_2"),
+            chain_a.clone(),
+        )
+        .unwrap();
+        let refund_msg = ExecuteMsg::RefundLiquidityIntent {
+            sender: user_a.clone(),
+            intent_id: "intent_2".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            refund_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Liquidity intent deadline has not passed")
+        );
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), refund_msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert!(!LIQUIDITY_INTENTS.has(
+            deps.as_ref().storage,
+            (user_a.to_sender_string(), "intent_2".to_string())
+        ));
+    }
+
+    #[test]
+    fn test_add_liquidity_from_virtual_balance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.virtual_balance_address = Some(Addr::unchecked("virtual_balance"));
+                Ok(state)
+            })
+            .unwrap();
+
+        let pair = Pair {
+            token_1: Token::create("eth".to_string()).unwrap(),
+            token_2: Token::create("usdc".to_string()).unwrap(),
+        };
+        save_vlp(deps.as_mut().storage, &pair, "vlp");
+        let liquidity = euclid::msgs::vlp::GetLiquidityResponse {
+            pair: pair.clone(),
+            token_1_reserve: Uint128::zero(),
+            token_2_reserve: Uint128::zero(),
+            total_lp_tokens: Uint128::zero(),
+            locked_lp_tokens: Uint128::zero(),
+        };
+        // The recipient chain already has a pool on the vlp
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                euclid::msgs::vlp::QueryMsg::Pool { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&euclid::msgs::vlp::PoolResponse {
+                        lp_shares: Uint128::zero(),
+                        reserve_1: Uint128::zero(),
+                        reserve_2: Uint128::zero(),
+                    })
+                    .unwrap(),
+                )),
+                _ => SystemResult::Ok(ContractResult::Ok(to_json_binary(&liquidity).unwrap())),
+            },
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let chain_uid = ChainUid::create("chaina".to_string()).unwrap();
+        let recipient = CrossChainUser {
+            chain_uid: chain_uid.clone(),
+            address: "lp_owner".to_string(),
+        };
+        let msg = ExecuteMsg::AddLiquidityFromVirtualBalance {
+            pair: pair.clone(),
+            token_1_liquidity: Uint128::new(100),
+            token_2_liquidity: Uint128::new(200),
+            slippage_tolerance: 5,
+            recipient: recipient.clone(),
+        };
+
+        // Lp tokens can only be minted on a registered chain
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("Recipient chain is not registered"));
+
+        CHAIN_UID_TO_CHAIN
+            .save(
+                deps.as_mut().storage,
+                chain_uid.clone(),
+                &Chain {
+                    factory_chain_id: "1".to_string(),
+                    factory: "factory".to_string(),
+                    chain_type: euclid::chain::ChainType::Native {},
+                },
+            )
+            .unwrap();
+
+        // Sunset vlps and retiring escrows on the recipient chain take no new liquidity
+        SUNSET_VLPS
+            .save(deps.as_mut().storage, "vlp".to_string(), &pair)
+            .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+        SUNSET_VLPS.remove(deps.as_mut().storage, "vlp".to_string());
+
+        let escrow_key = (pair.token_2.clone(), chain_uid.clone());
+        RETIRING_ESCROWS
+            .save(deps.as_mut().storage, escrow_key.clone(), &true)
+            .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EscrowRetiring {});
+        RETIRING_ESCROWS.remove(deps.as_mut().storage, escrow_key);
+
+        let res = execute(deps.as_mut(), env, mock_info("user", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(res.messages[2].id, VIRTUAL_BALANCE_ADD_LIQUIDITY_REPLY_ID);
+        assert_eq!(
+            PENDING_VIRTUAL_BALANCE_ADD_LIQUIDITY
+                .load(deps.as_ref().storage, "vlp".to_string())
+                .unwrap(),
+            recipient
+        );
+    }
+
+    // Wraps data the way wasmd encodes MsgExecuteContractResponse
+    fn execute_reply_data(data: Binary) -> Binary {
+        let mut encoded = vec![0x0a];
+        let mut len = data.len();
+        while len >= 0x80 {
+            encoded.push((len as u8 & 0x7f) | 0x80);
+            len >>= 7;
+        }
+        encoded.push(len as u8);
+        encoded.extend_from_slice(data.as_slice());
+        Binary::from(encoded)
+    }
+
+    #[test]
+    fn test_remove_liquidity_keep_in_virtual_balance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let sender = CrossChainUser {
+            chain_uid: ChainUid::create("chaina".to_string()).unwrap(),
+            address: "user".to_string(),
+        };
+        let pair = Pair {
+            token_1: Token::create("eth".to_string()).unwrap(),
+            token_2: Token::create("usdc".to_string()).unwrap(),
+        };
+        let reply_msg = |keep_in_virtual_balance: bool| {
+            let remove_liquidity = ChainIbcRemoveLiquidityExecuteMsg {
+                sender: sender.clone(),
+                lp_allocation: Uint128::new(10),
+                pair: pair.clone(),
+                cross_chain_addresses: vec![],
+                keep_in_virtual_balance,
+                tx_id: "tx_1".to_string(),
+            };
+            let response = VlpRemoveLiquidityResponse {
+                token_1_liquidity_released: Uint128::new(5),
+                token_2_liquidity_released: Uint128::new(20),
+                burn_lp_tokens: Uint128::new(10),
+                tx_id: "tx_1".to_string(),
+                sender: sender.clone(),
+                vlp_address: "vlp".to_string(),
+            };
+            let reply = Reply {
+                id: REMOVE_LIQUIDITY_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(execute_reply_data(to_json_binary(&response).unwrap())),
+                }),
+            };
+            (remove_liquidity, reply)
+        };
+
+        // Default behaviour releases both tokens from escrow
+        let (remove_liquidity, reply_data) = reply_msg(false);
+        PENDING_REMOVE_LIQUIDITY
+            .save(
+                deps.as_mut().storage,
+                (
+                    sender.chain_uid.clone(),
+                    sender.address.clone(),
+                    "tx_1".to_string(),
+                ),
+                &remove_liquidity,
+            )
+            .unwrap();
+        let res = reply(deps.as_mut(), env.clone(), reply_data).unwrap();
+        assert_eq!(res.messages.len(), 2);
+
+        // Tokens stay as the sender's virtual balance
+        let (remove_liquidity, reply_data) = reply_msg(true);
+        PENDING_REMOVE_LIQUIDITY
+            .save(
+                deps.as_mut().storage,
+                (
+                    sender.chain_uid.clone(),
+                    sender.address.clone(),
+                    "tx_1".to_string(),
+                ),
+                &remove_liquidity,
+            )
+            .unwrap();
+        let res = reply(deps.as_mut(), env, reply_data).unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn test_pool_creation_mode() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let chain_uid = ChainUid::create("chaina".to_string()).unwrap();
+        let pair = Pair {
+            token_1: Token::create("eth".to_string()).unwrap(),
+            token_2: Token::create("usdc".to_string()).unwrap(),
+        };
+        // Existing vlp so that allowed requests only register the pool
+        save_vlp(deps.as_mut().storage, &pair, "vlp");
+        let update_msg = |mode: PoolCreationMode| ExecuteMsg::UpdatePoolCreationMode {
+            chain_uid: chain_uid.clone(),
+            mode,
+        };
+        let request = |mut deps: DepsMut, creator: &str| {
+            reusable_internal_call(
+                &mut deps,
+                mock_env(),
+                mock_info("factory", &[]),
+                ChainIbcExecuteMsg::RequestPoolCreation {
+                    pair: pair.clone(),
+                    sender: CrossChainUser {
+                        chain_uid: chain_uid.clone(),
+                        address: creator.to_string(),
+                    },
+                    tx_id: "tx_1".to_string(),
+                    initial_liquidity: None,
+                },
+                chain_uid.clone(),
+            )
+        };
+
+        let allowlist = PoolCreationMode::Allowlist {
+            creators: vec!["alice".to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            update_msg(allowlist.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            update_msg(allowlist.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("Chain is not registered"));
+
+        CHAIN_UID_TO_CHAIN
+            .save(
+                deps.as_mut().storage,
+                chain_uid.clone(),
+                &Chain {
+                    factory_chain_id: "1".to_string(),
+                    factory: "factory".to_string(),
+                    chain_type: euclid::chain::ChainType::Native {},
+                },
+            )
+            .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            update_msg(allowlist.clone()),
+        )
+        .unwrap();
+        let res: PoolCreationModeResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetPoolCreationMode {
+                    chain_uid: chain_uid.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.mode, allowlist);
+
+        let not_allowed = |creator: &str| ContractError::PoolCreationNotAllowed {
+            creator: creator.to_string(),
+            chain_uid: chain_uid.to_string(),
+        };
+        assert_eq!(
+            request(deps.as_mut(), "bob").unwrap_err(),
+            not_allowed("bob")
+        );
+        request(deps.as_mut(), "alice").unwrap();
+
+        // Multi asset pools follow the same mode
+        let err = reusable_internal_call(
+            &mut deps.as_mut(),
+            mock_env(),
+            mock_info("factory", &[]),
+            ChainIbcExecuteMsg::RequestMultiAssetPoolCreation {
+                sender: CrossChainUser {
+                    chain_uid: chain_uid.clone(),
+                    address: "bob".to_string(),
+                },
+                tx_id: "tx_2".to_string(),
+                tokens: vec![
+                    Token::create("atom".to_string()).unwrap(),
+                    Token::create("eth".to_string()).unwrap(),
+                    Token::create("usdc".to_string()).unwrap(),
+                ],
+            },
+            chain_uid.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, not_allowed("bob"));
+
+        // Admin only mode accepts the chain's admin address alone, not the hub admin
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            update_msg(PoolCreationMode::AdminOnly {
+                admin: "chain_admin".to_string(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            request(deps.as_mut(), "alice").unwrap_err(),
+            not_allowed("alice")
+        );
+        assert_eq!(
+            request(deps.as_mut(), "owner").unwrap_err(),
+            not_allowed("owner")
+        );
+        request(deps.as_mut(), "chain_admin").unwrap();
+
+        // Open mode clears the chain's entry
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            update_msg(PoolCreationMode::Open),
+        )
+        .unwrap();
+        request(deps.as_mut(), "bob").unwrap();
+    }
+
+    #[test]
+    fn test_pool_creation_add_liquidity_reply() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+
+        let response = AddLiquidityResponse {
+            mint_lp_tokens: Uint128::new(1000),
+            vlp_address: "vlp".to_string(),
+        };
+        let res = reply(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: POOL_CREATION_ADD_LIQUIDITY_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(execute_reply_data(to_json_binary(&response).unwrap())),
+                }),
+            },
+        )
+        .unwrap();
+
+        // Pool creation ack carries the lp tokens minted for the initial liquidity
+        let ack: AcknowledgementMsg<PoolCreationResponse> = from_json(res.data.unwrap()).unwrap();
+        assert_eq!(
+            ack,
+            AcknowledgementMsg::Ok(PoolCreationResponse {
+                vlp_contract: "vlp".to_string(),
+                mint_lp_tokens: Some(Uint128::new(1000)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_limit_orders() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut(), mock_info("owner", &[]));
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.virtual_balance_address = Some(Addr::unchecked("virtual_balance"));
+                Ok(state)
+            })
+            .unwrap();
+
+        let eth = Token::create("eth".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        let pair = Pair::new(eth.clone(), usdc.clone()).unwrap();
+        save_vlp(deps.as_mut().storage, &pair, "vlp");
+
+        let set_simulated_out = |deps: &mut OwnedDeps<_, _, MockQuerier>, amount_out: u128| {
+            let usdc = usdc.clone();
+            deps.querier.update_wasm(move |query| match query {
+                WasmQuery::Smart { .. } => {
+                    let res = GetSwapResponse {
+                        amount_out: Uint128::new(amount_out),
+                        asset_out: usdc.clone(),
+                        lp_fee_bps: 0,
+                        euclid_fee_bps: 0,
+                        hops: vec![],
+                        execution_price: Decimal::zero(),
+                        price_impact_bps: 0,
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            });
+        };
+
+        let place_msg = ExecuteMsg::PlaceLimitOrder {
+            asset_in: eth.clone(),
+            amount_in: Uint128::new(100),
+            asset_out: usdc.clone(),
+            limit_price: Decimal::from_ratio(2u128, 1u128),
+            swaps: vec![NextSwapPair {
+                token_in: eth.clone(),
+                token_out: usdc.clone(),
+                test_fail: None,
+            }],
+        };
+        for _ in 0..2 {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("user", &[]),
+                place_msg.clone(),
+            )
+            .unwrap();
+            // Order amount is moved to the router's virtual balance
+            assert_eq!(res.messages.len(), 1);
+        }
+
+        let orders: LimitOrdersResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::GetUserLimitOrders {
+                    user: "user".to_string(),
+                    pagination: Pagination::new(None, None, None, None),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            orders
+                .orders
+                .iter()
+                .map(|order| order.order_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        // Pool price is below the limit, nothing to fill
+        set_simulated_out(&mut deps, 199);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteOrders { order_ids: vec![1] },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("None of the limit orders can be executed")
+        );
+
+        set_simulated_out(&mut deps, 200);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteOrders {
+                order_ids: vec![1, 3],
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, LIMIT_ORDER_EXECUTE_REPLY_ID);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteLimitOrderInternal { order_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(env.contract.address.as_str(), &[]),
+            ExecuteMsg::ExecuteLimitOrderInternal { order_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert!(!LIMIT_ORDERS.has(&deps.storage, 1));
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other", &[]),
+            ExecuteMsg::CancelLimitOrder { order_id: 2 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::CancelLimitOrder { order_id: 2 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert!(LIMIT_ORDERS.is_empty(&deps.storage));
+        assert!(USER_LIMIT_ORDERS.is_empty(&deps.storage));
+    }
 }
@@ -13,12 +13,14 @@ use euclid::error::ContractError;
 use euclid::msgs::vlp::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 use crate::query::{
-    query_all_pools, query_fee, query_liquidity, query_pool, query_simulate_swap, query_state,
-    query_total_fees_collected, query_total_fees_per_denom,
+    query_all_pools, query_daily_volume, query_fee, query_fee_growth, query_liquidity,
+    query_liquidity_depth, query_pool, query_simulate_swap, query_spot_price, query_state,
+    query_total_fees_collected, query_total_fees_per_denom, query_unclaimed_lp_fees,
+    query_undistributed_fees, query_volume,
 };
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:vlp";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:vlp";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -46,6 +48,9 @@ pub fn instantiate(
         last_updated: 0,
         total_lp_tokens: Uint128::zero(),
         admin: msg.admin,
+        dynamic_fee: None,
+        claimable_lp_fees: false,
+        fee_recipients: None,
     };
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -89,6 +94,19 @@ pub fn execute(
             euclid_fee_bps,
             recipient,
         } => execute::update_fee(deps, info, lp_fee_bps, euclid_fee_bps, recipient),
+        ExecuteMsg::UpdateDynamicFee { config } => execute::update_dynamic_fee(deps, info, config),
+        ExecuteMsg::UpdateFeeRecipients { recipients } => {
+            execute::update_fee_recipients(deps, info, recipients)
+        }
+        ExecuteMsg::DistributeFees {} => execute::distribute_fees(deps, env),
+        ExecuteMsg::UpdateLpFeeMode { claimable } => {
+            execute::update_lp_fee_mode(deps, info, claimable)
+        }
+        ExecuteMsg::ClaimLpFees {
+            chain_uid,
+            recipient,
+            amounts,
+        } => execute::claim_lp_fees(deps, env, info, chain_uid, recipient, amounts),
         ExecuteMsg::AddLiquidity {
             sender,
             token_1_liquidity,
@@ -110,6 +128,13 @@ pub fn execute(
             lp_allocation,
             tx_id,
         } => execute::remove_liquidity(deps, env, info, sender, lp_allocation, tx_id),
+        ExecuteMsg::Sunset {} => execute::sunset(deps, info),
+        ExecuteMsg::FlashSwap {
+            asset_out,
+            amount_out,
+            data,
+        } => execute::flash_swap(deps, env, info, asset_out, amount_out, data),
+        ExecuteMsg::SettleFlashSwap {} => execute::settle_flash_swap(deps, env, info),
         ExecuteMsg::Swap {
             sender,
             asset_in,
@@ -118,6 +143,7 @@ pub fn execute(
             tx_id,
             next_swaps,
             test_fail,
+            asset_out,
         } => execute::execute_swap(
             deps,
             env,
@@ -128,6 +154,7 @@ pub fn execute(
             tx_id,
             next_swaps,
             test_fail,
+            asset_out,
         ),
     }
 }
@@ -140,14 +167,26 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             asset,
             asset_amount,
             swaps,
-        } => query_simulate_swap(deps, asset, asset_amount, swaps),
+            asset_out,
+        } => query_simulate_swap(deps, env, asset, asset_amount, swaps, asset_out),
+        QueryMsg::SpotPrice { base } => query_spot_price(deps, base),
+        QueryMsg::LiquidityDepth {
+            asset_in,
+            target,
+            asset_out,
+        } => query_liquidity_depth(deps, env, asset_in, target, asset_out),
         QueryMsg::Liquidity {} => query_liquidity(deps, env),
-        QueryMsg::Fee {} => query_fee(deps),
+        QueryMsg::Fee {} => query_fee(deps, env),
         QueryMsg::TotalFeesCollected {} => query_total_fees_collected(deps),
         QueryMsg::TotalFeesPerDenom { denom } => query_total_fees_per_denom(deps, denom),
         QueryMsg::Pool { chain_uid } => query_pool(deps, chain_uid),
 
         QueryMsg::GetAllPools {} => query_all_pools(deps),
+        QueryMsg::UndistributedFees {} => query_undistributed_fees(deps),
+        QueryMsg::FeeGrowth {} => query_fee_growth(deps),
+        QueryMsg::UnclaimedLpFees { chain_uid } => query_unclaimed_lp_fees(deps, chain_uid),
+        QueryMsg::Volume { chain_uid } => query_volume(deps, chain_uid),
+        QueryMsg::DailyVolume { pagination } => query_daily_volume(deps, pagination),
     }
 }
 
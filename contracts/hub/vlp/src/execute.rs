@@ -1,27 +1,36 @@
 use cosmwasm_std::{
-    ensure, to_json_binary, Decimal, Decimal256, DepsMut, Env, MessageInfo, Response, SubMsg,
-    Uint128, WasmMsg,
+    ensure, to_json_binary, Binary, Decimal, Decimal256, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
+use cw_storage_plus::Bound;
 use euclid::{
     chain::{ChainUid, CrossChainUser},
     error::ContractError,
     events::{liquidity_event, simple_event, tx_event, TxType},
-    fee::MAX_FEE_BPS,
+    fee::{split_fee, validate_fee_recipients, DynamicFeeConfig, FeeRecipient, MAX_FEE_BPS},
     liquidity::AddLiquidityResponse,
     msgs::{
         virtual_balance::ExecuteTransfer,
-        vlp::{VlpRemoveLiquidityResponse, VlpSwapResponse},
+        vlp::{ExecuteMsg, FlashSwapCallbackMsg, VlpRemoveLiquidityResponse, VlpSwapResponse},
     },
-    pool::{Pool, PoolCreationResponse},
+    pool::{Pool, PoolCreationResponse, MINIMUM_LIQUIDITY},
     swap::NextSwapVlp,
     token::{Pair, Token},
+    utils::generate_tx,
     virtual_balance::BalanceKey,
 };
 
 use crate::{
-    query::{assert_slippage_tolerance, calculate_lp_allocation, calculate_swap},
+    query::{
+        assert_slippage_tolerance, calculate_lp_allocation, calculate_swap,
+        get_effective_lp_fee_bps, get_exact_unclaimed_lp_fees, get_pool_price,
+    },
     reply::{NEXT_SWAP_REPLY_ID, VIRTUAL_BALANCE_TRANSFER_REPLY_ID},
-    state::{self, BALANCES, CHAIN_LP_TOKENS, STATE},
+    state::{
+        self, FlashSwap, State, BALANCES, CHAIN_FEE_GROWTH, CHAIN_LP_TOKENS, CHAIN_UNCLAIMED_FEES,
+        CHAIN_VOLUME, DAILY_VOLUME, EUCLID_FEES_HELD, FEE_GROWTH, FLASH_SWAP, LP_FEES_HELD,
+        PRICE_OBSERVATIONS, SECONDS_PER_DAY, STATE, SUNSET, VOLUME,
+    },
 };
 
 /// Registers a new pool in the contract. Function called by Router Contract
@@ -50,6 +59,11 @@ pub fn register_pool(
     let state = STATE.load(deps.storage)?;
 
     ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(!is_sunset(&deps)?, ContractError::PoolSunset {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
 
     // Verify that chain pool does not already exist
     ensure!(
@@ -68,6 +82,7 @@ pub fn register_pool(
 
     let ack = PoolCreationResponse {
         vlp_contract: env.contract.address.to_string(),
+        mint_lp_tokens: None,
     };
 
     Ok(Response::new()
@@ -98,7 +113,7 @@ pub fn register_pool(
 ///
 /// Returns a response with the action and chain id attributes if successful.
 pub fn add_liquidity(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     sender: CrossChainUser,
@@ -109,8 +124,14 @@ pub fn add_liquidity(
 ) -> Result<Response, ContractError> {
     let mut state = STATE.load(deps.storage)?;
     ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(!is_sunset(&deps)?, ContractError::PoolSunset {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
 
     let mut chain_lp_tokens = CHAIN_LP_TOKENS.load(deps.storage, sender.chain_uid.clone())?;
+    settle_chain_lp_fees(&mut deps, &state, &sender.chain_uid)?;
 
     let pair = state.pair.clone();
 
@@ -153,6 +174,11 @@ pub fn add_liquidity(
         }
     );
 
+    // The first deposit locks MINIMUM_LIQUIDITY lp tokens so the pool can never be fully drained
+    if state.total_lp_tokens.is_zero() {
+        lock_minimum_liquidity(&mut deps, &mut state)?;
+    }
+
     chain_lp_tokens = chain_lp_tokens.checked_add(lp_allocation)?;
     CHAIN_LP_TOKENS.save(deps.storage, sender.chain_uid.clone(), &chain_lp_tokens)?;
 
@@ -216,7 +242,7 @@ pub fn add_liquidity(
 ///
 /// Returns a response with the action and chain id attributes if successful.
 pub fn remove_liquidity(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     sender: CrossChainUser,
@@ -226,15 +252,25 @@ pub fn remove_liquidity(
     // Get the pool for the chain_id provided
     let mut state = STATE.load(deps.storage)?;
     ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
     let pair = state.pair.clone();
 
     let mut total_reserve_1 = BALANCES.load(deps.storage, pair.token_1.clone())?;
     let mut total_reserve_2 = BALANCES.load(deps.storage, pair.token_2.clone())?;
 
     // Remove chain lp tokens from the sender, remove liquidity only works for a single chain remove liquidity
+    settle_chain_lp_fees(&mut deps, &state, &sender.chain_uid)?;
     let mut chain_lp_tokens = CHAIN_LP_TOKENS.load(deps.storage, sender.chain_uid.clone())?;
     chain_lp_tokens = chain_lp_tokens.checked_sub(lp_allocation)?;
-    CHAIN_LP_TOKENS.save(deps.storage, sender.chain_uid.clone(), &chain_lp_tokens)?;
+    // A sunset pool drops chains once all of their liquidity is withdrawn
+    if chain_lp_tokens.is_zero() && is_sunset(&deps)? {
+        CHAIN_LP_TOKENS.remove(deps.storage, sender.chain_uid.clone());
+    } else {
+        CHAIN_LP_TOKENS.save(deps.storage, sender.chain_uid.clone(), &chain_lp_tokens)?;
+    }
 
     // Fetch allocated liquidity to LP tokens
     let lp_tokens = state.total_lp_tokens;
@@ -318,7 +354,7 @@ pub fn remove_liquidity(
 }
 
 pub fn execute_swap(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     sender: CrossChainUser,
     asset_in: Token,
@@ -327,6 +363,7 @@ pub fn execute_swap(
     tx_id: String,
     next_swaps: Vec<NextSwapVlp>,
     test_fail: Option<bool>,
+    expected_asset_out: Option<Token>,
 ) -> Result<Response, ContractError> {
     ensure!(
         !test_fail.unwrap_or(false),
@@ -335,6 +372,12 @@ pub fn execute_swap(
     // Verify that the asset amount is non-zero
     ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
 
+    ensure!(!is_sunset(&deps)?, ContractError::PoolSunset {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
+
     let mut state = state::STATE.load(deps.storage)?;
 
     let pair = state.pair.clone();
@@ -343,8 +386,9 @@ pub fn execute_swap(
 
     // Get Fee from the state
     let fee = state.clone().fee;
+    let lp_fee_bps = record_price_observation(&mut deps, &env, &state)?;
 
-    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(fee.lp_fee_bps))?;
+    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(lp_fee_bps))?;
     let euclid_fee = amount_in.checked_mul_floor(Decimal::bps(fee.euclid_fee_bps))?;
 
     // Add the lp fee to total fees
@@ -360,6 +404,10 @@ pub fn execute_swap(
     let swap_amount = amount_in.checked_sub(total_fee)?;
 
     let asset_out = state.pair.get_other_token(asset_in.clone());
+    ensure!(
+        expected_asset_out.map_or(true, |expected| expected == asset_out),
+        ContractError::AssetDoesNotExist {}
+    );
 
     let mut token_in_reserve = BALANCES.load(deps.storage, asset_in.clone())?;
     let mut token_out_reserve = BALANCES.load(deps.storage, asset_out.clone())?;
@@ -379,8 +427,12 @@ pub fn execute_swap(
             },
         )?;
 
+    let fees_held = get_fees_held(&deps, &asset_in)?;
     ensure!(
-        vlp_virtual_balance_balance.amount == token_in_reserve.checked_add(amount_in)?,
+        vlp_virtual_balance_balance.amount
+            == token_in_reserve
+                .checked_add(fees_held)?
+                .checked_add(amount_in)?,
         ContractError::new("Swap didn't receive any funds!")
     );
 
@@ -395,9 +447,10 @@ pub fn execute_swap(
         }
     );
 
+    let reserve_lp_fee = accrue_lp_fee(&mut deps, &state, &asset_in, lp_fee)?;
     token_in_reserve = token_in_reserve
         .checked_add(swap_amount)?
-        .checked_add(lp_fee)?;
+        .checked_add(reserve_lp_fee)?;
     token_out_reserve = token_out_reserve.checked_sub(receive_amount)?;
 
     BALANCES.save(deps.storage, asset_in.clone(), &token_in_reserve)?;
@@ -430,39 +483,9 @@ pub fn execute_swap(
 
     let mut response = Response::new();
 
-    if !euclid_fee.is_zero() {
-        // Add the euclid fee to total fees
-        state
-            .total_fees_collected
-            .euclid_fees
-            .add_fee(asset_in.to_string(), euclid_fee);
-
-        let euclid_fee_transfer_msg =
-            euclid::msgs::virtual_balance::ExecuteMsg::Transfer(ExecuteTransfer {
-                amount: euclid_fee,
-                token_id: asset_in.to_string(),
-
-                // Source Address
-                from: CrossChainUser {
-                    address: env.contract.address.to_string(),
-                    chain_uid: ChainUid::vsl_chain_uid()?,
-                },
-
-                // Destination Address
-                to: fee.recipient,
-            });
-
-        let euclid_fee_transfer_msg = WasmMsg::Execute {
-            contract_addr: state.virtual_balance.clone(),
-            msg: to_json_binary(&euclid_fee_transfer_msg)?,
-            funds: vec![],
-        };
-
-        let euclid_fee_transfer_msg =
-            SubMsg::reply_on_error(euclid_fee_transfer_msg, VIRTUAL_BALANCE_TRANSFER_REPLY_ID);
-
-        response = response.add_submessage(euclid_fee_transfer_msg);
-    }
+    // Euclid fee stays in the vlp until DistributeFees is called
+    accrue_euclid_fee(&mut deps, &mut state, &asset_in, euclid_fee)?;
+    record_volume(&mut deps, &env, &sender.chain_uid, &asset_in, amount_in)?;
 
     match next_swaps.split_first() {
         Some((next_swap, forward_swaps)) => {
@@ -505,6 +528,7 @@ pub fn execute_swap(
                 tx_id: tx_id.clone(),
                 next_swaps: forward_swaps.to_vec(),
                 test_fail: next_swap.test_fail,
+                asset_out: next_swap.asset_out.clone(),
             };
             let next_swap_msg = WasmMsg::Execute {
                 contract_addr: next_swap.vlp_address.clone(),
@@ -579,6 +603,8 @@ pub fn execute_swap(
         .add_attribute("total_fee", total_fee)
         .add_attribute("euclid_fee", euclid_fee)
         .add_attribute("lp_fee", lp_fee)
+        .add_attribute("lp_fee_bps", lp_fee_bps.to_string())
+        .add_attribute("euclid_fee_bps", fee.euclid_fee_bps.to_string())
         .add_attribute("receive_amount", receive_amount)
         .set_data(acknowledgement))
 }
@@ -611,3 +637,584 @@ pub fn update_fee(
         .add_event(simple_event())
         .add_attribute("action", "update_fee"))
 }
+
+pub fn update_dynamic_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<DynamicFeeConfig>,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    if let Some(config) = &config {
+        ensure!(
+            config.window > 0,
+            ContractError::new("Dynamic fee window cannot be zero")
+        );
+        ensure!(
+            config.max_lp_fee_bps.le(&MAX_FEE_BPS),
+            ContractError::new("LP Fee cannot exceed maximum limit")
+        );
+    }
+    state.dynamic_fee = config;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_event(simple_event())
+        .add_attribute("action", "update_dynamic_fee")
+        .add_attribute("enabled", state.dynamic_fee.is_some().to_string()))
+}
+
+pub fn update_fee_recipients(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipients: Option<Vec<FeeRecipient>>,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    if let Some(recipients) = &recipients {
+        validate_fee_recipients(recipients)?;
+    }
+    state.fee_recipients = recipients;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_event(simple_event())
+        .add_attribute("action", "update_fee_recipients"))
+}
+
+pub fn distribute_fees(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
+    let state = STATE.load(deps.storage)?;
+
+    let mut fees = vec![];
+    for token in state.pair.get_vec_token() {
+        let amount = EUCLID_FEES_HELD
+            .may_load(deps.storage, token.clone())?
+            .unwrap_or_default();
+        if !amount.is_zero() {
+            fees.push((token, amount));
+        }
+    }
+    ensure!(
+        !fees.is_empty(),
+        ContractError::new("No fees to distribute")
+    );
+
+    // Vlp split first, then the router default and finally everything to the fee recipient
+    let recipients = match state.fee_recipients.clone() {
+        Some(recipients) => recipients,
+        None => {
+            let default_recipients: euclid::msgs::router::FeeRecipientsResponse =
+                deps.querier.query_wasm_smart(
+                    state.router.clone(),
+                    &euclid::msgs::router::QueryMsg::GetDefaultFeeRecipients {},
+                )?;
+            default_recipients.recipients.unwrap_or(vec![FeeRecipient {
+                recipient: state.fee.recipient.clone(),
+                weight_bps: 10_000,
+            }])
+        }
+    };
+
+    let vlp_cross_chain_struct = CrossChainUser {
+        address: env.contract.address.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let mut response = Response::new();
+    for (token, amount) in fees {
+        EUCLID_FEES_HELD.remove(deps.storage, token.clone());
+        response = response.add_attribute(format!("distributed_{token}"), amount);
+
+        for (recipient, share) in split_fee(amount, &recipients)? {
+            if share.is_zero() {
+                continue;
+            }
+            let transfer_msg = token.create_virtual_balance_transfer_msg(
+                state.virtual_balance.clone(),
+                share,
+                vlp_cross_chain_struct.clone(),
+                recipient,
+            )?;
+            response = response.add_submessage(SubMsg::reply_on_error(
+                transfer_msg,
+                VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+            ));
+        }
+    }
+
+    Ok(response
+        .add_event(simple_event())
+        .add_attribute("action", "distribute_fees"))
+}
+
+pub fn update_lp_fee_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    claimable: bool,
+) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    // Fees accrued so far stay claimable, only new fees follow the new mode
+    state.claimable_lp_fees = claimable;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_event(simple_event())
+        .add_attribute("action", "update_lp_fee_mode")
+        .add_attribute("claimable", claimable.to_string()))
+}
+
+pub fn claim_lp_fees(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    chain_uid: ChainUid,
+    recipient: CrossChainUser,
+    amounts: Option<(Uint128, Uint128)>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
+    ensure!(
+        chain_uid == ChainUid::vsl_chain_uid()?,
+        ContractError::new("Only hub lp tokens accrue claimable lp fees")
+    );
+
+    settle_chain_lp_fees(&mut deps, &state, &chain_uid)?;
+
+    let vlp_cross_chain_struct = CrossChainUser {
+        address: env.contract.address.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let mut response = Response::new();
+    for (index, token) in state.pair.get_vec_token().into_iter().enumerate() {
+        let key = (chain_uid.clone(), token.clone());
+        let unclaimed = CHAIN_UNCLAIMED_FEES
+            .may_load(deps.storage, key.clone())?
+            .unwrap_or_default();
+        let amount = match amounts {
+            Some((token_1_amount, _)) if index == 0 => token_1_amount,
+            Some((_, token_2_amount)) => token_2_amount,
+            None => Uint128::try_from(unclaimed.to_uint_floor()).map_err(StdError::from)?,
+        };
+        // The rounded down remainder stays unclaimed for the next claims
+        let remaining = unclaimed
+            .checked_sub(Decimal256::from_ratio(amount, 1u128))
+            .map_err(|_err| ContractError::InsufficientFunds {})?;
+
+        response = response.add_attribute(format!("claimed_{token}"), amount);
+        if amount.is_zero() {
+            continue;
+        }
+        CHAIN_UNCLAIMED_FEES.save(deps.storage, key, &remaining)?;
+        LP_FEES_HELD.update(deps.storage, token.clone(), |held| -> StdResult<_> {
+            Ok(held.unwrap_or_default().checked_sub(amount)?)
+        })?;
+
+        let transfer_msg = token.create_virtual_balance_transfer_msg(
+            state.virtual_balance.clone(),
+            amount,
+            vlp_cross_chain_struct.clone(),
+            recipient.clone(),
+        )?;
+        response = response.add_submessage(SubMsg::reply_on_error(
+            transfer_msg,
+            VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+        ));
+    }
+
+    Ok(response
+        .add_event(simple_event())
+        .add_attribute("action", "claim_lp_fees")
+        .add_attribute("chain_uid", chain_uid.to_string())
+        .add_attribute("recipient", recipient.to_sender_string()))
+}
+
+pub fn sunset(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.router, ContractError::Unauthorized {});
+    ensure!(!is_sunset(&deps)?, ContractError::PoolSunset {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
+
+    SUNSET.save(deps.storage, &true)?;
+
+    // Chains that never provided liquidity have nothing left to withdraw
+    let empty_chains = CHAIN_LP_TOKENS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((chain_uid, lp_tokens)) if lp_tokens.is_zero() => Some(Ok(chain_uid)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<ChainUid>>>()?;
+    for chain_uid in empty_chains {
+        CHAIN_LP_TOKENS.remove(deps.storage, chain_uid);
+    }
+
+    Ok(Response::new()
+        .add_event(simple_event())
+        .add_attribute("action", "sunset")
+        .add_attribute("total_lp_tokens", state.total_lp_tokens))
+}
+
+pub fn flash_swap(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_out: Token,
+    amount_out: Uint128,
+    data: Binary,
+) -> Result<Response, ContractError> {
+    ensure!(!amount_out.is_zero(), ContractError::ZeroAssetAmount {});
+    ensure!(!is_sunset(&deps)?, ContractError::PoolSunset {});
+    ensure!(
+        !is_flash_swapping(&deps)?,
+        ContractError::FlashSwapInProgress {}
+    );
+
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        asset_out.exists(state.pair.clone()),
+        ContractError::AssetDoesNotExist {}
+    );
+    // The pool can't be drained completely
+    let reserve_out = BALANCES.load(deps.storage, asset_out.clone())?;
+    ensure!(
+        amount_out.lt(&reserve_out),
+        ContractError::new("Insufficient liquidity for flash swap")
+    );
+
+    let caller = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: ChainUid::vsl_chain_uid()?,
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &caller)?;
+    FLASH_SWAP.save(
+        deps.storage,
+        &FlashSwap {
+            caller: caller.clone(),
+            asset_out: asset_out.clone(),
+            amount_out,
+            tx_id: tx_id.clone(),
+        },
+    )?;
+
+    let virtual_balance_transfer_msg =
+        euclid::msgs::virtual_balance::ExecuteMsg::Transfer(ExecuteTransfer {
+            amount: amount_out,
+            token_id: asset_out.to_string(),
+            from: CrossChainUser {
+                address: env.contract.address.to_string(),
+                chain_uid: ChainUid::vsl_chain_uid()?,
+            },
+            to: caller,
+        });
+    let virtual_balance_transfer_msg = WasmMsg::Execute {
+        contract_addr: state.virtual_balance,
+        msg: to_json_binary(&virtual_balance_transfer_msg)?,
+        funds: vec![],
+    };
+
+    let callback_msg = FlashSwapCallbackMsg::FlashSwapCallback {
+        vlp_address: env.contract.address.to_string(),
+        asset_out: asset_out.clone(),
+        amount_out,
+        data,
+    };
+    let callback_msg = WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: to_json_binary(&callback_msg)?,
+        funds: vec![],
+    };
+
+    // Messages run in order, so settlement sees everything the callback paid back
+    let settle_msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_json_binary(&ExecuteMsg::SettleFlashSwap {})?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_event(tx_event(&tx_id, info.sender.as_str(), TxType::Swap))
+        .add_attribute("action", "flash_swap")
+        .add_attribute("asset_out", asset_out.to_string())
+        .add_attribute("amount_out", amount_out)
+        .add_submessage(SubMsg::reply_on_error(
+            virtual_balance_transfer_msg,
+            VIRTUAL_BALANCE_TRANSFER_REPLY_ID,
+        ))
+        .add_message(callback_msg)
+        .add_message(settle_msg))
+}
+
+pub fn settle_flash_swap(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.sender == env.contract.address,
+        ContractError::Unauthorized {}
+    );
+    let flash_swap = FLASH_SWAP.load(deps.storage)?;
+    FLASH_SWAP.remove(deps.storage);
+
+    let mut state = STATE.load(deps.storage)?;
+    let fee = state.fee.clone();
+    // Reserves are still the ones from before the flash swap, so they are observed like a regular swap
+    let lp_fee_bps = record_price_observation(&mut deps, &env, &state)?;
+    let total_fee_bps = lp_fee_bps
+        .checked_add(fee.euclid_fee_bps)
+        .ok_or(ContractError::new("Fee overflow"))?;
+
+    let mut response = Response::new();
+    let mut reserves = vec![];
+    let mut balances_after_fee = vec![];
+    let mut new_reserves = vec![];
+    for token in state.pair.get_vec_token() {
+        let reserve = BALANCES.load(deps.storage, token.clone())?;
+        let balance: euclid::msgs::virtual_balance::GetBalanceResponse =
+            deps.querier.query_wasm_smart(
+                state.virtual_balance.clone(),
+                &euclid::msgs::virtual_balance::QueryMsg::GetBalance {
+                    balance_key: BalanceKey {
+                        cross_chain_user: CrossChainUser {
+                            address: env.contract.address.to_string(),
+                            chain_uid: ChainUid::vsl_chain_uid()?,
+                        },
+                        token_id: token.to_string(),
+                    },
+                },
+            )?;
+
+        // Held fees sit in the same virtual balance but are not part of the pool
+        let balance_amount = balance.amount.saturating_sub(get_fees_held(&deps, &token)?);
+
+        // Whatever exceeds the reserve left after the flash transfer was paid in
+        let amount_out = if token == flash_swap.asset_out {
+            flash_swap.amount_out
+        } else {
+            Uint128::zero()
+        };
+        let amount_in = balance_amount.saturating_sub(reserve.checked_sub(amount_out)?);
+
+        // Fees are rounded up for the invariant check so rounding always favours the pool
+        let total_fee = amount_in.checked_mul_ceil(Decimal::bps(total_fee_bps))?;
+        let lp_fee = amount_in.checked_mul_floor(Decimal::bps(lp_fee_bps))?;
+        let euclid_fee = amount_in.checked_mul_floor(Decimal::bps(fee.euclid_fee_bps))?;
+
+        reserves.push(Uint256::from(reserve));
+        balances_after_fee.push(Uint256::from(balance_amount.checked_sub(total_fee)?));
+        let reserve_lp_fee = accrue_lp_fee(&mut deps, &state, &token, lp_fee)?;
+        new_reserves.push((
+            token.clone(),
+            balance_amount
+                .checked_sub(euclid_fee)?
+                .checked_sub(lp_fee.checked_sub(reserve_lp_fee)?)?,
+        ));
+
+        response = response.add_attribute(format!("amount_in_{token}"), amount_in);
+        if amount_in.is_zero() {
+            continue;
+        }
+        state
+            .total_fees_collected
+            .lp_fees
+            .add_fee(token.to_string(), lp_fee);
+        accrue_euclid_fee(&mut deps, &mut state, &token, euclid_fee)?;
+        record_volume(
+            &mut deps,
+            &env,
+            &flash_swap.caller.chain_uid,
+            &token,
+            amount_in,
+        )?;
+    }
+
+    // Constant product with fees, same check as uniswap v2
+    ensure!(
+        balances_after_fee[0].checked_mul(balances_after_fee[1])?
+            >= reserves[0].checked_mul(reserves[1])?,
+        ContractError::FlashSwapInvariantViolated {}
+    );
+
+    for (token, reserve) in new_reserves {
+        BALANCES.save(deps.storage, token, &reserve)?;
+    }
+    STATE.save(deps.storage, &state)?;
+
+    let pool = Pool {
+        pair: state.pair.clone(),
+        reserve_1: BALANCES.load(deps.storage, state.pair.token_1.clone())?,
+        reserve_2: BALANCES.load(deps.storage, state.pair.token_2.clone())?,
+    };
+
+    Ok(response
+        .add_event(liquidity_event(&pool, &flash_swap.tx_id))
+        .add_attribute("action", "settle_flash_swap")
+        .add_attribute("caller", flash_swap.caller.address)
+        .add_attribute("tx_id", flash_swap.tx_id))
+}
+
+// Returns the lp fee for a swap in this block. With dynamic fees the price before the first swap of the
+// block is kept as an observation and observations that left the fee window are pruned
+fn record_price_observation(
+    deps: &mut DepsMut,
+    env: &Env,
+    state: &State,
+) -> Result<u64, ContractError> {
+    let Some(config) = &state.dynamic_fee else {
+        return Ok(state.fee.lp_fee_bps);
+    };
+    let now = env.block.time.seconds();
+    let expired = PRICE_OBSERVATIONS
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive(now.saturating_sub(config.window))),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<u64>>>()?;
+    for timestamp in expired {
+        PRICE_OBSERVATIONS.remove(deps.storage, timestamp);
+    }
+
+    let lp_fee_bps = get_effective_lp_fee_bps(deps.as_ref(), state, now)?;
+    if !PRICE_OBSERVATIONS.has(deps.storage, now) {
+        if let Some(price) = get_pool_price(deps.as_ref(), state)? {
+            PRICE_OBSERVATIONS.save(deps.storage, now, &price)?;
+        }
+    }
+    Ok(lp_fee_bps)
+}
+
+// Adds a swapped amount to the total, chain and daily volume of the token
+fn record_volume(
+    deps: &mut DepsMut,
+    env: &Env,
+    chain_uid: &ChainUid,
+    token: &Token,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let add = |volume: Option<Uint128>| -> StdResult<_> {
+        Ok(volume.unwrap_or_default().checked_add(amount)?)
+    };
+    VOLUME.update(deps.storage, token.clone(), add)?;
+    CHAIN_VOLUME.update(deps.storage, (chain_uid.clone(), token.clone()), add)?;
+    let day = env.block.time.seconds() / SECONDS_PER_DAY;
+    DAILY_VOLUME.update(deps.storage, (day, token.clone()), add)?;
+    Ok(())
+}
+
+fn accrue_euclid_fee(
+    deps: &mut DepsMut,
+    state: &mut State,
+    token: &Token,
+    euclid_fee: Uint128,
+) -> Result<(), ContractError> {
+    if euclid_fee.is_zero() {
+        return Ok(());
+    }
+    state
+        .total_fees_collected
+        .euclid_fees
+        .add_fee(token.to_string(), euclid_fee);
+    EUCLID_FEES_HELD.update(deps.storage, token.clone(), |held| -> StdResult<_> {
+        Ok(held.unwrap_or_default().checked_add(euclid_fee)?)
+    })?;
+    Ok(())
+}
+
+// Fees held in the vlp virtual balance on top of the reserves
+fn get_fees_held(deps: &DepsMut, token: &Token) -> Result<Uint128, ContractError> {
+    let lp_fees = LP_FEES_HELD
+        .may_load(deps.storage, token.clone())?
+        .unwrap_or_default();
+    let euclid_fees = EUCLID_FEES_HELD
+        .may_load(deps.storage, token.clone())?
+        .unwrap_or_default();
+    Ok(lp_fees.checked_add(euclid_fees)?)
+}
+
+// Credits the hub lp tokens' share of the lp fee through the fee growth when lp fees are claimable.
+// Lp tokens of other chains have no per holder accounting on the hub, so their share compounds into
+// the reserves like it does when fees aren't claimable. Returns the part of the fee that goes to the reserves
+fn accrue_lp_fee(
+    deps: &mut DepsMut,
+    state: &State,
+    token: &Token,
+    lp_fee: Uint128,
+) -> Result<Uint128, ContractError> {
+    let hub_lp_tokens = CHAIN_LP_TOKENS
+        .may_load(deps.storage, ChainUid::vsl_chain_uid()?)?
+        .unwrap_or_default();
+    if !state.claimable_lp_fees || hub_lp_tokens.is_zero() || lp_fee.is_zero() {
+        return Ok(lp_fee);
+    }
+    let claimable_fee = lp_fee.checked_multiply_ratio(hub_lp_tokens, state.total_lp_tokens)?;
+    let growth = Decimal256::checked_from_ratio(claimable_fee, hub_lp_tokens)
+        .map_err(|_err| ContractError::new("Fee growth overflow"))?;
+    FEE_GROWTH.update(deps.storage, token.clone(), |fee_growth| -> StdResult<_> {
+        Ok(fee_growth.unwrap_or_default().checked_add(growth)?)
+    })?;
+    LP_FEES_HELD.update(deps.storage, token.clone(), |held| -> StdResult<_> {
+        Ok(held.unwrap_or_default().checked_add(claimable_fee)?)
+    })?;
+    Ok(lp_fee.checked_sub(claimable_fee)?)
+}
+
+// Credits MINIMUM_LIQUIDITY lp tokens to the locked liquidity holder, they are backed by the
+// reserves but no chain can withdraw them
+pub fn lock_minimum_liquidity(deps: &mut DepsMut, state: &mut State) -> Result<(), ContractError> {
+    let locked_chain_uid = ChainUid::locked_liquidity_chain_uid()?;
+    let locked = CHAIN_LP_TOKENS
+        .may_load(deps.storage, locked_chain_uid.clone())?
+        .unwrap_or_default()
+        .checked_add(Uint128::new(MINIMUM_LIQUIDITY))?;
+    CHAIN_LP_TOKENS.save(deps.storage, locked_chain_uid, &locked)?;
+    state.total_lp_tokens = state
+        .total_lp_tokens
+        .checked_add(Uint128::new(MINIMUM_LIQUIDITY))?;
+    Ok(())
+}
+
+// Moves what a chain's lp tokens earned since the last settlement into its unclaimed fees,
+// has to run before the chain's lp tokens change
+fn settle_chain_lp_fees(
+    deps: &mut DepsMut,
+    state: &State,
+    chain_uid: &ChainUid,
+) -> Result<(), ContractError> {
+    for (token, unclaimed) in get_exact_unclaimed_lp_fees(deps.as_ref(), state, chain_uid)? {
+        let growth = FEE_GROWTH
+            .may_load(deps.storage, token.clone())?
+            .unwrap_or_default();
+        if growth.is_zero() {
+            continue;
+        }
+        let key = (chain_uid.clone(), token);
+        CHAIN_FEE_GROWTH.save(deps.storage, key.clone(), &growth)?;
+        CHAIN_UNCLAIMED_FEES.save(deps.storage, key, &unclaimed)?;
+    }
+    Ok(())
+}
+
+fn is_sunset(deps: &DepsMut) -> Result<bool, ContractError> {
+    Ok(SUNSET.may_load(deps.storage)?.unwrap_or_default())
+}
+
+fn is_flash_swapping(deps: &DepsMut) -> Result<bool, ContractError> {
+    Ok(FLASH_SWAP.may_load(deps.storage)?.is_some())
+}
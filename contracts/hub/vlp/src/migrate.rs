@@ -1,9 +1,36 @@
 use cosmwasm_std::{entry_point, DepsMut, Env, Response};
-use euclid::{error::ContractError, msgs::vlp::MigrateMsg};
+use euclid::{
+    chain::ChainUid, error::ContractError, migrate::ensure_migration_version, msgs::vlp::MigrateMsg,
+};
+
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    execute::lock_minimum_liquidity,
+    state::{CHAIN_LP_TOKENS, STATE},
+};
 
 /// This is the migrate entry point for the contract.
-/// Currently, it does not perform any migration logic and simply returns an empty response.
+/// It refuses migrations from a different contract or a newer version.
+///
+/// Pools funded before the minimum liquidity was credited to the locked liquidity holder get it now,
+/// those lp tokens were never minted but their share of the reserves was left in the pool.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_migration_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &[])?;
+
+    let mut state = STATE.load(deps.storage)?;
+    let locked_chain_uid = ChainUid::locked_liquidity_chain_uid()?;
+    let lock_liquidity =
+        !state.total_lp_tokens.is_zero() && !CHAIN_LP_TOKENS.has(deps.storage, locked_chain_uid);
+    if lock_liquidity {
+        lock_minimum_liquidity(&mut deps, &mut state)?;
+        STATE.save(deps.storage, &state)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION)
+        .add_attribute("locked_minimum_liquidity", lock_liquidity.to_string()))
 }
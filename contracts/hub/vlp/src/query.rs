@@ -1,25 +1,38 @@
 use cosmwasm_std::{
-    ensure, to_json_binary, Binary, Decimal, Decimal256, Deps, Env, Isqrt, Uint128,
+    ensure, to_json_binary, Binary, Decimal, Decimal256, Deps, Env, Isqrt, Order, StdError, Uint128,
 };
+use cw_storage_plus::{Bound, PrefixBound};
 use euclid::chain::ChainUid;
 use euclid::error::ContractError;
+use euclid::fee::compound_fee_bps;
 use euclid::pool::MINIMUM_LIQUIDITY;
-use euclid::swap::NextSwapVlp;
+use euclid::swap::{
+    get_amount_in_before_fees, get_compounded_fee, get_route_price_impact_bps, get_spot_price,
+    get_swap_amount_to_price, DepthTarget, NextSwapVlp, SwapHop,
+};
 use euclid::token::Token;
+use euclid::utils::Pagination;
 
 use euclid::msgs::vlp::{
-    AllPoolsResponse, FeeResponse, GetLiquidityResponse, GetStateResponse, GetSwapResponse,
-    PoolInfo, PoolResponse, TotalFeesPerDenomResponse, TotalFeesResponse,
+    AllPoolsResponse, DailyVolumeResponse, DayVolume, FeeGrowthResponse, FeeResponse,
+    GetLiquidityResponse, GetStateResponse, GetSwapResponse, LiquidityDepthResponse, PoolInfo,
+    PoolResponse, SpotPriceResponse, TotalFeesPerDenomResponse, TotalFeesResponse,
+    UnclaimedLpFeesResponse, UndistributedFeesResponse, VolumeResponse,
 };
 
-use crate::state::{State, BALANCES, CHAIN_LP_TOKENS, STATE};
+use crate::state::{
+    State, BALANCES, CHAIN_FEE_GROWTH, CHAIN_LP_TOKENS, CHAIN_UNCLAIMED_FEES, CHAIN_VOLUME,
+    DAILY_VOLUME, EUCLID_FEES_HELD, FEE_GROWTH, PRICE_OBSERVATIONS, STATE, SUNSET, VOLUME,
+};
 
 // Function to simulate swap in a query
 pub fn query_simulate_swap(
     deps: Deps,
+    env: Env,
     asset_in: Token,
     amount_in: Uint128,
     next_swaps: Vec<NextSwapVlp>,
+    expected_asset_out: Option<Token>,
 ) -> Result<Binary, ContractError> {
     // Verify that the asset amount is non-zero
     ensure!(!amount_in.is_zero(), ContractError::ZeroAssetAmount {});
@@ -33,8 +46,9 @@ pub fn query_simulate_swap(
 
     // Get Fee from the state
     let fee = state.clone().fee;
+    let lp_fee_bps = get_effective_lp_fee_bps(deps, &state, env.block.time.seconds())?;
 
-    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(fee.lp_fee_bps))?;
+    let lp_fee = amount_in.checked_mul_floor(Decimal::bps(lp_fee_bps))?;
     let euclid_fee = amount_in.checked_mul_floor(Decimal::bps(fee.euclid_fee_bps))?;
 
     // Calcuate the sum of fees
@@ -44,11 +58,28 @@ pub fn query_simulate_swap(
     let swap_amount = amount_in.checked_sub(total_fee)?;
 
     let asset_out = state.pair.get_other_token(asset_in.clone());
+    ensure!(
+        expected_asset_out.map_or(true, |expected| expected == asset_out),
+        ContractError::AssetDoesNotExist {}
+    );
 
-    let token_in_reserve = BALANCES.load(deps.storage, asset_in)?;
+    let token_in_reserve = BALANCES.load(deps.storage, asset_in.clone())?;
     let token_out_reserve = BALANCES.load(deps.storage, asset_out.clone())?;
 
     let receive_amount = calculate_swap(swap_amount, token_in_reserve, token_out_reserve)?;
+    let mut hop = SwapHop {
+        vlp_address: env.contract.address.to_string(),
+        asset_in,
+        amount_in,
+        asset_out: asset_out.clone(),
+        amount_out: receive_amount,
+        lp_fee,
+        euclid_fee,
+        spot_price: get_spot_price(token_in_reserve, token_out_reserve)?,
+        price_impact_bps: 0,
+    };
+    hop.price_impact_bps = get_route_price_impact_bps(&[hop.clone()])?;
+
     let response = match next_swaps.split_first() {
         Some((next_swap, forward_swaps)) => {
             let next_swap_response: GetSwapResponse = deps.querier.query_wasm_smart(
@@ -57,16 +88,115 @@ pub fn query_simulate_swap(
                     asset: asset_out,
                     asset_amount: receive_amount,
                     swaps: forward_swaps.to_vec(),
+                    asset_out: next_swap.asset_out.clone(),
                 },
             )?;
-            Ok(to_json_binary(&next_swap_response)?)
+            let mut hops = vec![hop];
+            hops.extend(next_swap_response.hops);
+            let (route_lp_fee_bps, route_euclid_fee_bps) = compound_fee_bps(
+                (lp_fee_bps, fee.euclid_fee_bps),
+                (
+                    next_swap_response.lp_fee_bps,
+                    next_swap_response.euclid_fee_bps,
+                ),
+            );
+            GetSwapResponse::new(
+                amount_in,
+                next_swap_response.amount_out,
+                next_swap_response.asset_out,
+                hops,
+                route_lp_fee_bps,
+                route_euclid_fee_bps,
+            )?
         }
-        None => Ok(to_json_binary(&GetSwapResponse {
-            amount_out: receive_amount,
+        None => GetSwapResponse::new(
+            amount_in,
+            receive_amount,
             asset_out,
-        })?),
+            vec![hop],
+            lp_fee_bps,
+            fee.euclid_fee_bps,
+        )?,
     };
-    response
+    Ok(to_json_binary(&response)?)
+}
+
+// Amount of asset_in that moves the price of asset_in to the target, along the same curve as calculate_swap.
+// The lp fee that isn't claimable by hub lp tokens stays in reserve_in and moves the price as well
+pub fn query_liquidity_depth(
+    deps: Deps,
+    env: Env,
+    asset_in: Token,
+    target: DepthTarget,
+    expected_asset_out: Option<Token>,
+) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        asset_in.exists(state.pair.clone()),
+        ContractError::AssetDoesNotExist {}
+    );
+    let asset_out = state.pair.get_other_token(asset_in.clone());
+    ensure!(
+        expected_asset_out.map_or(true, |expected| expected == asset_out),
+        ContractError::AssetDoesNotExist {}
+    );
+
+    let mut response = LiquidityDepthResponse {
+        vlp_address: env.contract.address.to_string(),
+        asset_in: asset_in.clone(),
+        asset_out: asset_out.clone(),
+        amount_in: Uint128::zero(),
+        amount_out: Uint128::zero(),
+        spot_price: Decimal::zero(),
+        target_price: Decimal::zero(),
+    };
+    let reserve_in = BALANCES.load(deps.storage, asset_in)?;
+    let reserve_out = BALANCES.load(deps.storage, asset_out)?;
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Ok(to_json_binary(&response)?);
+    }
+
+    response.spot_price = get_spot_price(reserve_in, reserve_out)?;
+    response.target_price = target.get_target_price(response.spot_price)?;
+    let lp_fee_bps = get_effective_lp_fee_bps(deps, &state, env.block.time.seconds())?;
+    let total_fee_bps = lp_fee_bps + state.fee.euclid_fee_bps;
+    let hub_lp_tokens = CHAIN_LP_TOKENS
+        .may_load(deps.storage, ChainUid::vsl_chain_uid()?)?
+        .unwrap_or_default();
+    let compounded_share = if state.claimable_lp_fees && !hub_lp_tokens.is_zero() {
+        Decimal::one().checked_sub(Decimal::from_ratio(hub_lp_tokens, state.total_lp_tokens))?
+    } else {
+        Decimal::one()
+    };
+    let compounded_fee = get_compounded_fee(lp_fee_bps, total_fee_bps, compounded_share)?;
+    let swap_amount = get_swap_amount_to_price(
+        reserve_in,
+        reserve_out,
+        response.target_price,
+        compounded_fee,
+    )?;
+    if !swap_amount.is_zero() {
+        response.amount_in = get_amount_in_before_fees(swap_amount, total_fee_bps)?;
+        response.amount_out = calculate_swap(swap_amount, reserve_in, reserve_out)?;
+    }
+    Ok(to_json_binary(&response)?)
+}
+
+// Function to query the price of base in the other token of the pair
+pub fn query_spot_price(deps: Deps, base: Token) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        base.exists(state.pair.clone()),
+        ContractError::AssetDoesNotExist {}
+    );
+    let quote = state.pair.get_other_token(base.clone());
+    let base_reserve = BALANCES.load(deps.storage, base.clone())?;
+    let quote_reserve = BALANCES.load(deps.storage, quote.clone())?;
+    Ok(to_json_binary(&SpotPriceResponse {
+        base,
+        quote,
+        price: get_spot_price(base_reserve, quote_reserve)?,
+    })?)
 }
 
 // Function to query the total liquidity
@@ -82,13 +212,34 @@ pub fn query_liquidity(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
             .may_load(deps.storage, state.pair.token_2)?
             .unwrap_or_default(),
         total_lp_tokens: state.total_lp_tokens,
+        locked_lp_tokens: CHAIN_LP_TOKENS
+            .may_load(deps.storage, ChainUid::locked_liquidity_chain_uid()?)?
+            .unwrap_or_default(),
     })?)
 }
 
 // Function to query fee of the contract
-pub fn query_fee(deps: Deps) -> Result<Binary, ContractError> {
+pub fn query_fee(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let effective_lp_fee_bps = get_effective_lp_fee_bps(deps, &state, env.block.time.seconds())?;
+    Ok(to_json_binary(&FeeResponse {
+        fee: state.fee,
+        dynamic_fee: state.dynamic_fee,
+        effective_lp_fee_bps,
+        fee_recipients: state.fee_recipients,
+    })?)
+}
+
+pub fn query_undistributed_fees(deps: Deps) -> Result<Binary, ContractError> {
     let state = STATE.load(deps.storage)?;
-    Ok(to_json_binary(&FeeResponse { fee: state.fee })?)
+    Ok(to_json_binary(&UndistributedFeesResponse {
+        token_1_fees: EUCLID_FEES_HELD
+            .may_load(deps.storage, state.pair.token_1)?
+            .unwrap_or_default(),
+        token_2_fees: EUCLID_FEES_HELD
+            .may_load(deps.storage, state.pair.token_2)?
+            .unwrap_or_default(),
+    })?)
 }
 
 // Function to query total fees collected of the contract
@@ -122,6 +273,93 @@ pub fn query_state(deps: Deps) -> Result<Binary, ContractError> {
         last_updated: state.last_updated,
         total_lp_tokens: state.total_lp_tokens,
         admin: state.admin,
+        sunset: SUNSET.may_load(deps.storage)?.unwrap_or_default(),
+        dynamic_fee: state.dynamic_fee,
+        claimable_lp_fees: state.claimable_lp_fees,
+    })?)
+}
+
+pub fn query_fee_growth(deps: Deps) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    Ok(to_json_binary(&FeeGrowthResponse {
+        token_1_fee_growth: FEE_GROWTH
+            .may_load(deps.storage, state.pair.token_1)?
+            .unwrap_or_default(),
+        token_2_fee_growth: FEE_GROWTH
+            .may_load(deps.storage, state.pair.token_2)?
+            .unwrap_or_default(),
+    })?)
+}
+
+pub fn query_unclaimed_lp_fees(deps: Deps, chain_uid: ChainUid) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let fees = get_unclaimed_lp_fees(deps, &state, &chain_uid)?;
+    Ok(to_json_binary(&UnclaimedLpFeesResponse {
+        chain_uid,
+        token_1_fees: fees[0].1,
+        token_2_fees: fees[1].1,
+    })?)
+}
+
+pub fn query_volume(deps: Deps, chain_uid: Option<ChainUid>) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let volume = |token: Token| -> Result<Uint128, ContractError> {
+        let volume = match &chain_uid {
+            Some(chain_uid) => CHAIN_VOLUME.may_load(deps.storage, (chain_uid.clone(), token))?,
+            None => VOLUME.may_load(deps.storage, token)?,
+        };
+        Ok(volume.unwrap_or_default())
+    };
+    Ok(to_json_binary(&VolumeResponse {
+        token_1_volume: volume(state.pair.token_1)?,
+        token_2_volume: volume(state.pair.token_2)?,
+        chain_uid,
+    })?)
+}
+
+pub fn query_daily_volume(
+    deps: Deps,
+    pagination: Pagination<u64>,
+) -> Result<Binary, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let Pagination {
+        min: start,
+        max: end,
+        skip,
+        limit,
+    } = pagination;
+    let skip = skip.unwrap_or(0) as usize;
+    let limit = limit.unwrap_or(10) as usize;
+
+    let start = start.map(PrefixBound::inclusive);
+    let end = end.map(PrefixBound::exclusive);
+
+    // Each day has an entry per token, entries of the same day are next to each other
+    let mut days: Vec<DayVolume> = vec![];
+    for item in DAILY_VOLUME.prefix_range(deps.storage, start, end, Order::Ascending) {
+        let ((day, token), volume) = item?;
+        if days.last().map_or(true, |last| last.day != day) {
+            if days.len() == skip + limit {
+                break;
+            }
+            days.push(DayVolume {
+                day,
+                token_1_volume: Uint128::zero(),
+                token_2_volume: Uint128::zero(),
+            });
+        }
+        let Some(last) = days.last_mut() else {
+            continue;
+        };
+        if token == state.pair.token_1 {
+            last.token_1_volume = volume;
+        } else {
+            last.token_2_volume = volume;
+        }
+    }
+
+    Ok(to_json_binary(&DailyVolumeResponse {
+        days: days.into_iter().skip(skip).collect(),
     })?)
 }
 
@@ -176,6 +414,115 @@ fn get_pool(
         lp_shares: chain_lp_tokens,
     })
 }
+// Claimable lp fees of a chain for each token of the pair, rounded down
+pub fn get_unclaimed_lp_fees(
+    deps: Deps,
+    state: &State,
+    chain_uid: &ChainUid,
+) -> Result<Vec<(Token, Uint128)>, ContractError> {
+    get_exact_unclaimed_lp_fees(deps, state, chain_uid)?
+        .into_iter()
+        .map(|(token, unclaimed)| {
+            let unclaimed = Uint128::try_from(unclaimed.to_uint_floor()).map_err(StdError::from)?;
+            Ok((token, unclaimed))
+        })
+        .collect()
+}
+
+// The settled fees plus what the chain's lp tokens earned since the last settlement. Only hub lp
+// tokens earn claimable fees, the fees of other chains' lp tokens compound into the reserves
+pub fn get_exact_unclaimed_lp_fees(
+    deps: Deps,
+    state: &State,
+    chain_uid: &ChainUid,
+) -> Result<Vec<(Token, Decimal256)>, ContractError> {
+    let chain_lp_tokens = if *chain_uid == ChainUid::vsl_chain_uid()? {
+        CHAIN_LP_TOKENS
+            .may_load(deps.storage, chain_uid.clone())?
+            .unwrap_or_default()
+    } else {
+        Uint128::zero()
+    };
+    state
+        .pair
+        .get_vec_token()
+        .into_iter()
+        .map(|token| {
+            let key = (chain_uid.clone(), token.clone());
+            let growth = FEE_GROWTH
+                .may_load(deps.storage, token.clone())?
+                .unwrap_or_default();
+            let checkpoint = CHAIN_FEE_GROWTH
+                .may_load(deps.storage, key.clone())?
+                .unwrap_or_default();
+            let pending = Decimal256::from_ratio(chain_lp_tokens, 1u128)
+                .checked_mul(growth.checked_sub(checkpoint)?)?;
+            let unclaimed = CHAIN_UNCLAIMED_FEES
+                .may_load(deps.storage, key)?
+                .unwrap_or_default();
+            Ok((token, unclaimed.checked_add(pending)?))
+        })
+        .collect()
+}
+
+// Lp fee charged at `now`. With dynamic fees the base lp fee is raised by the largest price movement
+// in the fee window, each movement weighted down linearly with its age so the fee decays back to the base
+pub fn get_effective_lp_fee_bps(deps: Deps, state: &State, now: u64) -> Result<u64, ContractError> {
+    let base_fee_bps = state.fee.lp_fee_bps;
+    let Some(config) = &state.dynamic_fee else {
+        return Ok(base_fee_bps);
+    };
+    let Some(price) = get_pool_price(deps, state)? else {
+        return Ok(base_fee_bps);
+    };
+
+    let max_fee_bps = config.max_lp_fee_bps.max(base_fee_bps);
+    let window_start = now.saturating_sub(config.window);
+    let mut movement = Decimal::zero();
+    for observation in PRICE_OBSERVATIONS.range(
+        deps.storage,
+        Some(Bound::inclusive(window_start)),
+        None,
+        Order::Ascending,
+    ) {
+        let (timestamp, observed_price) = observation?;
+        if observed_price.is_zero() {
+            continue;
+        }
+        // A move too large to represent is charged the max fee
+        let Ok(change) = price.abs_diff(observed_price).checked_div(observed_price) else {
+            return Ok(max_fee_bps);
+        };
+        let weight = Decimal::from_ratio(
+            config.window.saturating_sub(now.saturating_sub(timestamp)),
+            config.window,
+        );
+        movement = movement.max(change.checked_mul(weight)?);
+    }
+
+    let surcharge_bps = Uint128::new(10_000)
+        .checked_mul_floor(movement.checked_mul(config.volatility_factor)?)?
+        .u128();
+    let fee_bps = u128::from(base_fee_bps)
+        .saturating_add(surcharge_bps)
+        .min(u128::from(max_fee_bps));
+    Ok(fee_bps as u64)
+}
+
+// Price of token_1 in token_2, None while the pool has no liquidity or the price overflows a Decimal
+pub fn get_pool_price(deps: Deps, state: &State) -> Result<Option<Decimal>, ContractError> {
+    let reserve_1 = BALANCES
+        .may_load(deps.storage, state.pair.token_1.clone())?
+        .unwrap_or_default();
+    let reserve_2 = BALANCES
+        .may_load(deps.storage, state.pair.token_2.clone())?
+        .unwrap_or_default();
+    if reserve_1.is_zero() || reserve_2.is_zero() {
+        return Ok(None);
+    }
+    Ok(Decimal::checked_from_ratio(reserve_2, reserve_1).ok())
+}
+
 // Function to calculate the asset to be recieved after a swap
 pub fn calculate_swap(
     swap_amount: Uint128,
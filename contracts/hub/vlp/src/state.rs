@@ -1,9 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Decimal256, Uint128};
 use cw_storage_plus::{Item, Map};
 use euclid::{
-    chain::ChainUid,
-    fee::{Fee, TotalFees},
+    chain::{ChainUid, CrossChainUser},
+    fee::{DynamicFeeConfig, Fee, FeeRecipient, TotalFees},
     token::{Pair, Token},
 };
 
@@ -24,6 +24,13 @@ pub struct State {
     // total number of LP tokens issued
     pub total_lp_tokens: Uint128,
     pub admin: String,
+    // Raises the lp fee with recent price movement when set
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    // Lp fees accrue as claimable fees instead of compounding into the reserves when set
+    #[serde(default)]
+    pub claimable_lp_fees: bool,
+    // Euclid fee split of this vlp, overrides the router default
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
 }
 
 pub const STATE: Item<State> = Item::new("state");
@@ -31,3 +38,49 @@ pub const STATE: Item<State> = Item::new("state");
 pub const CHAIN_LP_TOKENS: Map<ChainUid, Uint128> = Map::new("chain_lp_tokens");
 
 pub const BALANCES: Map<Token, Uint128> = Map::new("balances");
+
+// Claimable lp fees accrued per hub lp token for each token of the pair
+pub const FEE_GROWTH: Map<Token, Decimal256> = Map::new("fee_growth");
+
+// Fee growth at the last settlement of a chain's lp tokens
+pub const CHAIN_FEE_GROWTH: Map<(ChainUid, Token), Decimal256> = Map::new("chain_fee_growth");
+
+// Fees settled to a chain's lp tokens that are not claimed yet, kept unrounded so the lps' rounded down
+// claims always add up to less than what the chain earned
+pub const CHAIN_UNCLAIMED_FEES: Map<(ChainUid, Token), Decimal256> =
+    Map::new("chain_unclaimed_fees");
+
+// Claimable lp fees held in the vlp virtual balance on top of the reserves
+pub const LP_FEES_HELD: Map<Token, Uint128> = Map::new("lp_fees_held");
+
+// Euclid fees held in the vlp virtual balance until they are distributed
+pub const EUCLID_FEES_HELD: Map<Token, Uint128> = Map::new("euclid_fees_held");
+
+// Pool price (token_2 per token_1) before the first swap of each block, keyed by block time in seconds.
+// Only recorded while dynamic fees are enabled and pruned once older than the fee window
+pub const PRICE_OBSERVATIONS: Map<u64, Decimal> = Map::new("price_observations");
+
+// Swapped amounts (fees included) of each token since the pool started
+pub const VOLUME: Map<Token, Uint128> = Map::new("volume");
+
+// Swapped amounts of each token by the chain the swaps came from
+pub const CHAIN_VOLUME: Map<(ChainUid, Token), Uint128> = Map::new("chain_volume");
+
+// Swapped amounts of each token per day, keyed by the days since the unix epoch
+pub const DAILY_VOLUME: Map<(u64, Token), Uint128> = Map::new("daily_volume");
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// Once set the pool only accepts liquidity withdrawals
+pub const SUNSET: Item<bool> = Item::new("sunset");
+
+#[cw_serde]
+pub struct FlashSwap {
+    pub caller: CrossChainUser,
+    pub asset_out: Token,
+    pub amount_out: Uint128,
+    pub tx_id: String,
+}
+
+// Set while a flash swap callback runs, other pool actions are blocked until it settles
+pub const FLASH_SWAP: Item<FlashSwap> = Item::new("flash_swap");
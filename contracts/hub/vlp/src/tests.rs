@@ -1,14 +1,30 @@
 #[cfg(test)]
 mod tests {
-    use crate::contract::{execute, instantiate};
-    use crate::state::{State, BALANCES, CHAIN_LP_TOKENS, STATE};
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, DepsMut, Response, Uint128};
+    use crate::contract::{execute, instantiate, query};
+    use crate::state::{
+        FlashSwap, State, BALANCES, CHAIN_LP_TOKENS, EUCLID_FEES_HELD, FLASH_SWAP, LP_FEES_HELD,
+        PRICE_OBSERVATIONS, SECONDS_PER_DAY, STATE, SUNSET,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockQuerier};
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Attribute, Binary, ContractResult, CosmosMsg, Decimal,
+        DepsMut, Env, OwnedDeps, Response, StdResult, SystemError, SystemResult, Uint128, WasmMsg,
+        WasmQuery,
+    };
     use euclid::chain::{ChainUid, CrossChainUser};
     use euclid::error::ContractError;
-    use euclid::fee::{DenomFees, Fee, TotalFees};
-    use euclid::msgs::vlp::{ExecuteMsg, InstantiateMsg};
+    use euclid::fee::{DenomFees, DynamicFeeConfig, Fee, FeeRecipient, TotalFees};
+    use euclid::msgs::virtual_balance::{
+        ExecuteMsg as VirtualBalanceExecuteMsg, GetBalanceResponse, QueryMsg,
+    };
+    use euclid::msgs::vlp::{
+        DailyVolumeResponse, DayVolume, ExecuteMsg, FeeResponse, GetLiquidityResponse,
+        GetSwapResponse, InstantiateMsg, LiquidityDepthResponse, QueryMsg as VlpQueryMsg,
+        SpotPriceResponse, UnclaimedLpFeesResponse, VolumeResponse,
+    };
+    use euclid::swap::DepthTarget;
     use euclid::token::{Pair, Token};
+    use euclid::utils::Pagination;
     use std::collections::HashMap;
 
     fn init(deps: DepsMut) -> Response {
@@ -65,6 +81,9 @@ mod tests {
             last_updated: 0,
             total_lp_tokens: Uint128::zero(),
             admin: "admin".to_string(),
+            dynamic_fee: None,
+            claimable_lp_fees: false,
+            fee_recipients: None,
         };
         let state = STATE.load(&deps.storage).unwrap();
         assert_eq!(state, expected_state);
@@ -180,4 +199,1030 @@ mod tests {
             ContractError::new("Euclid Fee cannot exceed maximum limit")
         );
     }
+
+    #[test]
+    fn test_sunset() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+
+        let sender = CrossChainUser {
+            chain_uid: ChainUid::create("1".to_string()).unwrap(),
+            address: "sender_address".to_string(),
+        };
+        let pair = Pair {
+            token_1: Token::create("token1".to_string()).unwrap(),
+            token_2: Token::create("token2".to_string()).unwrap(),
+        };
+        let register_msg = ExecuteMsg::RegisterPool {
+            sender: sender.clone(),
+            pair,
+            tx_id: "1".to_string(),
+        };
+        let info = mock_info("router", &[]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            register_msg.clone(),
+        )
+        .unwrap();
+
+        // Only router can sunset the pool
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Sunset {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Sunset {},
+        )
+        .unwrap();
+        assert!(SUNSET.load(&deps.storage).unwrap());
+
+        // Chain without liquidity is dropped
+        assert!(!CHAIN_LP_TOKENS.has(&deps.storage, sender.chain_uid.clone()));
+
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), register_msg).unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+
+        let msg = ExecuteMsg::AddLiquidity {
+            sender: sender.clone(),
+            tx_id: "2".to_string(),
+            token_1_liquidity: Uint128::new(100),
+            token_2_liquidity: Uint128::new(100),
+            slippage_tolerance: 10,
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+
+        let msg = ExecuteMsg::Swap {
+            sender,
+            tx_id: "3".to_string(),
+            asset_in: Token::create("token1".to_string()).unwrap(),
+            amount_in: Uint128::new(100),
+            min_token_out: Uint128::zero(),
+            next_swaps: vec![],
+            test_fail: None,
+            asset_out: None,
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+
+        let err = execute(deps.as_mut(), env, info, ExecuteMsg::Sunset {}).unwrap_err();
+        assert_eq!(err, ContractError::PoolSunset {});
+    }
+
+    #[test]
+    fn test_locked_minimum_liquidity() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+        let info = mock_info("router", &[]);
+
+        let attacker = CrossChainUser {
+            chain_uid: ChainUid::create("1".to_string()).unwrap(),
+            address: "attacker".to_string(),
+        };
+        let victim = CrossChainUser {
+            chain_uid: ChainUid::create("2".to_string()).unwrap(),
+            address: "victim".to_string(),
+        };
+        let pair = STATE.load(&deps.storage).unwrap().pair;
+        for sender in [&attacker, &victim] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::RegisterPool {
+                    sender: sender.clone(),
+                    pair: pair.clone(),
+                    tx_id: "register".to_string(),
+                },
+            )
+            .unwrap();
+        }
+        let add_liquidity = |sender: &CrossChainUser, amount: u128| ExecuteMsg::AddLiquidity {
+            sender: sender.clone(),
+            tx_id: "add".to_string(),
+            token_1_liquidity: Uint128::new(amount),
+            token_2_liquidity: Uint128::new(amount),
+            slippage_tolerance: 10,
+        };
+        let remove_liquidity =
+            |sender: &CrossChainUser, lp_allocation: u128| ExecuteMsg::RemoveLiquidity {
+                sender: sender.clone(),
+                tx_id: "remove".to_string(),
+                lp_allocation: Uint128::new(lp_allocation),
+            };
+        let liquidity = |deps: &OwnedDeps<_, _, _>| -> GetLiquidityResponse {
+            from_json(query(deps.as_ref(), mock_env(), VlpQueryMsg::Liquidity {}).unwrap()).unwrap()
+        };
+        let locked_chain_uid = ChainUid::locked_liquidity_chain_uid().unwrap();
+
+        // First deposit has to be worth more than the locked liquidity
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_liquidity(&attacker, 1000),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("LP Allocation cannot be zero"));
+
+        // Attacker seeds the pool with the smallest deposit and withdraws everything they can
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_liquidity(&attacker, 1001),
+        )
+        .unwrap();
+        assert_eq!(
+            CHAIN_LP_TOKENS
+                .load(&deps.storage, attacker.chain_uid.clone())
+                .unwrap(),
+            Uint128::new(1)
+        );
+        assert_eq!(
+            CHAIN_LP_TOKENS
+                .load(&deps.storage, locked_chain_uid.clone())
+                .unwrap(),
+            Uint128::new(1000)
+        );
+        let res = liquidity(&deps);
+        assert_eq!(res.total_lp_tokens, Uint128::new(1001));
+        assert_eq!(res.locked_lp_tokens, Uint128::new(1000));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            remove_liquidity(&attacker, 1),
+        )
+        .unwrap();
+        // Locked lp tokens can't be withdrawn so the lp supply never drops back to zero
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            remove_liquidity(&attacker, 1000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Overflow(..)));
+        let res = liquidity(&deps);
+        assert_eq!(res.total_lp_tokens, Uint128::new(1000));
+        assert_eq!(res.token_1_reserve, Uint128::new(1000));
+
+        // Victim gets a fair share and withdraws the full deposit
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_liquidity(&victim, 1_000_000),
+        )
+        .unwrap();
+        assert_eq!(
+            CHAIN_LP_TOKENS
+                .load(&deps.storage, victim.chain_uid.clone())
+                .unwrap(),
+            Uint128::new(1_000_000)
+        );
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            remove_liquidity(&victim, 1_000_000),
+        )
+        .unwrap();
+        let released = res
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "token_1_removed_liquidity")
+            .map(|attr| attr.value.clone());
+        assert_eq!(released, Some("1000000".to_string()));
+
+        // Only the locked liquidity is left, the per chain lp tokens add up to the supply
+        let res = liquidity(&deps);
+        assert_eq!(res.total_lp_tokens, res.locked_lp_tokens);
+        let chain_total = CHAIN_LP_TOKENS
+            .range(&deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| item.unwrap().1)
+            .sum::<Uint128>();
+        assert_eq!(chain_total, res.total_lp_tokens);
+    }
+
+    #[test]
+    fn test_spot_price_and_price_impact() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+
+        let spot_price = |deps: &OwnedDeps<_, _, _>, base: &Token| {
+            query(
+                deps.as_ref(),
+                mock_env(),
+                VlpQueryMsg::SpotPrice { base: base.clone() },
+            )
+            .map(|res| from_json::<SpotPriceResponse>(res).unwrap().price)
+        };
+        // No price without liquidity
+        assert!(spot_price(&deps, &token_1).is_err());
+
+        BALANCES
+            .save(
+                deps.as_mut().storage,
+                token_1.clone(),
+                &Uint128::new(1_000_000),
+            )
+            .unwrap();
+        BALANCES
+            .save(
+                deps.as_mut().storage,
+                token_2.clone(),
+                &Uint128::new(2_000_000),
+            )
+            .unwrap();
+        assert_eq!(
+            spot_price(&deps, &token_1).unwrap(),
+            Decimal::from_ratio(2u128, 1u128)
+        );
+        assert_eq!(
+            spot_price(&deps, &token_2).unwrap(),
+            Decimal::from_ratio(1u128, 2u128)
+        );
+
+        let res: GetSwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                VlpQueryMsg::SimulateSwap {
+                    asset: token_1.clone(),
+                    asset_amount: Uint128::new(10_000),
+                    swaps: vec![],
+                    asset_out: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // 1 bps of lp fee and euclid fee, the rest is swapped against the reserves
+        assert_eq!(res.amount_out, Uint128::new(19_799));
+        assert_eq!(
+            res.execution_price,
+            Decimal::from_ratio(19_799u128, 10_000u128)
+        );
+        assert_eq!(res.hops.len(), 1);
+        let hop = &res.hops[0];
+        assert_eq!(hop.vlp_address, env.contract.address.to_string());
+        assert_eq!(hop.amount_in, Uint128::new(10_000));
+        assert_eq!(hop.lp_fee, Uint128::new(1));
+        assert_eq!(hop.euclid_fee, Uint128::new(1));
+        assert_eq!(hop.spot_price, Decimal::from_ratio(2u128, 1u128));
+        // 19_799 received for 9_998 swapped at a spot price of 2
+        assert_eq!(hop.price_impact_bps, 98);
+        assert_eq!(res.price_impact_bps, hop.price_impact_bps);
+    }
+
+    #[test]
+    fn test_liquidity_depth() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut());
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+
+        let depth = |deps: &OwnedDeps<_, _, _>, asset_out: Option<Token>| {
+            query(
+                deps.as_ref(),
+                mock_env(),
+                VlpQueryMsg::LiquidityDepth {
+                    asset_in: token_1.clone(),
+                    target: DepthTarget::PriceMoveBps(100),
+                    asset_out,
+                },
+            )
+            .map(|res| from_json::<LiquidityDepthResponse>(res).unwrap())
+        };
+        // Nothing can be swapped in an empty pool
+        let res = depth(&deps, None).unwrap();
+        assert!(res.amount_in.is_zero());
+        assert!(res.amount_out.is_zero());
+
+        BALANCES
+            .save(
+                deps.as_mut().storage,
+                token_1.clone(),
+                &Uint128::new(1_000_000),
+            )
+            .unwrap();
+        BALANCES
+            .save(
+                deps.as_mut().storage,
+                token_2.clone(),
+                &Uint128::new(2_000_000),
+            )
+            .unwrap();
+        let res = depth(&deps, Some(token_2.clone())).unwrap();
+        assert_eq!(res.asset_out, token_2);
+        assert_eq!(res.spot_price, Decimal::from_ratio(2u128, 1u128));
+        assert_eq!(res.target_price, Decimal::from_ratio(198u128, 100u128));
+        // floor(sqrt(2_000_000_000_000 / 1.98)) - 1_000_000 = 5_037 is swapped, the 1 bps lp fee left in
+        // reserve_in is too small to change it and the 2 bps of fees come on top
+        assert_eq!(res.amount_in, Uint128::new(5_039));
+        assert_eq!(res.amount_out, Uint128::new(10_024));
+
+        // The pool can't swap token1 into itself
+        assert!(depth(&deps, Some(token_1.clone())).is_err());
+    }
+
+    #[test]
+    fn test_volume() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        init(deps.as_mut());
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+        for token in [&token_1, &token_2] {
+            BALANCES
+                .save(deps.as_mut().storage, token.clone(), &Uint128::new(1000))
+                .unwrap();
+        }
+
+        let swap = |deps: &mut OwnedDeps<_, _, MockQuerier>, env: &Env, chain: &str| {
+            // The swapped amount is already in the vlp virtual balance
+            let reserve = BALANCES.load(&deps.storage, token_1.clone()).unwrap();
+            deps.querier.update_wasm(move |_| {
+                let balance = GetBalanceResponse {
+                    amount: reserve + Uint128::new(100),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&balance).unwrap()))
+            });
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("router", &[]),
+                ExecuteMsg::Swap {
+                    sender: CrossChainUser {
+                        chain_uid: ChainUid::create(chain.to_string()).unwrap(),
+                        address: "user".to_string(),
+                    },
+                    tx_id: chain.to_string(),
+                    asset_in: token_1.clone(),
+                    amount_in: Uint128::new(100),
+                    min_token_out: Uint128::one(),
+                    next_swaps: vec![],
+                    test_fail: None,
+                    asset_out: None,
+                },
+            )
+            .unwrap();
+        };
+        swap(&mut deps, &env, "1");
+        env.block.time = env.block.time.plus_seconds(SECONDS_PER_DAY);
+        swap(&mut deps, &env, "2");
+
+        let volume = |deps: &OwnedDeps<_, _, _>, chain_uid: Option<ChainUid>| {
+            from_json::<VolumeResponse>(
+                query(deps.as_ref(), mock_env(), VlpQueryMsg::Volume { chain_uid }).unwrap(),
+            )
+            .unwrap()
+        };
+        let total = volume(&deps, None);
+        assert_eq!(total.token_1_volume, Uint128::new(200));
+        assert_eq!(total.token_2_volume, Uint128::zero());
+        let chain_1 = volume(&deps, Some(ChainUid::create("1".to_string()).unwrap()));
+        assert_eq!(chain_1.token_1_volume, Uint128::new(100));
+        let chain_3 = volume(&deps, Some(ChainUid::create("3".to_string()).unwrap()));
+        assert_eq!(chain_3.token_1_volume, Uint128::zero());
+
+        let daily_volume = |deps: &OwnedDeps<_, _, _>, pagination: Pagination<u64>| {
+            from_json::<DailyVolumeResponse>(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    VlpQueryMsg::DailyVolume { pagination },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .days
+        };
+        let first_day = mock_env().block.time.seconds() / SECONDS_PER_DAY;
+        let days = daily_volume(&deps, Pagination::new(None, None, None, None));
+        assert_eq!(
+            days,
+            vec![
+                DayVolume {
+                    day: first_day,
+                    token_1_volume: Uint128::new(100),
+                    token_2_volume: Uint128::zero(),
+                },
+                DayVolume {
+                    day: first_day + 1,
+                    token_1_volume: Uint128::new(100),
+                    token_2_volume: Uint128::zero(),
+                },
+            ]
+        );
+        let days = daily_volume(&deps, Pagination::new(None, None, Some(1), Some(1)));
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].day, first_day + 1);
+        let days = daily_volume(
+            &deps,
+            Pagination::new(None, Some(first_day + 1), None, None),
+        );
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].day, first_day);
+    }
+
+    #[test]
+    fn test_flash_swap() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_1.clone(), &Uint128::new(1000))
+            .unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_2.clone(), &Uint128::new(1000))
+            .unwrap();
+
+        let flash_msg = ExecuteMsg::FlashSwap {
+            asset_out: token_1.clone(),
+            amount_out: Uint128::new(100),
+            data: Binary::default(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot", &[]),
+            flash_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 3);
+
+        // Pool is locked until the flash swap settles
+        let swap_msg = ExecuteMsg::Swap {
+            sender: CrossChainUser {
+                chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                address: "user".to_string(),
+            },
+            tx_id: "1".to_string(),
+            asset_in: token_2.clone(),
+            amount_in: Uint128::new(100),
+            min_token_out: Uint128::zero(),
+            next_swaps: vec![],
+            test_fail: None,
+            asset_out: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            swap_msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::FlashSwapInProgress {});
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bot", &[]),
+            ExecuteMsg::SettleFlashSwap {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let set_vlp_balances = |deps: &mut OwnedDeps<_, _, MockQuerier>, paid_in: u128| {
+            deps.querier.update_wasm(move |query| match query {
+                WasmQuery::Smart { msg, .. } => {
+                    let QueryMsg::GetBalance { balance_key } = from_json(msg).unwrap() else {
+                        return SystemResult::Err(SystemError::Unknown {});
+                    };
+                    let amount = if balance_key.token_id == "token1" {
+                        900
+                    } else {
+                        1000 + paid_in
+                    };
+                    let balance = GetBalanceResponse {
+                        amount: Uint128::new(amount),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&balance).unwrap()))
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            });
+        };
+        let settle = |deps: &mut OwnedDeps<_, _, MockQuerier>| {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(env.contract.address.as_str(), &[]),
+                ExecuteMsg::SettleFlashSwap {},
+            )
+        };
+
+        // Paying back less than the constant product requires fails the whole flash swap
+        set_vlp_balances(&mut deps, 111);
+        let err = settle(&mut deps).unwrap_err();
+        assert_eq!(err, ContractError::FlashSwapInvariantViolated {});
+
+        set_vlp_balances(&mut deps, 10000);
+        FLASH_SWAP
+            .save(
+                deps.as_mut().storage,
+                &FlashSwap {
+                    caller: CrossChainUser {
+                        chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                        address: "bot".to_string(),
+                    },
+                    asset_out: token_1.clone(),
+                    amount_out: Uint128::new(100),
+                    tx_id: "flash".to_string(),
+                },
+            )
+            .unwrap();
+        // With dynamic fees the settlement observes the price from before the flash swap
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.dynamic_fee = Some(DynamicFeeConfig {
+                    window: 100,
+                    volatility_factor: Decimal::percent(10),
+                    max_lp_fee_bps: 500,
+                });
+                Ok(state)
+            })
+            .unwrap();
+        let res = settle(&mut deps).unwrap();
+        assert_eq!(
+            PRICE_OBSERVATIONS
+                .load(&deps.storage, env.block.time.seconds())
+                .unwrap(),
+            Decimal::one()
+        );
+        // Euclid fee on the paid in token is held until it is distributed
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            EUCLID_FEES_HELD
+                .load(&deps.storage, token_2.clone())
+                .unwrap(),
+            Uint128::one()
+        );
+        assert!(!FLASH_SWAP.exists(&deps.storage));
+        assert_eq!(
+            BALANCES.load(&deps.storage, token_1).unwrap(),
+            Uint128::new(900)
+        );
+        assert_eq!(
+            BALANCES.load(&deps.storage, token_2).unwrap(),
+            Uint128::new(10999)
+        );
+    }
+
+    #[test]
+    fn test_dynamic_fee() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        init(deps.as_mut());
+
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_1.clone(), &Uint128::new(1000))
+            .unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_2.clone(), &Uint128::new(1000))
+            .unwrap();
+
+        let config = DynamicFeeConfig {
+            window: 100,
+            volatility_factor: Decimal::percent(10),
+            max_lp_fee_bps: 500,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::UpdateDynamicFee {
+                config: Some(config.clone()),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateDynamicFee {
+                config: Some(config),
+            },
+        )
+        .unwrap();
+
+        // Price moved from 1.25 to 1 half a window ago, the observation outside the window is ignored
+        let now = env.block.time.seconds();
+        PRICE_OBSERVATIONS
+            .save(deps.as_mut().storage, now - 150, &Decimal::percent(1000))
+            .unwrap();
+        PRICE_OBSERVATIONS
+            .save(deps.as_mut().storage, now - 50, &Decimal::percent(125))
+            .unwrap();
+
+        // 20% move weighted by 0.5 is 1000 bps, times the 0.1 factor on top of the 1 bps base fee
+        let fee: FeeResponse =
+            from_json(query(deps.as_ref(), env.clone(), VlpQueryMsg::Fee {}).unwrap()).unwrap();
+        assert_eq!(fee.effective_lp_fee_bps, 101);
+
+        let simulation: GetSwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                VlpQueryMsg::SimulateSwap {
+                    asset: token_1.clone(),
+                    asset_amount: Uint128::new(100),
+                    swaps: vec![],
+                    asset_out: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(simulation.lp_fee_bps, 101);
+        assert_eq!(simulation.euclid_fee_bps, 1);
+
+        deps.querier.update_wasm(|_| {
+            let balance = GetBalanceResponse {
+                amount: Uint128::new(1100),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&balance).unwrap()))
+        });
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::Swap {
+                sender: CrossChainUser {
+                    chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                    address: "user".to_string(),
+                },
+                tx_id: "1".to_string(),
+                asset_in: token_1,
+                amount_in: Uint128::new(100),
+                min_token_out: Uint128::one(),
+                next_swaps: vec![],
+                test_fail: None,
+                asset_out: None,
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("lp_fee_bps", "101")));
+        // Price before the swap is observed and expired observations are pruned
+        assert_eq!(
+            PRICE_OBSERVATIONS.load(&deps.storage, now).unwrap(),
+            Decimal::one()
+        );
+        assert!(!PRICE_OBSERVATIONS.has(&deps.storage, now - 150));
+
+        // Once the move leaves the window the fee is back to the base
+        env.block.time = env.block.time.plus_seconds(50);
+        PRICE_OBSERVATIONS.remove(deps.as_mut().storage, now);
+        let fee: FeeResponse =
+            from_json(query(deps.as_ref(), env, VlpQueryMsg::Fee {}).unwrap()).unwrap();
+        assert_eq!(fee.effective_lp_fee_bps, 1);
+    }
+
+    #[test]
+    fn test_dynamic_fee_extreme_price() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+        // Price of token_1 doesn't fit in a Decimal
+        BALANCES
+            .save(deps.as_mut().storage, token_1.clone(), &Uint128::one())
+            .unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_2.clone(), &Uint128::MAX)
+            .unwrap();
+        STATE
+            .update(deps.as_mut().storage, |mut state| -> StdResult<_> {
+                state.dynamic_fee = Some(DynamicFeeConfig {
+                    window: 100,
+                    volatility_factor: Decimal::percent(10),
+                    max_lp_fee_bps: 500,
+                });
+                Ok(state)
+            })
+            .unwrap();
+
+        deps.querier.update_wasm(|_| {
+            let balance = GetBalanceResponse {
+                amount: Uint128::new(101),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&balance).unwrap()))
+        });
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::Swap {
+                sender: CrossChainUser {
+                    chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                    address: "user".to_string(),
+                },
+                tx_id: "1".to_string(),
+                asset_in: token_1,
+                amount_in: Uint128::new(100),
+                min_token_out: Uint128::one(),
+                next_swaps: vec![],
+                test_fail: None,
+                asset_out: None,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&Attribute::new("lp_fee_bps", "1")));
+        // The swap goes through without observing the price
+        assert!(!PRICE_OBSERVATIONS.has(&deps.storage, env.block.time.seconds()));
+
+        // An observed move too large for a Decimal is charged the max fee
+        BALANCES
+            .save(deps.as_mut().storage, token_2, &Uint128::new(1_000_000))
+            .unwrap();
+        PRICE_OBSERVATIONS
+            .save(
+                deps.as_mut().storage,
+                env.block.time.seconds(),
+                &Decimal::raw(1),
+            )
+            .unwrap();
+        let fee: FeeResponse =
+            from_json(query(deps.as_ref(), env, VlpQueryMsg::Fee {}).unwrap()).unwrap();
+        assert_eq!(fee.effective_lp_fee_bps, 500);
+    }
+
+    #[test]
+    fn test_claimable_lp_fees() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let token_2 = Token::create("token2".to_string()).unwrap();
+        let hub = ChainUid::vsl_chain_uid().unwrap();
+        let chain_b = ChainUid::create("chainb".to_string()).unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_1.clone(), &Uint128::new(1000))
+            .unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, token_2.clone(), &Uint128::new(1000))
+            .unwrap();
+        CHAIN_LP_TOKENS
+            .save(deps.as_mut().storage, hub.clone(), &Uint128::new(60))
+            .unwrap();
+        CHAIN_LP_TOKENS
+            .save(deps.as_mut().storage, chain_b.clone(), &Uint128::new(40))
+            .unwrap();
+        let mut state = STATE.load(&deps.storage).unwrap();
+        state.total_lp_tokens = Uint128::new(100);
+        state.fee.lp_fee_bps = 100;
+        STATE.save(deps.as_mut().storage, &state).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::UpdateLpFeeMode { claimable: true },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateLpFeeMode { claimable: true },
+        )
+        .unwrap();
+
+        deps.querier.update_wasm(|_| {
+            let balance = GetBalanceResponse {
+                amount: Uint128::new(2000),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&balance).unwrap()))
+        });
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::Swap {
+                sender: CrossChainUser {
+                    chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                    address: "user".to_string(),
+                },
+                tx_id: "1".to_string(),
+                asset_in: token_1.clone(),
+                amount_in: Uint128::new(1000),
+                min_token_out: Uint128::one(),
+                next_swaps: vec![],
+                test_fail: None,
+                asset_out: None,
+            },
+        )
+        .unwrap();
+
+        // The hub lp tokens' 6 of the 10 token lp fee is held outside the reserves, the rest compounds
+        assert_eq!(
+            BALANCES.load(&deps.storage, token_1.clone()).unwrap(),
+            Uint128::new(1994)
+        );
+        assert_eq!(
+            LP_FEES_HELD.load(&deps.storage, token_1.clone()).unwrap(),
+            Uint128::new(6)
+        );
+        let unclaimed: UnclaimedLpFeesResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                VlpQueryMsg::UnclaimedLpFees {
+                    chain_uid: chain_b.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(unclaimed.token_1_fees.is_zero());
+        assert!(unclaimed.token_2_fees.is_zero());
+
+        let recipient = CrossChainUser {
+            chain_uid: hub.clone(),
+            address: "lp".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::ClaimLpFees {
+                chain_uid: hub.clone(),
+                recipient: recipient.clone(),
+                amounts: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("router", &[]),
+            ExecuteMsg::ClaimLpFees {
+                chain_uid: chain_b,
+                recipient: recipient.clone(),
+                amounts: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Only hub lp tokens accrue claimable lp fees")
+        );
+
+        // Hub lps claim their own rounded amounts until the chain total is used up
+        let claim = |deps: &mut OwnedDeps<_, _, _>, amount: u128| {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("router", &[]),
+                ExecuteMsg::ClaimLpFees {
+                    chain_uid: hub.clone(),
+                    recipient: recipient.clone(),
+                    amounts: Some((Uint128::new(amount), Uint128::zero())),
+                },
+            )
+        };
+        let err = claim(&mut deps, 7).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+        let res = claim(&mut deps, 4).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert!(res
+            .attributes
+            .contains(&Attribute::new(format!("claimed_{token_1}"), "4")));
+        let err = claim(&mut deps, 3).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+        claim(&mut deps, 2).unwrap();
+        assert!(LP_FEES_HELD.load(&deps.storage, token_1).unwrap().is_zero());
+        let unclaimed: UnclaimedLpFeesResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                VlpQueryMsg::UnclaimedLpFees { chain_uid: hub },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(unclaimed.token_1_fees.is_zero());
+    }
+
+    #[test]
+    fn test_distribute_fees() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+
+        let token_1 = Token::create("token1".to_string()).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::DistributeFees {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("No fees to distribute"));
+
+        let recipient = |address: &str| CrossChainUser {
+            chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+            address: address.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateFeeRecipients {
+                recipients: Some(vec![FeeRecipient {
+                    recipient: recipient("treasury"),
+                    weight_bps: 9_000,
+                }]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Fee recipient weights must add up to 10000 bps")
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateFeeRecipients {
+                recipients: Some(vec![
+                    FeeRecipient {
+                        recipient: recipient("treasury"),
+                        weight_bps: 6_000,
+                    },
+                    FeeRecipient {
+                        recipient: recipient("stakers"),
+                        weight_bps: 3_000,
+                    },
+                    FeeRecipient {
+                        recipient: recipient("insurance"),
+                        weight_bps: 1_000,
+                    },
+                ]),
+            },
+        )
+        .unwrap();
+
+        EUCLID_FEES_HELD
+            .save(deps.as_mut().storage, token_1.clone(), &Uint128::new(105))
+            .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::DistributeFees {},
+        )
+        .unwrap();
+        // Rounding dust goes to the first recipient
+        let amounts: Vec<Uint128> = res
+            .messages
+            .iter()
+            .map(|msg| match &msg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                    let VirtualBalanceExecuteMsg::Transfer(transfer) = from_json(msg).unwrap()
+                    else {
+                        panic!("Unexpected message");
+                    };
+                    transfer.amount
+                }
+                _ => panic!("Unexpected message"),
+            })
+            .collect();
+        assert_eq!(
+            amounts,
+            vec![Uint128::new(64), Uint128::new(31), Uint128::new(10)]
+        );
+        assert!(!EUCLID_FEES_HELD.has(&deps.storage, token_1));
+    }
 }
@@ -13,8 +13,8 @@ use cw20_base::contract::{
 };
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:cw20";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cw20";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -23,9 +23,9 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
     let cw20_resp = cw20_instantiate(deps.branch(), env.clone(), info, msg.clone().into())?;
+    // cw20 base sets its own contract version, so overwrite it after instantiating
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let state = State {
         token_pair: msg.token_pair.clone(),
         factory_address: msg.factory,
@@ -1,9 +1,23 @@
 use cosmwasm_std::{entry_point, DepsMut, Env, Response};
-use euclid::{error::ContractError, msgs::vlp::MigrateMsg};
+use euclid::{error::ContractError, migrate::ensure_migration_version, msgs::cw20::MigrateMsg};
 
-/// This is the migrate entry point for the contract.
-/// Currently, it does not perform any migration logic and simply returns an empty response.
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+
+/// This is the migrate entry point for the lp token.
+/// Lp tokens instantiated as a plain cw20 base contract are accepted and moved to this contract's version.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Older lp tokens were stored under the cw20 base contract version
+    let previous = ensure_migration_version(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &["crates.io:cw20-base"],
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
 }
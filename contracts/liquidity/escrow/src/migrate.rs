@@ -1,9 +1,17 @@
 use cosmwasm_std::{entry_point, DepsMut, Env, Response};
-use euclid::{error::ContractError, msgs::vlp::MigrateMsg};
+use euclid::{error::ContractError, migrate::ensure_migration_version, msgs::escrow::MigrateMsg};
 
-/// This is the migrate entry point for the contract.
-/// Currently, it does not perform any migration logic and simply returns an empty response.
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+
+/// This is the migrate entry point for the escrow.
+/// Allowed denoms and balances are left untouched, only the contract version is checked and bumped.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_migration_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &[])?;
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError};
+use cosmwasm_std::{ensure, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError};
 use cw2::set_contract_version;
 use euclid::chain::CrossChainUser;
 use euclid::error::ContractError;
@@ -10,14 +10,19 @@ use euclid::fee::DenomFees;
 use euclid_ibc::msg::CHAIN_IBC_EXECUTE_MSG_QUEUE_RANGE;
 
 use crate::execute::{
-    add_liquidity_request, execute_native_receive_callback, execute_request_deregister_denom,
-    execute_request_pool_creation, execute_request_register_denom, execute_request_register_escrow,
-    execute_swap_request, execute_update_hub_channel, execute_withdraw_virtual_balance,
-    receive_cw20,
+    add_liquidity_from_virtual_balance_request, add_liquidity_leg_request, add_liquidity_request,
+    add_multi_asset_liquidity_request, execute_cancel_dca_schedule, execute_create_dca_schedule,
+    execute_dca_swap, execute_native_receive_callback, execute_request_deregister_denom,
+    execute_request_multi_asset_pool_creation, execute_request_pool_creation,
+    execute_request_register_denom, execute_request_register_escrow, execute_request_retire_escrow,
+    execute_swap_request, execute_update_hub_channel, execute_update_pool_creation_fee,
+    execute_withdraw_virtual_balance, receive_cw20, remove_multi_asset_liquidity_request,
 };
 use crate::query::{
-    get_escrow, get_lp_token_address, get_partner_fees_collected, get_vlp, pending_liquidity,
-    pending_remove_liquidity, pending_swaps, query_all_pools, query_all_tokens, query_state,
+    get_dca_schedule, get_dca_schedules, get_escrow, get_lp_token_address,
+    get_partner_fees_collected, get_pool_creation_fee, get_vlp, pending_liquidity,
+    pending_remove_liquidity, pending_swaps, query_all_pools, query_all_tokens,
+    query_retiring_tokens, query_state,
 };
 use crate::reply::{
     on_cw20_instantiate_reply, on_escrow_instantiate_reply, on_ibc_ack_and_timeout_reply,
@@ -29,8 +34,8 @@ use crate::{ibc, reply};
 use euclid::msgs::factory::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:factory";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:factory";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -87,6 +92,48 @@ pub fn execute(
             slippage_tolerance,
             timeout,
         ),
+        ExecuteMsg::AddLiquidityLegRequest {
+            intent_id,
+            pair,
+            counterparty,
+            token,
+            amount,
+            slippage_tolerance,
+            recipient,
+            deadline,
+            timeout,
+        } => add_liquidity_leg_request(
+            &mut deps,
+            info,
+            env,
+            intent_id,
+            pair,
+            counterparty,
+            token,
+            amount,
+            slippage_tolerance,
+            recipient,
+            deadline,
+            timeout,
+        ),
+        ExecuteMsg::AddLiquidityFromVirtualBalance {
+            pair,
+            token_1_liquidity,
+            token_2_liquidity,
+            slippage_tolerance,
+            recipient,
+            timeout,
+        } => add_liquidity_from_virtual_balance_request(
+            &mut deps,
+            info,
+            env,
+            pair,
+            token_1_liquidity,
+            token_2_liquidity,
+            slippage_tolerance,
+            recipient,
+            timeout,
+        ),
         ExecuteMsg::ExecuteSwapRequest {
             asset_in,
             asset_out,
@@ -117,9 +164,54 @@ pub fn execute(
                 partner_fee,
             )
         }
+        ExecuteMsg::CreateDcaSchedule {
+            asset_in,
+            asset_out,
+            amount_per_swap,
+            min_amount_out,
+            total_swaps,
+            interval,
+            swaps,
+            timeout,
+            cross_chain_addresses,
+        } => {
+            // Cw20 deposits go through the receive hook
+            ensure!(
+                asset_in.token_type.is_native(),
+                ContractError::UnsupportedDenomination {}
+            );
+            let deposit = info
+                .funds
+                .iter()
+                .find(|coin| coin.denom == asset_in.get_denom())
+                .map(|coin| coin.amount)
+                .unwrap_or_default();
+            execute_create_dca_schedule(
+                &mut deps,
+                env,
+                info.sender,
+                deposit,
+                asset_in,
+                asset_out,
+                amount_per_swap,
+                min_amount_out,
+                total_swaps,
+                interval,
+                swaps,
+                timeout,
+                cross_chain_addresses,
+            )
+        }
+        ExecuteMsg::ExecuteDcaSwap { schedule_id } => execute_dca_swap(&mut deps, env, schedule_id),
+        ExecuteMsg::CancelDcaSchedule { schedule_id } => {
+            execute_cancel_dca_schedule(&mut deps, info, schedule_id)
+        }
         ExecuteMsg::UpdateHubChannel { new_channel } => {
             execute_update_hub_channel(deps, info, new_channel)
         }
+        ExecuteMsg::UpdatePoolCreationFee { fee } => {
+            execute_update_pool_creation_fee(deps, info, fee)
+        }
         ExecuteMsg::RequestRegisterDenom { token } => {
             execute_request_register_denom(deps, info, token)
         }
@@ -133,6 +225,7 @@ pub fn execute(
             lp_token_decimal,
             lp_token_marketing,
             timeout,
+            initial_liquidity,
         } => execute_request_pool_creation(
             &mut deps,
             env,
@@ -143,10 +236,43 @@ pub fn execute(
             lp_token_decimal,
             lp_token_marketing,
             timeout,
+            initial_liquidity,
         ),
         ExecuteMsg::RequestRegisterEscrow { token, timeout } => {
             execute_request_register_escrow(&mut deps, env, info, token, timeout)
         }
+        ExecuteMsg::RequestRetireEscrow { token, timeout } => {
+            execute_request_retire_escrow(&mut deps, env, info, token, timeout)
+        }
+        ExecuteMsg::RequestMultiAssetPoolCreation { tokens, timeout } => {
+            execute_request_multi_asset_pool_creation(&mut deps, env, info, tokens, timeout)
+        }
+        ExecuteMsg::AddMultiAssetLiquidityFromVirtualBalance {
+            vlp_address,
+            liquidity,
+            min_lp_allocation,
+            timeout,
+        } => add_multi_asset_liquidity_request(
+            &mut deps,
+            env,
+            info,
+            vlp_address,
+            liquidity,
+            min_lp_allocation,
+            timeout,
+        ),
+        ExecuteMsg::RemoveMultiAssetLiquidity {
+            vlp_address,
+            lp_allocation,
+            timeout,
+        } => remove_multi_asset_liquidity_request(
+            &mut deps,
+            env,
+            info,
+            vlp_address,
+            lp_allocation,
+            timeout,
+        ),
         ExecuteMsg::WithdrawVirtualBalance {
             token,
             amount,
@@ -191,8 +317,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         QueryMsg::PendingRemoveLiquidity { user, pagination } => {
             pending_remove_liquidity(deps, user, pagination)
         }
+        QueryMsg::GetDcaSchedule { schedule_id } => get_dca_schedule(deps, schedule_id),
+        QueryMsg::GetDcaSchedules { pagination } => get_dca_schedules(deps, pagination),
         QueryMsg::GetAllTokens {} => query_all_tokens(deps),
+        QueryMsg::GetRetiringTokens {} => query_retiring_tokens(deps),
         QueryMsg::GetPartnerFeesCollected {} => get_partner_fees_collected(deps),
+        QueryMsg::GetPoolCreationFee {} => get_pool_creation_fee(deps),
     }
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    ensure, from_json, to_json_binary, Binary, CosmosMsg, Decimal, DepsMut, Env, IbcTimeout,
-    MessageInfo, Response, SubMsg, Uint128, WasmMsg,
+    coins, ensure, from_json, to_json_binary, Addr, Binary, CosmosMsg, Decimal, DepsMut, Env,
+    IbcTimeout, MessageInfo, Response, SubMsg, Uint128, WasmMsg,
 };
 use cw20::Cw20ReceiveMsg;
 use euclid::{
@@ -9,25 +9,30 @@ use euclid::{
     error::ContractError,
     events::{swap_event, tx_event, TxType},
     fee::{PartnerFee, MAX_PARTNER_FEE_BPS},
-    liquidity::{AddLiquidityRequest, RemoveLiquidityRequest},
+    liquidity::{
+        AddLiquidityLegRequest, AddLiquidityRequest, InitialLiquidity, RemoveLiquidityRequest,
+    },
     msgs::escrow::{AllowedTokenResponse, QueryMsg as EscrowQueryMsg},
-    pool::{EscrowCreateRequest, PoolCreateRequest},
-    swap::{NextSwapPair, SwapRequest},
+    pool::{sort_pool_tokens, EscrowCreateRequest, PoolCreateRequest, PoolCreationFee},
+    swap::{DcaSchedule, NextSwapPair, SwapRequest},
     timeout::get_timeout,
     token::{Pair, PairWithDenom, Token, TokenWithDenom},
     utils::generate_tx,
 };
 use euclid_ibc::msg::{
-    ChainIbcExecuteMsg, ChainIbcRemoveLiquidityExecuteMsg, ChainIbcWithdrawExecuteMsg,
-    HubIbcExecuteMsg,
+    ChainIbcAddLiquidityFromVirtualBalanceExecuteMsg, ChainIbcAddLiquidityLegExecuteMsg,
+    ChainIbcAddMultiAssetLiquidityExecuteMsg, ChainIbcExecuteMsg,
+    ChainIbcRemoveLiquidityExecuteMsg, ChainIbcRemoveMultiAssetLiquidityExecuteMsg,
+    ChainIbcWithdrawExecuteMsg, HubIbcExecuteMsg,
 };
 
 use crate::{
     ibc::receive,
     state::{
-        HUB_CHANNEL, PAIR_TO_VLP, PENDING_ADD_LIQUIDITY, PENDING_ESCROW_REQUESTS,
-        PENDING_POOL_REQUESTS, PENDING_REMOVE_LIQUIDITY, PENDING_SWAPS, STATE, TOKEN_TO_ESCROW,
-        VLP_TO_CW20,
+        DCA_SCHEDULES, DCA_SCHEDULE_COUNT, HUB_CHANNEL, PAIR_TO_VLP, PENDING_ADD_LIQUIDITY,
+        PENDING_ADD_LIQUIDITY_LEGS, PENDING_ESCROW_REQUESTS, PENDING_MULTI_ASSET_POOL_FEES,
+        PENDING_POOL_REQUESTS, PENDING_REMOVE_LIQUIDITY, PENDING_SWAPS, POOL_CREATION_FEE,
+        RETIRING_TOKENS, STATE, TOKEN_TO_ESCROW, VLP_TO_CW20,
     },
 };
 
@@ -50,6 +55,32 @@ pub fn execute_update_hub_channel(
     ))
 }
 
+pub fn execute_update_pool_creation_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee: Option<PoolCreationFee>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(info.sender == state.admin, ContractError::Unauthorized {});
+
+    let mut response = Response::new().add_attribute("method", "update_pool_creation_fee");
+    match fee {
+        Some(fee) => {
+            ensure!(
+                !fee.amount.amount.is_zero(),
+                ContractError::ZeroAssetAmount {}
+            );
+            deps.api.addr_validate(&fee.treasury)?;
+            POOL_CREATION_FEE.save(deps.storage, &fee)?;
+            response = response
+                .add_attribute("fee", fee.amount.to_string())
+                .add_attribute("treasury", fee.treasury);
+        }
+        None => POOL_CREATION_FEE.remove(deps.storage),
+    }
+    Ok(response)
+}
+
 // Function to send IBC request to Router in VSL to create a new pool
 pub fn execute_request_pool_creation(
     deps: &mut DepsMut,
@@ -61,6 +92,7 @@ pub fn execute_request_pool_creation(
     lp_token_decimal: u8,
     lp_token_marketing: Option<cw20_base::msg::InstantiateMarketingInfo>,
     timeout: Option<u64>,
+    initial_liquidity: Option<InitialLiquidity>,
 ) -> Result<Response, ContractError> {
     let state = STATE.load(deps.storage)?;
     let sender = CrossChainUser {
@@ -80,6 +112,10 @@ pub fn execute_request_pool_creation(
 
     let tokens = pair.get_vec_token_info();
     for token in tokens {
+        ensure!(
+            !RETIRING_TOKENS.has(deps.storage, token.token.clone()),
+            ContractError::EscrowRetiring {}
+        );
         let escrow_address = TOKEN_TO_ESCROW.may_load(deps.storage, token.clone().token)?;
         if let Some(escrow_address) = escrow_address {
             let token_allowed_query_msg = EscrowQueryMsg::TokenAllowed {
@@ -111,11 +147,65 @@ pub fn execute_request_pool_creation(
         marketing: lp_token_marketing,
     };
     lp_token_instantiate_msg.validate()?;
+
+    // The deposit is held here until the ack, then moved to the escrows or refunded
+    let mut deposit_msgs: Vec<CosmosMsg> = Vec::new();
+    if let Some(initial_liquidity) = &initial_liquidity {
+        // Liquidity amounts follow the pair order
+        pair.validate()?;
+        ensure!(
+            !(initial_liquidity.token_1_liquidity.is_zero()
+                || initial_liquidity.token_2_liquidity.is_zero()),
+            ContractError::ZeroAssetAmount {}
+        );
+        ensure!(
+            (1..=100).contains(&initial_liquidity.slippage_tolerance),
+            ContractError::InvalidSlippageTolerance {}
+        );
+        deposit_msgs.extend(liquidity_deposit_msg(
+            &info,
+            &env,
+            &pair.token_1,
+            initial_liquidity.token_1_liquidity,
+        )?);
+        deposit_msgs.extend(liquidity_deposit_msg(
+            &info,
+            &env,
+            &pair.token_2,
+            initial_liquidity.token_2_liquidity,
+        )?);
+    }
+
+    // The fee is sent on top of any native initial liquidity of the same denom
+    let creation_fee = POOL_CREATION_FEE.may_load(deps.storage)?;
+    if let Some(fee) = &creation_fee {
+        let mut required = fee.amount.amount;
+        if let Some(initial_liquidity) = &initial_liquidity {
+            for (token, amount) in [
+                (&pair.token_1, initial_liquidity.token_1_liquidity),
+                (&pair.token_2, initial_liquidity.token_2_liquidity),
+            ] {
+                if token.token_type.is_native() && token.get_denom() == fee.amount.denom {
+                    required = required.checked_add(amount)?;
+                }
+            }
+        }
+        let paid = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == fee.amount.denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+        ensure!(paid >= required, ContractError::InsufficientDeposit {});
+    }
+
     let req = PoolCreateRequest {
         tx_id: tx_id.clone(),
         sender: info.sender.to_string(),
         pair_info: pair.clone(),
         lp_token_instantiate_msg,
+        initial_liquidity: initial_liquidity.clone(),
+        creation_fee,
     };
 
     PENDING_POOL_REQUESTS.save(deps.storage, (info.sender.clone(), tx_id.clone()), &req)?;
@@ -124,6 +214,7 @@ pub fn execute_request_pool_creation(
         pair: pair.get_pair()?,
         sender,
         tx_id: tx_id.clone(),
+        initial_liquidity,
     }
     .to_msg(
         deps,
@@ -143,9 +234,48 @@ pub fn execute_request_pool_creation(
         ))
         .add_attribute("tx_id", tx_id)
         .add_attribute("method", "request_pool_creation")
+        .add_messages(deposit_msgs)
         .add_submessage(pool_create_msg))
 }
 
+// Cw20 deposits are pulled from the sender with an allowance, native deposits have to be sent
+// with the message
+fn liquidity_deposit_msg(
+    info: &MessageInfo,
+    env: &Env,
+    token: &TokenWithDenom,
+    amount: Uint128,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    if token.token_type.is_smart() {
+        let msg = token.token_type.create_transfer_msg(
+            amount,
+            env.contract.address.to_string(),
+            Some(info.sender.to_string()),
+        )?;
+        return Ok(Some(msg));
+    }
+    // If funds empty return error
+    ensure!(
+        !info.funds.is_empty(),
+        ContractError::InsufficientDeposit {}
+    );
+
+    // Check for funds sent with the message
+    let amt = info
+        .funds
+        .iter()
+        .find(|x| x.denom == token.token_type.get_denom())
+        .ok_or(ContractError::Generic {
+            err: "Denom not found".to_string(),
+        })?;
+
+    ensure!(
+        amt.amount.ge(&amount),
+        ContractError::InsufficientDeposit {}
+    );
+    Ok(None)
+}
+
 pub fn execute_request_register_escrow(
     deps: &mut DepsMut,
     env: Env,
@@ -207,6 +337,58 @@ pub fn execute_request_register_escrow(
         .add_submessage(register_escrow_msg))
 }
 
+pub fn execute_request_retire_escrow(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Token,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(state.admin == info.sender, ContractError::Unauthorized {});
+
+    ensure!(
+        TOKEN_TO_ESCROW.has(deps.storage, token.clone()),
+        ContractError::EscrowDoesNotExist {}
+    );
+
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let timeout = get_timeout(timeout)?;
+
+    // Retirement is tracked after the ack because router decides whether the escrow can already be removed
+    let retire_escrow_msg = ChainIbcExecuteMsg::RequestEscrowRetirement {
+        token: token.clone(),
+        sender,
+        tx_id: tx_id.clone(),
+    }
+    .to_msg(
+        deps,
+        &env,
+        state.router_contract,
+        state.chain_uid,
+        state.is_native,
+        channel,
+        timeout,
+    )?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            info.sender.as_str(),
+            TxType::EscrowRetirement,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "request_escrow_retirement")
+        .add_attribute("token", token.to_string())
+        .add_submessage(retire_escrow_msg))
+}
+
 // Add liquidity to the pool
 // TODO look into alternatives of using .branch(), maybe unifying the functions would help
 pub fn add_liquidity_request(
@@ -256,6 +438,10 @@ pub fn add_liquidity_request(
     // Do an early check for tokens escrow so that if it exists, it should allow the denom that we are sending
     let tokens = pair_info.get_vec_token_info();
     for token in tokens {
+        ensure!(
+            !RETIRING_TOKENS.has(deps.storage, token.token.clone()),
+            ContractError::EscrowRetiring {}
+        );
         let escrow_address = TOKEN_TO_ESCROW
             .load(deps.storage, token.token)
             .or(Err(ContractError::EscrowDoesNotExist {}))?;
@@ -280,86 +466,185 @@ pub fn add_liquidity_request(
     let mut msgs: Vec<CosmosMsg> = Vec::new();
 
     // IF TOKEN IS A SMART CONTRACT IT REQUIRES APPROVAL FOR TRANSFER
-    if token_1.token_type.is_smart() {
-        let msg = token_1.token_type.create_transfer_msg(
-            token_1_liquidity,
-            env.contract.address.clone().to_string(),
-            Some(sender.address.clone()),
-        )?;
-        msgs.push(msg);
-    } else {
-        // If funds empty return error
-        ensure!(
-            !info.funds.is_empty(),
-            ContractError::InsufficientDeposit {}
-        );
+    msgs.extend(liquidity_deposit_msg(
+        &info,
+        &env,
+        &token_1,
+        token_1_liquidity,
+    )?);
+    msgs.extend(liquidity_deposit_msg(
+        &info,
+        &env,
+        &token_2,
+        token_2_liquidity,
+    )?);
 
-        // Check for funds sent with the message
-        let amt = info
-            .funds
-            .iter()
-            .find(|x| x.denom == token_1.token_type.get_denom())
-            .ok_or(ContractError::Generic {
-                err: "Denom not found".to_string(),
-            })?;
+    let liquidity_tx_info = AddLiquidityRequest {
+        sender: info.sender.to_string(),
+        token_1_liquidity,
+        token_2_liquidity,
+        pair_info,
+        tx_id: tx_id.clone(),
+    };
 
-        ensure!(
-            amt.amount.ge(&token_1_liquidity),
-            ContractError::InsufficientDeposit {}
-        );
+    PENDING_ADD_LIQUIDITY.save(
+        deps.storage,
+        (info.sender.clone(), tx_id.clone()),
+        &liquidity_tx_info,
+    )?;
+
+    let add_liq_msg = ChainIbcExecuteMsg::AddLiquidity {
+        sender,
+        token_1_liquidity,
+        token_2_liquidity,
+        slippage_tolerance,
+        pair,
+        tx_id: tx_id.clone(),
     }
+    .to_msg(
+        deps,
+        &env,
+        state.router_contract,
+        state.chain_uid,
+        state.is_native,
+        channel,
+        timeout,
+    )?;
 
-    // Same for token 2
-    if token_2.token_type.is_smart() {
-        let msg = token_2.token_type.create_transfer_msg(
-            token_2_liquidity,
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            info.sender.as_str(),
+            euclid::events::TxType::AddLiquidity,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "add_liquidity_request")
+        .add_messages(msgs)
+        .add_submessage(add_liq_msg))
+}
+
+// Deposit one token of a liquidity intent, the other token is deposited through its own factory
+pub fn add_liquidity_leg_request(
+    deps: &mut DepsMut,
+    info: MessageInfo,
+    env: Env,
+    intent_id: String,
+    pair: Pair,
+    counterparty: CrossChainUser,
+    token: TokenWithDenom,
+    amount: Uint128,
+    slippage_tolerance: u64,
+    recipient: CrossChainUser,
+    deadline: u64,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    pair.validate()?;
+    token.token.validate()?;
+    recipient.chain_uid.validate()?;
+    counterparty.chain_uid.validate()?;
+
+    let state = STATE.load(deps.storage)?;
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    ensure!(
+        !intent_id.is_empty(),
+        ContractError::new("Intent id cannot be empty")
+    );
+    ensure!(
+        (1..=100).contains(&slippage_tolerance),
+        ContractError::InvalidSlippageTolerance {}
+    );
+    ensure!(!amount.is_zero(), ContractError::ZeroAssetAmount {});
+    ensure!(
+        deadline > env.block.time.seconds(),
+        ContractError::new("Deadline has already passed")
+    );
+    ensure!(
+        pair.get_vec_token().contains(&token.token),
+        ContractError::new("Token is not part of the pair")
+    );
+    ensure!(
+        !PENDING_ADD_LIQUIDITY_LEGS.has(deps.storage, (info.sender.clone(), tx_id.clone())),
+        ContractError::TxAlreadyExist {}
+    );
+    ensure!(
+        PAIR_TO_VLP.has(deps.storage, pair.get_tupple()),
+        ContractError::PoolDoesNotExists {}
+    );
+    ensure!(
+        !RETIRING_TOKENS.has(deps.storage, token.token.clone()),
+        ContractError::EscrowRetiring {}
+    );
+
+    let escrow_address = TOKEN_TO_ESCROW
+        .load(deps.storage, token.token.clone())
+        .or(Err(ContractError::EscrowDoesNotExist {}))?;
+    let token_allowed: AllowedTokenResponse = deps.querier.query_wasm_smart(
+        escrow_address,
+        &EscrowQueryMsg::TokenAllowed {
+            denom: token.token_type.clone(),
+        },
+    )?;
+    ensure!(
+        token_allowed.allowed,
+        ContractError::UnsupportedDenomination {}
+    );
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let timeout = get_timeout(timeout)?;
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    // IF TOKEN IS A SMART CONTRACT IT REQUIRES APPROVAL FOR TRANSFER
+    if token.token_type.is_smart() {
+        let msg = token.token_type.create_transfer_msg(
+            amount,
             env.contract.address.clone().to_string(),
             Some(sender.address.clone()),
         )?;
         msgs.push(msg);
     } else {
-        // If funds empty return error
-        ensure!(
-            !info.funds.is_empty(),
-            ContractError::InsufficientDeposit {}
-        );
-
         let amt = info
             .funds
             .iter()
-            .find(|x| x.denom == token_2.token_type.get_denom())
+            .find(|x| x.denom == token.token_type.get_denom())
             .ok_or(ContractError::Generic {
                 err: "Denom not found".to_string(),
             })?;
 
         ensure!(
-            amt.amount.ge(&token_2_liquidity),
+            amt.amount.ge(&amount),
             ContractError::InsufficientDeposit {}
         );
     }
 
-    let liquidity_tx_info = AddLiquidityRequest {
-        sender: info.sender.to_string(),
-        token_1_liquidity,
-        token_2_liquidity,
-        pair_info,
-        tx_id: tx_id.clone(),
-    };
-
-    PENDING_ADD_LIQUIDITY.save(
+    PENDING_ADD_LIQUIDITY_LEGS.save(
         deps.storage,
         (info.sender.clone(), tx_id.clone()),
-        &liquidity_tx_info,
+        &AddLiquidityLegRequest {
+            sender: info.sender.to_string(),
+            tx_id: tx_id.clone(),
+            intent_id: intent_id.clone(),
+            token: token.clone(),
+            amount,
+        },
     )?;
 
-    let add_liq_msg = ChainIbcExecuteMsg::AddLiquidity {
+    let add_liq_leg_msg = ChainIbcExecuteMsg::AddLiquidityLeg(ChainIbcAddLiquidityLegExecuteMsg {
         sender,
-        token_1_liquidity,
-        token_2_liquidity,
-        slippage_tolerance,
+        intent_id: intent_id.clone(),
         pair,
+        counterparty,
+        token: token.token,
+        amount,
+        slippage_tolerance,
+        recipient,
+        deadline,
         tx_id: tx_id.clone(),
-    }
+    })
     .to_msg(
         deps,
         &env,
@@ -377,8 +662,81 @@ pub fn add_liquidity_request(
             euclid::events::TxType::AddLiquidity,
         ))
         .add_attribute("tx_id", tx_id)
-        .add_attribute("method", "add_liquidity_request")
+        .add_attribute("method", "add_liquidity_leg_request")
+        .add_attribute("intent_id", intent_id)
         .add_messages(msgs)
+        .add_submessage(add_liq_leg_msg))
+}
+
+// Add liquidity from the sender's virtual balance, nothing is escrowed on this chain
+pub fn add_liquidity_from_virtual_balance_request(
+    deps: &mut DepsMut,
+    info: MessageInfo,
+    env: Env,
+    pair: Pair,
+    token_1_liquidity: Uint128,
+    token_2_liquidity: Uint128,
+    slippage_tolerance: u64,
+    recipient: Option<CrossChainUser>,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    pair.validate()?;
+
+    let state = STATE.load(deps.storage)?;
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let recipient = recipient.unwrap_or(sender.clone());
+    recipient.chain_uid.validate()?;
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+
+    ensure!(
+        (1..=100).contains(&slippage_tolerance),
+        ContractError::InvalidSlippageTolerance {}
+    );
+    ensure!(
+        !(token_1_liquidity.is_zero() || token_2_liquidity.is_zero()),
+        ContractError::ZeroAssetAmount {}
+    );
+    ensure!(
+        PAIR_TO_VLP.has(deps.storage, pair.get_tupple()),
+        ContractError::PoolDoesNotExists {}
+    );
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let timeout = get_timeout(timeout)?;
+
+    let add_liq_msg = ChainIbcExecuteMsg::AddLiquidityFromVirtualBalance(
+        ChainIbcAddLiquidityFromVirtualBalanceExecuteMsg {
+            sender,
+            pair,
+            token_1_liquidity,
+            token_2_liquidity,
+            slippage_tolerance,
+            recipient: recipient.clone(),
+            tx_id: tx_id.clone(),
+        },
+    )
+    .to_msg(
+        deps,
+        &env,
+        state.router_contract,
+        state.chain_uid,
+        state.is_native,
+        channel,
+        timeout,
+    )?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            info.sender.as_str(),
+            euclid::events::TxType::AddLiquidity,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "add_liquidity_from_virtual_balance_request")
+        .add_attribute("recipient", recipient.to_sender_string())
         .add_submessage(add_liq_msg))
 }
 
@@ -393,6 +751,7 @@ pub fn remove_liquidity_request(
     lp_allocation: Uint128,
     timeout: Option<u64>,
     cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+    keep_in_virtual_balance: bool,
 ) -> Result<Response, ContractError> {
     let state = STATE.load(deps.storage)?;
     let sender_addr = deps.api.addr_validate(&sender.address)?;
@@ -440,6 +799,7 @@ pub fn remove_liquidity_request(
         lp_allocation,
         pair,
         cross_chain_addresses,
+        keep_in_virtual_balance,
         tx_id: tx_id.clone(),
     })
     .to_msg(
@@ -533,6 +893,10 @@ pub fn execute_swap_request(
     let timeout = get_timeout(timeout)?;
 
     // Verify that this asset is allowed
+    ensure!(
+        !RETIRING_TOKENS.has(deps.storage, asset_in.token.clone()),
+        ContractError::EscrowRetiring {}
+    );
     let escrow = TOKEN_TO_ESCROW.load(deps.storage, asset_in.token.clone())?;
 
     let token_allowed: euclid::msgs::escrow::AllowedTokenResponse = deps.querier.query_wasm_smart(
@@ -627,6 +991,170 @@ pub fn execute_swap_request(
         .add_submessage(swap_msg))
 }
 
+pub fn execute_create_dca_schedule(
+    deps: &mut DepsMut,
+    env: Env,
+    owner: Addr,
+    deposit: Uint128,
+    asset_in: TokenWithDenom,
+    asset_out: Token,
+    amount_per_swap: Uint128,
+    min_amount_out: Uint128,
+    total_swaps: u64,
+    interval: u64,
+    swaps: Vec<NextSwapPair>,
+    timeout: Option<u64>,
+    cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+) -> Result<Response, ContractError> {
+    asset_in.validate(deps.as_ref())?;
+    ensure!(
+        !amount_per_swap.is_zero() && !min_amount_out.is_zero(),
+        ContractError::ZeroAssetAmount {}
+    );
+    ensure!(
+        total_swaps > 0 && interval > 0,
+        ContractError::new("Schedule needs at least one swap and a non zero interval")
+    );
+    let first_swap = swaps
+        .first()
+        .ok_or(ContractError::new("Empty Swap not allowed"))?;
+    ensure!(
+        first_swap.token_in == asset_in.token,
+        ContractError::new("Amount in doesn't match swap route")
+    );
+    let last_swap = swaps
+        .last()
+        .ok_or(ContractError::new("Empty Swap not allowed"))?;
+    ensure!(
+        last_swap.token_out == asset_out,
+        ContractError::new("Amount out doesn't match swap route")
+    );
+
+    let schedule = DcaSchedule {
+        owner: owner.clone(),
+        asset_in,
+        asset_out,
+        amount_per_swap,
+        min_amount_out,
+        swaps,
+        cross_chain_addresses,
+        timeout,
+        interval,
+        total_swaps,
+        executed_swaps: 0,
+        // First swap can be executed right away
+        next_execution: env.block.time.seconds(),
+    };
+    ensure!(
+        deposit == schedule.remaining_amount()?,
+        ContractError::new("Deposit must equal amount per swap times total swaps")
+    );
+
+    let schedule_id = DCA_SCHEDULE_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(1)
+        .ok_or(ContractError::new("Dca schedule count overflow"))?;
+    DCA_SCHEDULE_COUNT.save(deps.storage, &schedule_id)?;
+    DCA_SCHEDULES.save(deps.storage, schedule_id, &schedule)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "create_dca_schedule")
+        .add_attribute("schedule_id", schedule_id.to_string())
+        .add_attribute("owner", owner)
+        .add_attribute("deposit", deposit)
+        .add_attribute("total_swaps", total_swaps.to_string())
+        .add_attribute("interval", interval.to_string()))
+}
+
+pub fn execute_dca_swap(
+    deps: &mut DepsMut,
+    env: Env,
+    schedule_id: u64,
+) -> Result<Response, ContractError> {
+    let mut schedule = DCA_SCHEDULES
+        .load(deps.storage, schedule_id)
+        .map_err(|_err| ContractError::new("Dca schedule doesn't exist"))?;
+    ensure!(
+        env.block.time.seconds() >= schedule.next_execution,
+        ContractError::new("Next dca swap is not due yet")
+    );
+
+    schedule.executed_swaps += 1;
+    schedule.next_execution = env.block.time.seconds().saturating_add(schedule.interval);
+    if schedule.executed_swaps == schedule.total_swaps {
+        DCA_SCHEDULES.remove(deps.storage, schedule_id);
+    } else {
+        DCA_SCHEDULES.save(deps.storage, schedule_id, &schedule)?;
+    }
+
+    // The leg amount is already held by the factory, so the swap is requested as if it was just deposited
+    let info = if schedule.asset_in.token_type.is_native() {
+        MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: coins(
+                schedule.amount_per_swap.u128(),
+                schedule.asset_in.get_denom(),
+            ),
+        }
+    } else {
+        MessageInfo {
+            sender: Addr::unchecked(schedule.asset_in.get_denom()),
+            funds: vec![],
+        }
+    };
+    let sender = CrossChainUser {
+        address: schedule.owner.to_string(),
+        chain_uid: STATE.load(deps.storage)?.chain_uid,
+    };
+
+    let response = execute_swap_request(
+        deps,
+        info,
+        env,
+        sender,
+        schedule.asset_in,
+        schedule.asset_out,
+        schedule.amount_per_swap,
+        schedule.min_amount_out,
+        schedule.swaps,
+        schedule.timeout,
+        schedule.cross_chain_addresses,
+        None,
+    )?;
+    Ok(response
+        .add_attribute("dca_schedule_id", schedule_id.to_string())
+        .add_attribute("dca_executed_swaps", schedule.executed_swaps.to_string()))
+}
+
+pub fn execute_cancel_dca_schedule(
+    deps: &mut DepsMut,
+    info: MessageInfo,
+    schedule_id: u64,
+) -> Result<Response, ContractError> {
+    let schedule = DCA_SCHEDULES
+        .load(deps.storage, schedule_id)
+        .map_err(|_err| ContractError::new("Dca schedule doesn't exist"))?;
+    ensure!(
+        schedule.owner == info.sender,
+        ContractError::Unauthorized {}
+    );
+    DCA_SCHEDULES.remove(deps.storage, schedule_id);
+
+    let refund_amount = schedule.remaining_amount()?;
+    let refund_msg =
+        schedule
+            .asset_in
+            .create_transfer_msg(refund_amount, schedule.owner.to_string(), None)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel_dca_schedule")
+        .add_attribute("schedule_id", schedule_id.to_string())
+        .add_attribute("refund_to", schedule.owner)
+        .add_attribute("refund_amount", refund_amount)
+        .add_message(refund_msg))
+}
+
 /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
 ///
 /// * **cw20_msg** is the CW20 message that has to be processed.
@@ -680,11 +1208,44 @@ pub fn receive_cw20(
                 partner_fee,
             )
         }
+        Cw20HookMsg::CreateDcaSchedule {
+            asset_in,
+            asset_out,
+            amount_per_swap,
+            min_amount_out,
+            total_swaps,
+            interval,
+            swaps,
+            timeout,
+            cross_chain_addresses,
+        } => {
+            ensure!(
+                info.sender == asset_in.get_denom(),
+                ContractError::AssetDoesNotExist {}
+            );
+            let owner = deps.api.addr_validate(&sender.address)?;
+            execute_create_dca_schedule(
+                &mut deps,
+                env,
+                owner,
+                cw20_msg.amount,
+                asset_in,
+                asset_out,
+                amount_per_swap,
+                min_amount_out,
+                total_swaps,
+                interval,
+                swaps,
+                timeout,
+                cross_chain_addresses,
+            )
+        }
         Cw20HookMsg::RemoveLiquidity {
             pair,
             lp_allocation,
             timeout,
             cross_chain_addresses,
+            keep_in_virtual_balance,
         } => remove_liquidity_request(
             &mut deps,
             info,
@@ -694,6 +1255,7 @@ pub fn receive_cw20(
             lp_allocation,
             timeout,
             cross_chain_addresses,
+            keep_in_virtual_balance.unwrap_or_default(),
         ),
 
         _ => Err(ContractError::NotImplemented {}),
@@ -807,6 +1369,164 @@ pub fn execute_withdraw_virtual_balance(
         .add_submessage(withdraw_msg))
 }
 
+pub fn execute_request_multi_asset_pool_creation(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tokens: Vec<Token>,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let tokens = sort_pool_tokens(tokens)?;
+    let state = STATE.load(deps.storage)?;
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    let timeout = get_timeout(timeout)?;
+
+    // Same creation fee as pair pools, sent to the treasury on success and refunded on failure
+    if let Some(fee) = POOL_CREATION_FEE.may_load(deps.storage)? {
+        let paid = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == fee.amount.denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+        ensure!(
+            paid >= fee.amount.amount,
+            ContractError::InsufficientDeposit {}
+        );
+        PENDING_MULTI_ASSET_POOL_FEES.save(
+            deps.storage,
+            (info.sender.clone(), tx_id.clone()),
+            &fee,
+        )?;
+    }
+
+    let pool_create_msg = ChainIbcExecuteMsg::RequestMultiAssetPoolCreation {
+        sender,
+        tx_id: tx_id.clone(),
+        tokens,
+    }
+    .to_msg(
+        deps,
+        &env,
+        state.router_contract,
+        state.chain_uid,
+        state.is_native,
+        channel,
+        timeout,
+    )?;
+
+    Ok(Response::new()
+        .add_event(tx_event(&tx_id, info.sender.as_str(), TxType::PoolCreation))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "request_multi_asset_pool_creation")
+        .add_submessage(pool_create_msg))
+}
+
+pub fn add_multi_asset_liquidity_request(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vlp_address: String,
+    liquidity: Vec<(Token, Uint128)>,
+    min_lp_allocation: Uint128,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        !liquidity.is_empty() && liquidity.iter().all(|(_, amount)| !amount.is_zero()),
+        ContractError::ZeroAssetAmount {}
+    );
+    let state = STATE.load(deps.storage)?;
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    let timeout = get_timeout(timeout)?;
+
+    let add_liq_msg =
+        ChainIbcExecuteMsg::AddMultiAssetLiquidity(ChainIbcAddMultiAssetLiquidityExecuteMsg {
+            sender,
+            vlp_address: vlp_address.clone(),
+            liquidity,
+            min_lp_allocation,
+            tx_id: tx_id.clone(),
+        })
+        .to_msg(
+            deps,
+            &env,
+            state.router_contract,
+            state.chain_uid,
+            state.is_native,
+            channel,
+            timeout,
+        )?;
+
+    Ok(Response::new()
+        .add_event(tx_event(&tx_id, info.sender.as_str(), TxType::AddLiquidity))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "add_multi_asset_liquidity_request")
+        .add_attribute("vlp_address", vlp_address)
+        .add_submessage(add_liq_msg))
+}
+
+pub fn remove_multi_asset_liquidity_request(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vlp_address: String,
+    lp_allocation: Uint128,
+    timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure!(!lp_allocation.is_zero(), ContractError::ZeroAssetAmount {});
+    let state = STATE.load(deps.storage)?;
+
+    let channel = HUB_CHANNEL.load(deps.storage)?;
+    let sender = CrossChainUser {
+        address: info.sender.to_string(),
+        chain_uid: state.chain_uid.clone(),
+    };
+    let tx_id = generate_tx(deps.branch(), &env, &sender)?;
+    let timeout = get_timeout(timeout)?;
+
+    let remove_liq_msg = ChainIbcExecuteMsg::RemoveMultiAssetLiquidity(
+        ChainIbcRemoveMultiAssetLiquidityExecuteMsg {
+            sender,
+            vlp_address: vlp_address.clone(),
+            lp_allocation,
+            tx_id: tx_id.clone(),
+        },
+    )
+    .to_msg(
+        deps,
+        &env,
+        state.router_contract,
+        state.chain_uid,
+        state.is_native,
+        channel,
+        timeout,
+    )?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            info.sender.as_str(),
+            TxType::RemoveLiquidity,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "remove_multi_asset_liquidity_request")
+        .add_attribute("vlp_address", vlp_address)
+        .add_attribute("lp_allocation", lp_allocation)
+        .add_submessage(remove_liq_msg))
+}
+
 pub fn execute_native_receive_callback(
     deps: DepsMut,
     env: Env,
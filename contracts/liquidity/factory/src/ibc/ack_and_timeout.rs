@@ -1,19 +1,22 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Binary, CosmosMsg, DepsMut, Env, IbcAcknowledgement,
+    from_json, to_json_binary, BankMsg, Binary, CosmosMsg, DepsMut, Env, IbcAcknowledgement,
     IbcBasicResponse, IbcPacketAckMsg, IbcPacketTimeoutMsg, ReplyOn, Response, StdError, StdResult,
     SubMsg, Uint128, WasmMsg,
 };
 use euclid::{
     error::ContractError,
     events::swap_event,
-    liquidity::{AddLiquidityResponse, RemoveLiquidityResponse},
+    liquidity::{
+        AddLiquidityLegResponse, AddLiquidityRequest, AddLiquidityResponse,
+        MultiAssetLiquidityResponse, RemoveLiquidityResponse,
+    },
     msgs::{
         cw20::ExecuteMsg as Cw20ExecuteMsg, escrow::InstantiateMsg as EscrowInstantiateMsg,
         factory::ExecuteMsg,
     },
-    pool::{EscrowCreationResponse, PoolCreationResponse},
+    pool::{EscrowCreationResponse, EscrowRetirementResponse, PoolCreationResponse},
     swap::{SwapResponse, WithdrawResponse},
     token::Token,
 };
@@ -22,9 +25,10 @@ use euclid_ibc::{ack::AcknowledgementMsg, msg::ChainIbcExecuteMsg};
 use crate::{
     reply::{CW20_INSTANTIATE_REPLY_ID, ESCROW_INSTANTIATE_REPLY_ID, IBC_ACK_AND_TIMEOUT_REPLY_ID},
     state::{
-        PAIR_TO_VLP, PENDING_ADD_LIQUIDITY, PENDING_ESCROW_REQUESTS, PENDING_POOL_REQUESTS,
-        PENDING_REMOVE_LIQUIDITY, PENDING_SWAPS, STATE, TOKEN_TO_ESCROW, VLP_TO_CW20,
-        VLP_TO_LP_SHARES,
+        PAIR_TO_VLP, PENDING_ADD_LIQUIDITY, PENDING_ADD_LIQUIDITY_LEGS, PENDING_ESCROW_REQUESTS,
+        PENDING_MULTI_ASSET_POOL_FEES, PENDING_POOL_LIQUIDITY, PENDING_POOL_REQUESTS,
+        PENDING_REMOVE_LIQUIDITY, PENDING_SWAPS, RETIRING_TOKENS, STATE, TOKEN_TO_ESCROW,
+        VLP_TO_CW20, VLP_TO_LP_SHARES,
     },
 };
 
@@ -85,11 +89,49 @@ pub fn reusable_internal_ack_call(
             ack_escrow_creation(deps, env, sender.address, res, tx_id, is_native)
         }
 
+        ChainIbcExecuteMsg::RequestEscrowRetirement { tx_id, token, .. } => {
+            let res: AcknowledgementMsg<EscrowRetirementResponse> = from_json(ack)?;
+
+            ack_escrow_retirement(deps, token, res, tx_id, is_native)
+        }
+
         ChainIbcExecuteMsg::AddLiquidity { tx_id, sender, .. } => {
             // Process acknowledgment for add liquidity
             let res: AcknowledgementMsg<AddLiquidityResponse> = from_json(ack)?;
             ack_add_liquidity(deps, res, sender.address, tx_id, is_native)
         }
+        ChainIbcExecuteMsg::AddLiquidityLeg(msg) => {
+            let res: AcknowledgementMsg<AddLiquidityLegResponse> = from_json(ack)?;
+            ack_add_liquidity_leg(deps, res, msg.sender.address, msg.tx_id, is_native)
+        }
+        ChainIbcExecuteMsg::AddLiquidityFromVirtualBalance(msg) => {
+            let res: AcknowledgementMsg<AddLiquidityResponse> = from_json(ack)?;
+            ack_add_liquidity_from_virtual_balance(res, msg.sender.address, msg.tx_id, is_native)
+        }
+        ChainIbcExecuteMsg::RequestMultiAssetPoolCreation { tx_id, sender, .. } => {
+            let res: AcknowledgementMsg<PoolCreationResponse> = from_json(ack)?;
+            ack_multi_asset_pool_creation(deps, res, sender.address, tx_id, is_native)
+        }
+        ChainIbcExecuteMsg::AddMultiAssetLiquidity(msg) => {
+            let res: AcknowledgementMsg<MultiAssetLiquidityResponse> = from_json(ack)?;
+            ack_multi_asset_liquidity(
+                res,
+                "add_multi_asset_liquidity",
+                msg.sender.address,
+                msg.tx_id,
+                is_native,
+            )
+        }
+        ChainIbcExecuteMsg::RemoveMultiAssetLiquidity(msg) => {
+            let res: AcknowledgementMsg<MultiAssetLiquidityResponse> = from_json(ack)?;
+            ack_multi_asset_liquidity(
+                res,
+                "remove_multi_asset_liquidity",
+                msg.sender.address,
+                msg.tx_id,
+                is_native,
+            )
+        }
         ChainIbcExecuteMsg::RemoveLiquidity(msg) => {
             // Process acknowledgment for add liquidity
             let res: AcknowledgementMsg<RemoveLiquidityResponse> = from_json(ack)?;
@@ -182,11 +224,51 @@ fn ack_pool_creation(
                 existing_req.pair_info.get_pair()?.get_tupple(),
                 &data.vlp_contract.clone(),
             )?;
+            let mut msgs: Vec<CosmosMsg> = Vec::new();
+            if let Some(fee) = existing_req.creation_fee.clone() {
+                msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: fee.treasury,
+                    amount: vec![fee.amount],
+                }));
+            }
+            // Escrows and the lp token are instantiated below, the initial liquidity is settled in the lp token reply
+            if let (Some(liquidity), Some(mint_lp_tokens)) =
+                (existing_req.initial_liquidity.clone(), data.mint_lp_tokens)
+            {
+                VLP_TO_LP_SHARES.save(deps.storage, data.vlp_contract.clone(), &mint_lp_tokens)?;
+                PENDING_POOL_LIQUIDITY.save(
+                    deps.storage,
+                    data.vlp_contract.clone(),
+                    &(
+                        AddLiquidityRequest {
+                            sender: existing_req.sender.clone(),
+                            tx_id: tx_id.clone(),
+                            token_1_liquidity: liquidity.token_1_liquidity,
+                            token_2_liquidity: liquidity.token_2_liquidity,
+                            pair_info: existing_req.pair_info.clone(),
+                        },
+                        mint_lp_tokens,
+                    ),
+                )?;
+            } else if let Some(liquidity) = existing_req.initial_liquidity.clone() {
+                // Hub created the pool without minting lp tokens, so the liquidity goes back to the sender
+                msgs.push(existing_req.pair_info.token_1.create_transfer_msg(
+                    liquidity.token_1_liquidity,
+                    existing_req.sender.clone(),
+                    None,
+                )?);
+                msgs.push(existing_req.pair_info.token_2.create_transfer_msg(
+                    liquidity.token_2_liquidity,
+                    existing_req.sender.clone(),
+                    None,
+                )?);
+            }
             // Prepare response
             let mut res = Response::new()
                 .add_attribute("tx_id", tx_id)
                 .add_attribute("method", "pool_creation")
-                .add_attribute("vlp", data.vlp_contract.clone());
+                .add_attribute("vlp", data.vlp_contract.clone())
+                .add_messages(msgs);
             // Collects PairInfo into a vector of Token Info for easy iteration
             let tokens = existing_req.pair_info.get_vec_token_info();
             for token in tokens {
@@ -246,10 +328,31 @@ fn ack_pool_creation(
             if is_native {
                 return Err(ContractError::new(&err));
             }
+            // Refund the initial liquidity and creation fee held for the pool
+            let mut msgs: Vec<CosmosMsg> = Vec::new();
+            if let Some(fee) = existing_req.creation_fee {
+                msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: existing_req.sender.clone(),
+                    amount: vec![fee.amount],
+                }));
+            }
+            if let Some(liquidity) = existing_req.initial_liquidity {
+                msgs.push(existing_req.pair_info.token_1.create_transfer_msg(
+                    liquidity.token_1_liquidity,
+                    existing_req.sender.clone(),
+                    None,
+                )?);
+                msgs.push(existing_req.pair_info.token_2.create_transfer_msg(
+                    liquidity.token_2_liquidity,
+                    existing_req.sender,
+                    None,
+                )?);
+            }
             Ok(Response::new()
                 .add_attribute("tx_id", tx_id)
                 .add_attribute("method", "reject_pool_request")
-                .add_attribute("error", err.clone()))
+                .add_attribute("error", err.clone())
+                .add_messages(msgs))
         }
     }
 }
@@ -313,6 +416,45 @@ fn ack_escrow_creation(
     }
 }
 
+fn ack_escrow_retirement(
+    deps: DepsMut,
+    token: Token,
+    res: AcknowledgementMsg<EscrowRetirementResponse>,
+    tx_id: String,
+    is_native: bool,
+) -> Result<Response, ContractError> {
+    match res {
+        AcknowledgementMsg::Ok(data) => {
+            let escrow_address = TOKEN_TO_ESCROW.load(deps.storage, token.clone())?;
+            if data.retired {
+                // Hub balance is fully withdrawn, remove the escrow so the token can no longer be used
+                TOKEN_TO_ESCROW.remove(deps.storage, token.clone());
+                RETIRING_TOKENS.remove(deps.storage, token.clone());
+            } else {
+                RETIRING_TOKENS.save(deps.storage, token.clone(), &true)?;
+            }
+
+            Ok(Response::new()
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("method", "escrow_retirement")
+                .add_attribute("token", token.to_string())
+                .add_attribute("escrow", escrow_address)
+                .add_attribute("remaining_balance", data.remaining_balance)
+                .add_attribute("retired", data.retired.to_string()))
+        }
+
+        AcknowledgementMsg::Error(err) => {
+            if is_native {
+                return Err(ContractError::new(&err));
+            }
+            Ok(Response::new()
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("method", "reject_escrow_retirement")
+                .add_attribute("error", err.clone()))
+        }
+    }
+}
+
 // Function to process add liquidity acknowledgment
 fn ack_add_liquidity(
     deps: DepsMut,
@@ -405,6 +547,163 @@ fn ack_add_liquidity(
     }
 }
 
+fn ack_add_liquidity_leg(
+    deps: DepsMut,
+    res: AcknowledgementMsg<AddLiquidityLegResponse>,
+    sender: String,
+    tx_id: String,
+    is_native: bool,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let req_key = (sender.clone(), tx_id.clone());
+    let leg_info = PENDING_ADD_LIQUIDITY_LEGS.load(deps.storage, req_key.clone())?;
+    PENDING_ADD_LIQUIDITY_LEGS.remove(deps.storage, req_key);
+    match res {
+        AcknowledgementMsg::Ok(data) => {
+            // Router accounted the leg either way, lp tokens are minted separately on the
+            // recipient chain once the intent completes
+            let escrow_contract =
+                TOKEN_TO_ESCROW.load(deps.storage, leg_info.token.token.clone())?;
+            let send_msg = leg_info
+                .token
+                .create_escrow_msg(leg_info.amount, escrow_contract)?;
+
+            Ok(Response::new()
+                .add_attribute("method", "ack_add_liquidity_leg")
+                .add_attribute("intent_id", data.intent_id)
+                .add_attribute("completed", data.completed.to_string())
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("sender", sender)
+                .add_message(send_msg))
+        }
+        AcknowledgementMsg::Error(err) => {
+            // Its a native call so you can return error to reject complete execution call
+            if is_native {
+                return Err(ContractError::new(&err));
+            }
+            let refund_msg =
+                leg_info
+                    .token
+                    .create_transfer_msg(leg_info.amount, sender.to_string(), None)?;
+
+            Ok(Response::new()
+                .add_attribute("method", "liquidity_leg_tx_err_refund")
+                .add_attribute("intent_id", leg_info.intent_id)
+                .add_attribute("sender", sender)
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("error", err)
+                .add_message(refund_msg))
+        }
+    }
+}
+
+fn ack_add_liquidity_from_virtual_balance(
+    res: AcknowledgementMsg<AddLiquidityResponse>,
+    sender: String,
+    tx_id: String,
+    is_native: bool,
+) -> Result<Response, ContractError> {
+    // No funds are held for this request, lp tokens are minted by a separate message from the hub
+    match res {
+        AcknowledgementMsg::Ok(data) => Ok(Response::new()
+            .add_attribute("method", "ack_add_liquidity_from_virtual_balance")
+            .add_attribute("vlp_address", data.vlp_address)
+            .add_attribute("mint_lp_tokens", data.mint_lp_tokens)
+            .add_attribute("tx_id", tx_id)
+            .add_attribute("sender", sender)),
+        AcknowledgementMsg::Error(err) => {
+            if is_native {
+                return Err(ContractError::new(&err));
+            }
+            Ok(Response::new()
+                .add_attribute("method", "add_liquidity_from_virtual_balance_tx_err")
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("sender", sender)
+                .add_attribute("error", err))
+        }
+    }
+}
+
+fn ack_multi_asset_pool_creation(
+    deps: DepsMut,
+    res: AcknowledgementMsg<PoolCreationResponse>,
+    sender: String,
+    tx_id: String,
+    is_native: bool,
+) -> Result<Response, ContractError> {
+    let fee_key = (deps.api.addr_validate(&sender)?, tx_id.clone());
+    let creation_fee = PENDING_MULTI_ASSET_POOL_FEES.may_load(deps.storage, fee_key.clone())?;
+    PENDING_MULTI_ASSET_POOL_FEES.remove(deps.storage, fee_key);
+
+    // Multi asset pools live only on the hub, there is no lp token or pair to store here
+    match res {
+        AcknowledgementMsg::Ok(data) => {
+            let fee_msgs: Vec<CosmosMsg> = creation_fee
+                .into_iter()
+                .map(|fee| {
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: fee.treasury,
+                        amount: vec![fee.amount],
+                    })
+                })
+                .collect();
+            Ok(Response::new()
+                .add_attribute("method", "ack_multi_asset_pool_creation")
+                .add_attribute("vlp_address", data.vlp_contract)
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("sender", sender)
+                .add_messages(fee_msgs))
+        }
+        AcknowledgementMsg::Error(err) => {
+            if is_native {
+                return Err(ContractError::new(&err));
+            }
+            let refund_msgs: Vec<CosmosMsg> = creation_fee
+                .into_iter()
+                .map(|fee| {
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: sender.clone(),
+                        amount: vec![fee.amount],
+                    })
+                })
+                .collect();
+            Ok(Response::new()
+                .add_attribute("method", "multi_asset_pool_creation_tx_err")
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("sender", sender)
+                .add_attribute("error", err)
+                .add_messages(refund_msgs))
+        }
+    }
+}
+
+fn ack_multi_asset_liquidity(
+    res: AcknowledgementMsg<MultiAssetLiquidityResponse>,
+    method: &str,
+    sender: String,
+    tx_id: String,
+    is_native: bool,
+) -> Result<Response, ContractError> {
+    // Liquidity moves between virtual balances on the hub, no funds are held for this request
+    match res {
+        AcknowledgementMsg::Ok(data) => Ok(Response::new()
+            .add_attribute("method", format!("ack_{method}"))
+            .add_attribute("vlp_address", data.vlp_address)
+            .add_attribute("tx_id", tx_id)
+            .add_attribute("sender", sender)),
+        AcknowledgementMsg::Error(err) => {
+            if is_native {
+                return Err(ContractError::new(&err));
+            }
+            Ok(Response::new()
+                .add_attribute("method", format!("{method}_tx_err"))
+                .add_attribute("tx_id", tx_id)
+                .add_attribute("sender", sender)
+                .add_attribute("error", err))
+        }
+    }
+}
+
 fn ack_remove_liquidity(
     deps: DepsMut,
     res: AcknowledgementMsg<RemoveLiquidityResponse>,
@@ -9,10 +9,15 @@ use euclid::{
     error::ContractError,
     events::{tx_event, TxType},
     msgs::{
+        cw20::ExecuteMsg as Cw20ExecuteMsg,
         escrow::ExecuteMsg as EscrowExecuteMsg,
-        factory::{ExecuteMsg, RegisterFactoryResponse, ReleaseEscrowResponse},
+        factory::{
+            DelistPoolResponse, ExecuteMsg, MintLpTokensResponse, RegisterFactoryResponse,
+            ReleaseEscrowResponse,
+        },
     },
-    token::Token,
+    pool::EscrowRetirementResponse,
+    token::{Pair, Token},
 };
 use euclid_ibc::{
     ack::{make_ack_fail, AcknowledgementMsg},
@@ -21,7 +26,10 @@ use euclid_ibc::{
 
 use crate::{
     reply::IBC_RECEIVE_REPLY_ID,
-    state::{HUB_CHANNEL, STATE, TOKEN_TO_ESCROW},
+    state::{
+        HUB_CHANNEL, PAIR_TO_VLP, RETIRING_TOKENS, STATE, TOKEN_TO_ESCROW, VLP_TO_CW20,
+        VLP_TO_LP_SHARES,
+    },
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -95,6 +103,24 @@ pub fn reusable_internal_call(
         HubIbcExecuteMsg::UpdateFactoryChannel { chain_uid, tx_id } => {
             execute_update_factory_channel(deps, env, chain_uid, tx_id)
         }
+        HubIbcExecuteMsg::DelistPool {
+            chain_uid,
+            pair,
+            vlp_address,
+            tx_id,
+        } => execute_delist_pool(deps, env, chain_uid, pair, vlp_address, tx_id),
+        HubIbcExecuteMsg::MintLpTokens {
+            chain_uid,
+            recipient,
+            vlp_address,
+            amount,
+            tx_id,
+        } => execute_mint_lp_tokens(deps, env, chain_uid, recipient, vlp_address, amount, tx_id),
+        HubIbcExecuteMsg::RetireEscrow {
+            chain_uid,
+            token,
+            tx_id,
+        } => execute_retire_escrow(deps, chain_uid, token, tx_id),
     }
 }
 
@@ -161,6 +187,148 @@ fn execute_update_factory_channel(
         .add_attribute("router", state.router_contract)
         .set_data(ack))
 }
+fn execute_delist_pool(
+    deps: DepsMut,
+    env: Env,
+    chain_uid: ChainUid,
+    pair: Pair,
+    vlp_address: String,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        state.chain_uid == chain_uid,
+        ContractError::new("Chain UID mismatch")
+    );
+
+    // Factories that never had this pool have nothing to remove
+    let pair_key = PAIR_TO_VLP.key(pair.get_tupple());
+    if let Some(vlp) = pair_key.may_load(deps.storage)? {
+        ensure!(
+            vlp == vlp_address,
+            ContractError::new("VLP address mismatch")
+        );
+        pair_key.remove(deps.storage);
+    }
+
+    let ack_msg = DelistPoolResponse {
+        factory_address: env.contract.address.to_string(),
+        chain_id: env.block.chain_id,
+        pair,
+        vlp_address: vlp_address.clone(),
+    };
+
+    let ack = to_json_binary(&AcknowledgementMsg::Ok(ack_msg))?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &state.router_contract,
+            TxType::PoolDelisting,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "delist_pool")
+        .add_attribute("vlp_address", vlp_address)
+        .set_data(ack))
+}
+
+fn execute_retire_escrow(
+    deps: DepsMut,
+    chain_uid: ChainUid,
+    token: Token,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        state.chain_uid == chain_uid,
+        ContractError::new("Chain UID mismatch")
+    );
+    ensure!(
+        RETIRING_TOKENS.has(deps.storage, token.clone()),
+        ContractError::new("Escrow is not retiring")
+    );
+
+    // Hub balance is fully withdrawn, remove the escrow so the token can no longer be used
+    TOKEN_TO_ESCROW.remove(deps.storage, token.clone());
+    RETIRING_TOKENS.remove(deps.storage, token.clone());
+
+    let ack_msg = EscrowRetirementResponse {
+        token: token.clone(),
+        remaining_balance: Uint128::zero(),
+        retired: true,
+    };
+    let ack = to_json_binary(&AcknowledgementMsg::Ok(ack_msg))?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &state.router_contract,
+            TxType::EscrowRetirement,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "retire_escrow")
+        .add_attribute("token", token.to_string())
+        .set_data(ack))
+}
+
+fn execute_mint_lp_tokens(
+    deps: DepsMut,
+    env: Env,
+    chain_uid: ChainUid,
+    recipient: String,
+    vlp_address: String,
+    amount: Uint128,
+    tx_id: String,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    ensure!(
+        state.chain_uid == chain_uid,
+        ContractError::new("Chain UID mismatch")
+    );
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let cw20_address = VLP_TO_CW20.load(deps.storage, vlp_address.clone())?;
+
+    let shares = VLP_TO_LP_SHARES
+        .may_load(deps.storage, vlp_address.clone())?
+        .unwrap_or_default();
+    VLP_TO_LP_SHARES.save(
+        deps.storage,
+        vlp_address.clone(),
+        &shares.checked_add(amount)?,
+    )?;
+
+    let cw20_mint_msg = WasmMsg::Execute {
+        contract_addr: cw20_address.into_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    let ack_msg = MintLpTokensResponse {
+        factory_address: env.contract.address.to_string(),
+        chain_id: env.block.chain_id,
+        vlp_address: vlp_address.clone(),
+        recipient: recipient.to_string(),
+        amount,
+    };
+    let ack = to_json_binary(&AcknowledgementMsg::Ok(ack_msg))?;
+
+    Ok(Response::new()
+        .add_event(tx_event(
+            &tx_id,
+            &state.router_contract,
+            TxType::AddLiquidity,
+        ))
+        .add_attribute("tx_id", tx_id)
+        .add_attribute("method", "mint_lp_tokens")
+        .add_attribute("vlp_address", vlp_address)
+        .add_attribute("recipient", recipient)
+        .add_message(cw20_mint_msg)
+        .set_data(ack))
+}
+
 fn execute_release_escrow(
     deps: DepsMut,
     env: Env,
@@ -1,9 +1,57 @@
-use cosmwasm_std::{entry_point, DepsMut, Env, Response};
-use euclid::{error::ContractError, msgs::vlp::MigrateMsg};
+use cosmwasm_std::{
+    entry_point, from_json, Addr, DepsMut, Env, Order, Response, StdResult, Storage,
+};
+use cw_storage_plus::KeyDeserialize;
+use euclid::{
+    error::ContractError,
+    migrate::ensure_migration_version,
+    msgs::factory::MigrateMsg,
+    pool::{EscrowCreateRequest, PoolCreateRequest},
+};
+
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    state::{PENDING_ESCROW_REQUESTS, PENDING_POOL_REQUESTS},
+};
 
 /// This is the migrate entry point for the contract.
-/// Currently, it does not perform any migration logic and simply returns an empty response.
+/// It refuses migrations from a different contract or a newer version and moves state that changed storage namespace.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_migration_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &[])?;
+
+    let moved_escrow_requests = migrate_pending_escrow_requests(deps.storage)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_contract", previous.contract)
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("new_version", CONTRACT_VERSION)
+        .add_attribute("moved_escrow_requests", moved_escrow_requests.to_string()))
+}
+
+// Pending escrow requests used to share the pending pool requests namespace, move them to their own map
+pub fn migrate_pending_escrow_requests(storage: &mut dyn Storage) -> Result<u64, ContractError> {
+    // Raw keys so that values of either type don't fail deserialization
+    let keys = PENDING_POOL_REQUESTS
+        .keys_raw(storage, None, None, Order::Ascending)
+        .map(<(Addr, String)>::from_vec)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut moved = 0;
+    for key in keys {
+        let path = PENDING_POOL_REQUESTS.key(key.clone());
+        let Some(raw) = storage.get(&path) else {
+            continue;
+        };
+        if from_json::<PoolCreateRequest>(&raw).is_ok() {
+            continue;
+        }
+        if let Ok(request) = from_json::<EscrowCreateRequest>(&raw) {
+            PENDING_ESCROW_REQUESTS.save(storage, key, &request)?;
+            storage.remove(&path);
+            moved += 1;
+        }
+    }
+    Ok(moved)
 }
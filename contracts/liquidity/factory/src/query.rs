@@ -3,18 +3,19 @@ use cw_storage_plus::Bound;
 use euclid::{
     error::ContractError,
     msgs::factory::{
-        AllPoolsResponse, AllTokensResponse, GetEscrowResponse, GetLPTokenResponse,
-        GetPendingLiquidityResponse, GetPendingRemoveLiquidityResponse, GetPendingSwapsResponse,
-        GetVlpResponse, PartnerFeesCollectedPerDenomResponse, PartnerFeesCollectedResponse,
-        PoolVlpResponse, StateResponse,
+        AllPoolsResponse, AllTokensResponse, DcaScheduleResponse, DcaSchedulesResponse,
+        GetEscrowResponse, GetLPTokenResponse, GetPendingLiquidityResponse,
+        GetPendingRemoveLiquidityResponse, GetPendingSwapsResponse, GetVlpResponse,
+        PartnerFeesCollectedPerDenomResponse, PartnerFeesCollectedResponse,
+        PoolCreationFeeResponse, PoolVlpResponse, StateResponse,
     },
     token::{Pair, Token},
     utils::Pagination,
 };
 
 use crate::state::{
-    HUB_CHANNEL, PAIR_TO_VLP, PENDING_ADD_LIQUIDITY, PENDING_REMOVE_LIQUIDITY, PENDING_SWAPS,
-    STATE, TOKEN_TO_ESCROW, VLP_TO_CW20,
+    DCA_SCHEDULES, HUB_CHANNEL, PAIR_TO_VLP, PENDING_ADD_LIQUIDITY, PENDING_REMOVE_LIQUIDITY,
+    PENDING_SWAPS, POOL_CREATION_FEE, RETIRING_TOKENS, STATE, TOKEN_TO_ESCROW, VLP_TO_CW20,
 };
 
 // Returns the VLP address
@@ -31,6 +32,12 @@ pub fn get_partner_fees_collected(deps: Deps) -> Result<Binary, ContractError> {
     })?)
 }
 
+pub fn get_pool_creation_fee(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_json_binary(&PoolCreationFeeResponse {
+        fee: POOL_CREATION_FEE.may_load(deps.storage)?,
+    })?)
+}
+
 pub fn get_partner_fees_collected_per_denom(
     deps: Deps,
     denom: String,
@@ -50,10 +57,12 @@ pub fn get_lp_token_address(deps: Deps, vlp: String) -> Result<Binary, ContractE
 
 // Returns the Escrow address alongside allowed denoms if available
 pub fn get_escrow(deps: Deps, token_id: String) -> Result<Binary, ContractError> {
-    let escrow_address = TOKEN_TO_ESCROW.may_load(deps.storage, Token::create(token_id)?)?;
+    let token = Token::create(token_id)?;
+    let escrow_address = TOKEN_TO_ESCROW.may_load(deps.storage, token.clone())?;
     let mut response = GetEscrowResponse {
         escrow_address: escrow_address.clone(),
         denoms: vec![],
+        retiring: RETIRING_TOKENS.has(deps.storage, token),
     };
     if escrow_address.is_some() {
         let denoms: euclid::msgs::escrow::AllowedDenomsResponse = deps.querier.query_wasm_smart(
@@ -103,6 +112,16 @@ pub fn query_all_tokens(deps: Deps) -> Result<Binary, ContractError> {
     to_json_binary(&AllTokensResponse { tokens }).map_err(Into::into)
 }
 
+// Returns the tokens whose escrow is being retired
+pub fn query_retiring_tokens(deps: Deps) -> Result<Binary, ContractError> {
+    let tokens = RETIRING_TOKENS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .flatten()
+        .collect();
+
+    to_json_binary(&AllTokensResponse { tokens }).map_err(Into::into)
+}
+
 // Returns the pending swaps for this pair with pagination
 pub fn pending_swaps(
     deps: Deps,
@@ -124,6 +143,39 @@ pub fn pending_swaps(
     Ok(to_json_binary(&GetPendingSwapsResponse { pending_swaps })?)
 }
 
+pub fn get_dca_schedule(deps: Deps, schedule_id: u64) -> Result<Binary, ContractError> {
+    let schedule = DCA_SCHEDULES
+        .load(deps.storage, schedule_id)
+        .map_err(|_err| ContractError::new("Dca schedule doesn't exist"))?;
+    Ok(to_json_binary(&DcaScheduleResponse {
+        schedule_id,
+        schedule,
+    })?)
+}
+
+// Returns the active dca schedules with pagination
+pub fn get_dca_schedules(deps: Deps, pagination: Pagination<u64>) -> Result<Binary, ContractError> {
+    let min = pagination.min.map(Bound::inclusive);
+    let max = pagination.max.map(Bound::inclusive);
+
+    let schedules: Result<_, ContractError> = DCA_SCHEDULES
+        .range(deps.storage, min, max, Order::Ascending)
+        .skip(pagination.skip.unwrap_or(0) as usize)
+        .take(pagination.limit.unwrap_or(10) as usize)
+        .map(|v| {
+            let (schedule_id, schedule) = v?;
+            Ok(DcaScheduleResponse {
+                schedule_id,
+                schedule,
+            })
+        })
+        .collect();
+
+    Ok(to_json_binary(&DcaSchedulesResponse {
+        schedules: schedules?,
+    })?)
+}
+
 // Returns the pending liquidity transactions for a user with pagination
 pub fn pending_liquidity(
     deps: Deps,
@@ -1,10 +1,12 @@
 use crate::{
     ibc,
-    state::{TOKEN_TO_ESCROW, VLP_TO_CW20},
+    state::{PENDING_POOL_LIQUIDITY, TOKEN_TO_ESCROW, VLP_TO_CW20},
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, DepsMut, Env, Reply, Response, SubMsgResult, WasmMsg,
 };
-use cosmwasm_std::{from_json, DepsMut, Env, Reply, Response, SubMsgResult};
 use cw_utils::{parse_execute_response_data, parse_reply_instantiate_data};
-use euclid::error::ContractError;
+use euclid::{error::ContractError, msgs::cw20::ExecuteMsg as Cw20ExecuteMsg};
 use euclid_ibc::{ack::make_ack_fail, msg::CHAIN_IBC_EXECUTE_MSG_QUEUE};
 
 pub const ESCROW_INSTANTIATE_REPLY_ID: u64 = 1;
@@ -47,10 +49,43 @@ pub fn on_cw20_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response,
             let cw20_data: euclid::msgs::escrow::Cw20InstantiateResponse =
                 from_json(instantiate_data.data.unwrap_or_default())?;
 
-            VLP_TO_CW20.save(deps.storage, cw20_data.vlp, &cw20_address)?;
-            Ok(Response::new()
+            VLP_TO_CW20.save(deps.storage, cw20_data.vlp.clone(), &cw20_address)?;
+            let mut response = Response::new()
                 .add_attribute("action", "reply_pool_instantiate")
-                .add_attribute("cw20", cw20_address))
+                .add_attribute("cw20", cw20_address.clone());
+
+            // Pool was created with initial liquidity, escrows of new tokens were instantiated before the lp token
+            if let Some((liquidity_info, mint_lp_tokens)) =
+                PENDING_POOL_LIQUIDITY.may_load(deps.storage, cw20_data.vlp.clone())?
+            {
+                PENDING_POOL_LIQUIDITY.remove(deps.storage, cw20_data.vlp);
+                for (token_info, liquidity) in [
+                    (
+                        liquidity_info.pair_info.token_1,
+                        liquidity_info.token_1_liquidity,
+                    ),
+                    (
+                        liquidity_info.pair_info.token_2,
+                        liquidity_info.token_2_liquidity,
+                    ),
+                ] {
+                    let escrow_contract =
+                        TOKEN_TO_ESCROW.load(deps.storage, token_info.token.clone())?;
+                    response = response
+                        .add_message(token_info.create_escrow_msg(liquidity, escrow_contract)?);
+                }
+                response = response
+                    .add_message(WasmMsg::Execute {
+                        contract_addr: cw20_address.into_string(),
+                        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                            recipient: liquidity_info.sender,
+                            amount: mint_lp_tokens,
+                        })?,
+                        funds: vec![],
+                    })
+                    .add_attribute("initial_liquidity_tx_id", liquidity_info.tx_id);
+            }
+            Ok(response)
         }
     }
 }
@@ -4,9 +4,9 @@ use cw_storage_plus::{Item, Map};
 use euclid::{
     chain::ChainUid,
     fee::DenomFees,
-    liquidity::{AddLiquidityRequest, RemoveLiquidityRequest},
-    pool::{EscrowCreateRequest, PoolCreateRequest},
-    swap::SwapRequest,
+    liquidity::{AddLiquidityLegRequest, AddLiquidityRequest, RemoveLiquidityRequest},
+    pool::{EscrowCreateRequest, PoolCreateRequest, PoolCreationFee},
+    swap::{DcaSchedule, SwapRequest},
     token::Token,
 };
 
@@ -34,6 +34,9 @@ pub const STATE: Item<State> = Item::new("state");
 // Channel that connects factory to hub chain
 pub const HUB_CHANNEL: Item<String> = Item::new("hub_channel");
 
+// Fee charged for pool creation requests, pool creation is free when unset
+pub const POOL_CREATION_FEE: Item<PoolCreationFee> = Item::new("pool_creation_fee");
+
 // Map Pair to vlp address
 pub const PAIR_TO_VLP: Map<(Token, Token), String> = Map::new("pair_to_vlp");
 
@@ -43,6 +46,9 @@ pub const VLP_TO_LP_SHARES: Map<String, Uint128> = Map::new("vlp_to_lp_shares");
 // New Factory states
 pub const TOKEN_TO_ESCROW: Map<Token, Addr> = Map::new("token_to_escrow");
 
+// Tokens whose escrow doesn't accept new deposits, removed along with the escrow once retired
+pub const RETIRING_TOKENS: Map<Token, bool> = Map::new("retiring_tokens");
+
 // New CW20 states
 pub const VLP_TO_CW20: Map<String, Addr> = Map::new("vlp_to_cw20");
 
@@ -50,8 +56,13 @@ pub const VLP_TO_CW20: Map<String, Addr> = Map::new("vlp_to_cw20");
 pub const PENDING_POOL_REQUESTS: Map<(Addr, String), PoolCreateRequest> =
     Map::new("request_to_pool");
 
+// Creation fee paid with a multi asset pool request, held until the ack like the pair pool fee
+pub const PENDING_MULTI_ASSET_POOL_FEES: Map<(Addr, String), PoolCreationFee> =
+    Map::new("pending_multi_asset_pool_fees");
+
+// Map for pending escrow requests for user
 pub const PENDING_ESCROW_REQUESTS: Map<(Addr, String), EscrowCreateRequest> =
-    Map::new("request_to_pool");
+    Map::new("pending_escrow_requests");
 
 // Map for pending swaps for user
 pub const PENDING_SWAPS: Map<(Addr, String), SwapRequest> = Map::new("pending_swaps");
@@ -59,6 +70,17 @@ pub const PENDING_SWAPS: Map<(Addr, String), SwapRequest> = Map::new("pending_sw
 // Map for PENDING liquidity transactions
 pub const PENDING_ADD_LIQUIDITY: Map<(Addr, String), AddLiquidityRequest> =
     Map::new("pending_add_liquidity");
+// Initial liquidity of a created pool and the lp tokens it minted, keyed by vlp. Deposited into
+// the escrows and minted once the lp token is instantiated
+pub const PENDING_POOL_LIQUIDITY: Map<String, (AddLiquidityRequest, Uint128)> =
+    Map::new("pending_pool_liquidity");
+// Map for PENDING legs of cross chain liquidity intents
+pub const PENDING_ADD_LIQUIDITY_LEGS: Map<(Addr, String), AddLiquidityLegRequest> =
+    Map::new("pending_add_liquidity_legs");
 // Map for PENDING liquidity transactions
 pub const PENDING_REMOVE_LIQUIDITY: Map<(Addr, String), RemoveLiquidityRequest> =
     Map::new("pending_remove_liquidity");
+
+// Scheduled swaps, the unswapped part of the deposit is held by the factory
+pub const DCA_SCHEDULES: Map<u64, DcaSchedule> = Map::new("dca_schedules");
+pub const DCA_SCHEDULE_COUNT: Item<u64> = Item::new("dca_schedule_count");
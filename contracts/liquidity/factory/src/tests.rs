@@ -1,15 +1,34 @@
 #[cfg(test)]
 mod tests {
     use crate::contract::{execute, instantiate};
-    use crate::state::{State, HUB_CHANNEL, STATE};
+    use crate::ibc::ack_and_timeout::reusable_internal_ack_call;
+    use crate::migrate::migrate;
+    use crate::state::{
+        State, DCA_SCHEDULES, HUB_CHANNEL, PENDING_ESCROW_REQUESTS, PENDING_MULTI_ASSET_POOL_FEES,
+        PENDING_POOL_LIQUIDITY, PENDING_POOL_REQUESTS, STATE, TOKEN_TO_ESCROW,
+    };
     use std::collections::HashMap;
 
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{DepsMut, Response};
-    use euclid::chain::ChainUid;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+    };
+    use cosmwasm_std::{
+        coin, coins, to_json_binary, Addr, Attribute, BankMsg, ContractResult, CosmosMsg, DepsMut,
+        Order, Response, SystemResult, Uint128,
+    };
+    use cw2::set_contract_version;
+    use cw_storage_plus::Map;
+    use euclid::chain::{ChainUid, CrossChainUser};
     use euclid::error::ContractError;
     use euclid::fee::DenomFees;
-    use euclid::msgs::factory::{ExecuteMsg, InstantiateMsg};
+    use euclid::liquidity::InitialLiquidity;
+    use euclid::msgs::escrow::AllowedTokenResponse;
+    use euclid::msgs::factory::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+    use euclid::pool::{EscrowCreateRequest, PoolCreationFee, PoolCreationResponse};
+    use euclid::swap::NextSwapPair;
+    use euclid::token::{PairWithDenom, Token, TokenType, TokenWithDenom};
+    use euclid_ibc::ack::AcknowledgementMsg;
+    use euclid_ibc::msg::ChainIbcExecuteMsg;
 
     fn _initialize_state(deps: &mut DepsMut) {
         let state = State {
@@ -80,6 +99,425 @@ mod tests {
         assert_eq!(HUB_CHANNEL.load(&deps.storage).unwrap(), "2".to_string());
     }
 
+    #[test]
+    fn test_pool_creation_fee() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+        HUB_CHANNEL
+            .save(deps.as_mut().storage, &"1".to_string())
+            .unwrap();
+
+        let fee = PoolCreationFee {
+            amount: coin(100, "eucl"),
+            treasury: "treasury".to_string(),
+        };
+        let update_msg = ExecuteMsg::UpdatePoolCreationFee {
+            fee: Some(fee.clone()),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("not_owner", &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap();
+
+        let token = |id: &str| TokenWithDenom {
+            token: Token::create(id.to_string()).unwrap(),
+            token_type: TokenType::Native {
+                denom: id.to_string(),
+            },
+        };
+        let request_msg = ExecuteMsg::RequestPoolCreation {
+            pair: PairWithDenom {
+                token_1: token("eth"),
+                token_2: token("usdc"),
+            },
+            timeout: None,
+            lp_token_name: "LP Token".to_string(),
+            lp_token_symbol: "LPT".to_string(),
+            lp_token_decimal: 6,
+            lp_token_marketing: None,
+            initial_liquidity: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(99, "eucl")),
+            request_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientDeposit {});
+
+        // Fee is held with the pending request until the ack
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(100, "eucl")),
+            request_msg,
+        )
+        .unwrap();
+        let (_, request) = PENDING_POOL_REQUESTS
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.creation_fee, Some(fee.clone()));
+
+        // Multi asset pools are charged the same fee, refunded when the hub rejects the pool
+        let tokens = vec![
+            Token::create("atom".to_string()).unwrap(),
+            Token::create("eth".to_string()).unwrap(),
+            Token::create("usdc".to_string()).unwrap(),
+        ];
+        let multi_msg = ExecuteMsg::RequestMultiAssetPoolCreation {
+            tokens: tokens.clone(),
+            timeout: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(99, "eucl")),
+            multi_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientDeposit {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(100, "eucl")),
+            multi_msg,
+        )
+        .unwrap();
+        let ((_, tx_id), held) = PENDING_MULTI_ASSET_POOL_FEES
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(held, fee);
+
+        let res = reusable_internal_ack_call(
+            deps.as_mut(),
+            env,
+            ChainIbcExecuteMsg::RequestMultiAssetPoolCreation {
+                sender: CrossChainUser {
+                    chain_uid: ChainUid::create("1".to_string()).unwrap(),
+                    address: "user".to_string(),
+                },
+                tx_id,
+                tokens,
+            },
+            to_json_binary(&AcknowledgementMsg::<PoolCreationResponse>::Error(
+                "Pool creation is not allowed".to_string(),
+            ))
+            .unwrap(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user".to_string(),
+                amount: coins(100, "eucl"),
+            })
+        );
+        assert!(PENDING_MULTI_ASSET_POOL_FEES.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn test_pool_creation_ack_without_lp_tokens() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+        HUB_CHANNEL
+            .save(deps.as_mut().storage, &"1".to_string())
+            .unwrap();
+
+        let token = |id: &str| TokenWithDenom {
+            token: Token::create(id.to_string()).unwrap(),
+            token_type: TokenType::Native {
+                denom: id.to_string(),
+            },
+        };
+        let pair = PairWithDenom {
+            token_1: token("eth"),
+            token_2: token("usdc"),
+        };
+        let initial_liquidity = InitialLiquidity {
+            token_1_liquidity: Uint128::new(1000),
+            token_2_liquidity: Uint128::new(2000),
+            slippage_tolerance: 1,
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[coin(1000, "eth"), coin(2000, "usdc")]),
+            ExecuteMsg::RequestPoolCreation {
+                pair: pair.clone(),
+                timeout: None,
+                lp_token_name: "LP Token".to_string(),
+                lp_token_symbol: "LPT".to_string(),
+                lp_token_decimal: 6,
+                lp_token_marketing: None,
+                initial_liquidity: Some(initial_liquidity.clone()),
+            },
+        )
+        .unwrap();
+        let (_, request) = PENDING_POOL_REQUESTS
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .next()
+            .unwrap()
+            .unwrap();
+
+        // Hub created the pool but minted no lp tokens, so the held liquidity is refunded
+        let res = reusable_internal_ack_call(
+            deps.as_mut(),
+            env,
+            ChainIbcExecuteMsg::RequestPoolCreation {
+                sender: CrossChainUser {
+                    chain_uid: ChainUid::create("1".to_string()).unwrap(),
+                    address: "user".to_string(),
+                },
+                tx_id: request.tx_id,
+                pair: pair.get_pair().unwrap(),
+                initial_liquidity: Some(initial_liquidity),
+            },
+            to_json_binary(&AcknowledgementMsg::Ok(PoolCreationResponse {
+                vlp_contract: "vlp".to_string(),
+                mint_lp_tokens: None,
+            }))
+            .unwrap(),
+            false,
+        )
+        .unwrap();
+        let refunds: Vec<CosmosMsg> = res.messages[..2]
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect();
+        assert_eq!(
+            refunds,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "user".to_string(),
+                    amount: coins(1000, "eth"),
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "user".to_string(),
+                    amount: coins(2000, "usdc"),
+                }),
+            ]
+        );
+        assert!(PENDING_POOL_LIQUIDITY.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn test_request_retire_escrow() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        init(deps.as_mut());
+        HUB_CHANNEL
+            .save(deps.as_mut().storage, &"1".to_string())
+            .unwrap();
+
+        let token = Token::create("eucl".to_string()).unwrap();
+        let msg = ExecuteMsg::RequestRetireEscrow {
+            token: token.clone(),
+            timeout: None,
+        };
+
+        // Unauthorized
+        let info = mock_info("not_owner", &[]);
+        let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Escrow doesn't exist
+        let info = mock_info("owner", &[]);
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::EscrowDoesNotExist {});
+
+        TOKEN_TO_ESCROW
+            .save(deps.as_mut().storage, token, &Addr::unchecked("escrow"))
+            .unwrap();
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_pending_escrow_requests() {
+        let mut deps = mock_dependencies();
+        init(deps.as_mut());
+
+        // Escrow requests used to be stored under the pool requests namespace
+        let legacy_requests: Map<(Addr, String), EscrowCreateRequest> = Map::new("request_to_pool");
+        let key = (Addr::unchecked("sender"), "tx_1".to_string());
+        let request = EscrowCreateRequest {
+            sender: "sender".to_string(),
+            tx_id: "tx_1".to_string(),
+            token: TokenWithDenom {
+                token: Token::create("eucl".to_string()).unwrap(),
+                token_type: TokenType::Native {
+                    denom: "eucl".to_string(),
+                },
+            },
+        };
+        legacy_requests
+            .save(deps.as_mut().storage, key.clone(), &request)
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("moved_escrow_requests", "1")));
+        assert_eq!(
+            PENDING_ESCROW_REQUESTS
+                .load(deps.as_ref().storage, key.clone())
+                .unwrap(),
+            request
+        );
+        assert!(!PENDING_POOL_REQUESTS.has(deps.as_ref().storage, key));
+
+        // Migrating from a different contract is rejected
+        set_contract_version(deps.as_mut().storage, "crates.io:vlp", "0.1.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CannotMigrate {
+                previous_contract: "crates.io:vlp".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_dca_schedule() {
+        let mut deps = mock_dependencies_with_balance(&coins(1000, "eucl"));
+        let mut env = mock_env();
+        init(deps.as_mut());
+        HUB_CHANNEL
+            .save(deps.as_mut().storage, &"1".to_string())
+            .unwrap();
+        let eucl = Token::create("eucl".to_string()).unwrap();
+        let usdc = Token::create("usdc".to_string()).unwrap();
+        TOKEN_TO_ESCROW
+            .save(
+                deps.as_mut().storage,
+                eucl.clone(),
+                &Addr::unchecked("escrow"),
+            )
+            .unwrap();
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&AllowedTokenResponse { allowed: true }).unwrap(),
+            ))
+        });
+
+        let create_msg = ExecuteMsg::CreateDcaSchedule {
+            asset_in: TokenWithDenom {
+                token: eucl.clone(),
+                token_type: TokenType::Native {
+                    denom: "eucl".to_string(),
+                },
+            },
+            asset_out: usdc.clone(),
+            amount_per_swap: Uint128::new(100),
+            min_amount_out: Uint128::new(1),
+            total_swaps: 3,
+            interval: 60,
+            swaps: vec![NextSwapPair {
+                token_in: eucl,
+                token_out: usdc,
+                test_fail: None,
+            }],
+            timeout: None,
+            cross_chain_addresses: vec![],
+        };
+
+        // Deposit has to cover every swap of the schedule
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(200, "eucl")),
+            create_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::new("Deposit must equal amount per swap times total swaps")
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(300, "eucl")),
+            create_msg,
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteDcaSwap { schedule_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let schedule = DCA_SCHEDULES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(schedule.executed_swaps, 1);
+        assert_eq!(schedule.next_execution, env.block.time.seconds() + 60);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteDcaSwap { schedule_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::new("Next dca swap is not due yet"));
+
+        env.block.time = env.block.time.plus_seconds(60);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteDcaSwap { schedule_id: 1 },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            ExecuteMsg::CancelDcaSchedule { schedule_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Only the last swap is still unspent
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("user", &[]),
+            ExecuteMsg::CancelDcaSchedule { schedule_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user".to_string(),
+                amount: coins(100, "eucl"),
+            })
+        );
+        assert!(!DCA_SCHEDULES.has(deps.as_ref().storage, 1));
+    }
+
     //     #[test]
     //     fn test_execute_request_pool_creation() {
     //         let mut deps = mock_dependencies();
@@ -45,6 +45,11 @@ impl ChainUid {
     pub fn vsl_chain_uid() -> Result<Self, ContractError> {
         Self::create("vsl".to_string())
     }
+
+    // Holder of the lp tokens locked by the first deposit of a vlp, no factory can use it
+    pub fn locked_liquidity_chain_uid() -> Result<Self, ContractError> {
+        Self::create("locked".to_string())
+    }
 }
 
 impl<'a> PrimaryKey<'a> for ChainUid {
@@ -21,11 +21,25 @@ pub enum Cw20HookMsg {
         cross_chain_addresses: Vec<CrossChainUserWithLimit>,
         partner_fee: Option<PartnerFee>,
     },
+    // Deposit amount_per_swap * total_swaps to be swapped over time
+    CreateDcaSchedule {
+        asset_in: TokenWithDenom,
+        asset_out: Token,
+        amount_per_swap: Uint128,
+        min_amount_out: Uint128,
+        total_swaps: u64,
+        interval: u64,
+        swaps: Vec<NextSwapPair>,
+        timeout: Option<u64>,
+        cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+    },
     RemoveLiquidity {
         pair: Pair,
         lp_allocation: Uint128,
         timeout: Option<u64>,
         // First element in array has highest priority
         cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+        // Keep the released tokens as hub virtual balance, cross_chain_addresses are ignored
+        keep_in_virtual_balance: Option<bool>,
     },
 }
@@ -32,6 +32,15 @@ pub enum ContractError {
     #[error("Error - {err}")]
     Generic { err: String },
 
+    #[error("Cannot migrate from different contract type: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error("Cannot migrate from newer version {previous_version} to {new_version}")]
+    CannotMigrateToOlderVersion {
+        previous_version: String,
+        new_version: String,
+    },
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -152,6 +161,18 @@ pub enum ContractError {
     #[error("EscrowAlreadyExists")]
     EscrowAlreadyExists {},
 
+    #[error("Escrow is being retired, new deposits are not allowed")]
+    EscrowRetiring {},
+
+    #[error("Pool is being sunset, only liquidity withdrawals are allowed")]
+    PoolSunset {},
+
+    #[error("A flash swap is in progress on this pool")]
+    FlashSwapInProgress {},
+
+    #[error("Flash swap was not paid back, pool invariant violated")]
+    FlashSwapInvariantViolated {},
+
     #[error("The swap does not exist in state for the sender")]
     SwapDoesNotExist {},
 
@@ -176,6 +197,9 @@ pub enum ContractError {
     #[error("Pool Instantiate Failed {err}")]
     PoolInstantiateFailed { err: String },
 
+    #[error("Pool creation not allowed for {creator} on chain {chain_uid}")]
+    PoolCreationNotAllowed { creator: String, chain_uid: String },
+
     #[error("ContractLocked")]
     ContractLocked {},
 
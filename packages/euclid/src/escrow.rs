@@ -1,7 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Uint128;
 
-use crate::{chain::CrossChainUser, token::Token};
+use crate::{
+    chain::{ChainUid, CrossChainUser},
+    token::Token,
+};
 
 #[cw_serde]
 pub struct EscrowReleaseRequest {
@@ -17,3 +20,20 @@ pub struct EscrowReleaseRequest {
 pub struct EscrowReleaseResponse {
     pub success: bool,
 }
+
+// Record of an escrow release that was rejected or timed out on the factory chain
+#[cw_serde]
+pub struct FailedEscrowRelease {
+    pub tx_id: String,
+    pub sender: CrossChainUser,
+    pub token: Token,
+    pub amount: Uint128,
+    // Chain on which the release was attempted
+    pub chain_uid: ChainUid,
+    pub to_address: String,
+    pub error: String,
+    // Whether the release was retried on the next address in the original list
+    pub retried: bool,
+    // Tx id of the retry, the retry is linked back to this release through its original tx id
+    pub retry_tx_id: Option<String>,
+}
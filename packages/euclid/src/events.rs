@@ -48,7 +48,9 @@ pub enum TxType {
     AddLiquidity,
     RemoveLiquidity,
     PoolCreation,
+    PoolDelisting,
     EscrowCreation,
+    EscrowRetirement,
     EscrowRelease,
     EscrowWithdraw,
     RegisterFactory,
@@ -64,7 +66,9 @@ impl fmt::Display for TxType {
             TxType::AddLiquidity => "add_liquidity",
             TxType::RemoveLiquidity => "remove_liquidity",
             TxType::PoolCreation => "pool_creation",
+            TxType::PoolDelisting => "pool_delisting",
             TxType::EscrowCreation => "escrow_creation",
+            TxType::EscrowRetirement => "escrow_retirement",
             TxType::EscrowRelease => "escrow_release",
             TxType::EscrowWithdraw => "escrow_withdraw",
             TxType::RegisterFactory => "register_factory",
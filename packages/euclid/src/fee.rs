@@ -1,6 +1,6 @@
-use crate::chain::CrossChainUser;
+use crate::{chain::CrossChainUser, error::ContractError};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{ensure, Decimal, Uint128};
 use std::collections::HashMap;
 
 // Set maximum fee as 10%
@@ -16,6 +16,80 @@ pub struct Fee {
     pub recipient: CrossChainUser,
 }
 
+// Share of the euclid fee sent to a recipient when the fees are distributed
+#[cw_serde]
+pub struct FeeRecipient {
+    pub recipient: CrossChainUser,
+    // Weights of all recipients add up to 10000
+    pub weight_bps: u64,
+}
+
+pub fn validate_fee_recipients(recipients: &[FeeRecipient]) -> Result<(), ContractError> {
+    ensure!(
+        !recipients.is_empty(),
+        ContractError::new("Fee recipients cannot be empty")
+    );
+    let total_weight = recipients
+        .iter()
+        .try_fold(0u64, |total, recipient| {
+            total.checked_add(recipient.weight_bps)
+        })
+        .ok_or(ContractError::new("Fee recipient weight overflow"))?;
+    ensure!(
+        total_weight == 10_000,
+        ContractError::new("Fee recipient weights must add up to 10000 bps")
+    );
+    Ok(())
+}
+
+// Splits the amount between the recipients by weight, rounding dust goes to the first recipient
+pub fn split_fee(
+    amount: Uint128,
+    recipients: &[FeeRecipient],
+) -> Result<Vec<(CrossChainUser, Uint128)>, ContractError> {
+    let mut shares = recipients
+        .iter()
+        .map(|recipient| {
+            Ok((
+                recipient.recipient.clone(),
+                amount.checked_mul_floor(Decimal::bps(recipient.weight_bps))?,
+            ))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+    let distributed = shares
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, share)| {
+            total.checked_add(*share)
+        })?;
+    if let Some((_, first_share)) = shares.first_mut() {
+        *first_share = first_share.checked_add(amount.checked_sub(distributed)?)?;
+    }
+    Ok(shares)
+}
+
+// Lp and euclid fee bps of a route, the fees of the rest of the route are charged on what the first swap leaves
+pub fn compound_fee_bps(
+    (lp_fee_bps, euclid_fee_bps): (u64, u64),
+    (next_lp_fee_bps, next_euclid_fee_bps): (u64, u64),
+) -> (u64, u64) {
+    let remaining_bps = 10_000u64.saturating_sub(lp_fee_bps + euclid_fee_bps);
+    (
+        lp_fee_bps + next_lp_fee_bps * remaining_bps / 10_000,
+        euclid_fee_bps + next_euclid_fee_bps * remaining_bps / 10_000,
+    )
+}
+
+// Optional lp fee mode where the fee rises with recent price movement of the pool
+#[cw_serde]
+pub struct DynamicFeeConfig {
+    // Seconds of price history considered, older movements no longer raise the fee
+    pub window: u64,
+    // Extra lp fee bps charged per bps of price movement, movements weigh less as they age
+    pub volatility_factor: Decimal,
+    // Upper bound of the dynamic lp fee, can't exceed MAX_FEE_BPS
+    pub max_lp_fee_bps: u64,
+}
+
 #[cw_serde]
 pub struct TotalFees {
     // Fee for lp providers
@@ -53,3 +127,15 @@ pub struct PartnerFee {
     pub partner_fee_bps: u64,
     pub recipient: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compound_fee_bps() {
+        // Second hop fees are charged on the 98% left after the first hop
+        assert_eq!(compound_fee_bps((100, 100), (100, 50)), (198, 149));
+        assert_eq!(compound_fee_bps((30, 0), (0, 0)), (30, 0));
+    }
+}
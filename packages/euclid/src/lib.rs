@@ -5,6 +5,7 @@ pub mod escrow;
 pub mod events;
 pub mod fee;
 pub mod liquidity;
+pub mod migrate;
 pub mod pool;
 pub mod swap;
 pub mod timeout;
@@ -1,7 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
-use crate::token::{Pair, PairWithDenom};
+use crate::{
+    chain::CrossChainUser,
+    token::{Pair, PairWithDenom, Token, TokenWithDenom},
+};
 
 #[cw_serde]
 pub struct AddLiquidityRequest {
@@ -12,6 +15,15 @@ pub struct AddLiquidityRequest {
     pub pair_info: PairWithDenom,
 }
 
+// Liquidity deposited together with a pool creation request, lp tokens are minted to the pool creator
+#[cw_serde]
+pub struct InitialLiquidity {
+    pub token_1_liquidity: Uint128,
+    pub token_2_liquidity: Uint128,
+    // Only used if the pair's vlp already exists on the hub and the pool is just registered
+    pub slippage_tolerance: u64,
+}
+
 // Struct to handle Acknowledgement Response for a Liquidity Request
 #[cw_serde]
 pub struct AddLiquidityResponse {
@@ -19,6 +31,49 @@ pub struct AddLiquidityResponse {
     pub vlp_address: String,
 }
 
+#[cw_serde]
+pub struct AddLiquidityLegRequest {
+    pub sender: String,
+    pub tx_id: String,
+    pub intent_id: String,
+    pub token: TokenWithDenom,
+    pub amount: Uint128,
+}
+
+// Struct to handle Acknowledgement Response for one leg of a liquidity intent
+#[cw_serde]
+pub struct AddLiquidityLegResponse {
+    pub intent_id: String,
+    // True if this leg completed the intent and liquidity was added to the vlp
+    pub completed: bool,
+}
+
+// First leg of a liquidity intent, held by the router until the other token arrives
+#[cw_serde]
+pub struct LiquidityIntent {
+    pub sender: CrossChainUser,
+    pub token: Token,
+    pub amount: Uint128,
+    pub pair: Pair,
+    pub slippage_tolerance: u64,
+    // Only this user can deposit the other leg
+    pub counterparty: CrossChainUser,
+    // Lp tokens are minted to this user on its chain
+    pub recipient: CrossChainUser,
+    // Unix timestamp in seconds after which the held leg can be refunded
+    pub deadline: u64,
+}
+
+// Record of lp tokens that could not be minted on the recipient chain, the shares stay on the vlp until retried
+#[cw_serde]
+pub struct FailedLpMint {
+    pub tx_id: String,
+    pub recipient: CrossChainUser,
+    pub vlp_address: String,
+    pub amount: Uint128,
+    pub error: String,
+}
+
 #[cw_serde]
 pub struct RemoveLiquidityRequest {
     pub sender: String,
@@ -36,3 +91,10 @@ pub struct RemoveLiquidityResponse {
     pub burn_lp_tokens: Uint128,
     pub vlp_address: String,
 }
+
+// Struct to handle Acknowledgement Response for liquidity added to a multi asset pool
+#[cw_serde]
+pub struct MultiAssetLiquidityResponse {
+    pub vlp_address: String,
+    pub tx_id: String,
+}
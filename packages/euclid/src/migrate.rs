@@ -0,0 +1,131 @@
+use cosmwasm_std::{ensure, Storage};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use semver::Version;
+
+use crate::error::ContractError;
+
+/// Verifies that the stored contract can be migrated to the given name and version and stores the new version.
+///
+/// # Arguments
+///
+/// * `storage` - The contract storage.
+/// * `contract_name` - Name of the contract being migrated to.
+/// * `contract_version` - Version of the contract being migrated to.
+/// * `legacy_names` - Names this contract was stored under before, their versions are not comparable so only the name is checked.
+///
+/// # Errors
+///
+/// Returns an error if the stored contract has a different name or a newer version.
+///
+/// # Returns
+///
+/// Returns the previously stored contract version so that callers can run the migration steps they need.
+pub fn ensure_migration_version(
+    storage: &mut dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+    legacy_names: &[&str],
+) -> Result<ContractVersion, ContractError> {
+    let previous = get_contract_version(storage)?;
+
+    if previous.contract != contract_name {
+        ensure!(
+            legacy_names.contains(&previous.contract.as_str()),
+            ContractError::CannotMigrate {
+                previous_contract: previous.contract,
+            }
+        );
+    } else {
+        let previous_version = parse_version(&previous.version)?;
+        let new_version = parse_version(contract_version)?;
+        ensure!(
+            previous_version <= new_version,
+            ContractError::CannotMigrateToOlderVersion {
+                previous_version: previous.version,
+                new_version: contract_version.to_string(),
+            }
+        );
+    }
+
+    set_contract_version(storage, contract_name, contract_version)?;
+    Ok(previous)
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|err| ContractError::new(&err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    struct TestMigrationVersion {
+        name: &'static str,
+        stored_contract: &'static str,
+        stored_version: &'static str,
+        expected_error: Option<ContractError>,
+    }
+
+    #[test]
+    fn test_ensure_migration_version() {
+        let test_cases = vec![
+            TestMigrationVersion {
+                name: "Same version",
+                stored_contract: "crates.io:router",
+                stored_version: "0.1.0",
+                expected_error: None,
+            },
+            TestMigrationVersion {
+                name: "Older version",
+                stored_contract: "crates.io:router",
+                stored_version: "0.0.9",
+                expected_error: None,
+            },
+            TestMigrationVersion {
+                name: "Legacy name with unrelated version",
+                stored_contract: "crates.io:legacy",
+                stored_version: "5.0.0",
+                expected_error: None,
+            },
+            TestMigrationVersion {
+                name: "Newer version",
+                stored_contract: "crates.io:router",
+                stored_version: "0.2.0",
+                expected_error: Some(ContractError::CannotMigrateToOlderVersion {
+                    previous_version: "0.2.0".to_string(),
+                    new_version: "0.1.0".to_string(),
+                }),
+            },
+            TestMigrationVersion {
+                name: "Different contract",
+                stored_contract: "crates.io:vlp",
+                stored_version: "0.1.0",
+                expected_error: Some(ContractError::CannotMigrate {
+                    previous_contract: "crates.io:vlp".to_string(),
+                }),
+            },
+        ];
+
+        for test in test_cases {
+            let mut storage = MockStorage::new();
+            set_contract_version(&mut storage, test.stored_contract, test.stored_version).unwrap();
+
+            let res = ensure_migration_version(
+                &mut storage,
+                "crates.io:router",
+                "0.1.0",
+                &["crates.io:legacy"],
+            );
+            match test.expected_error {
+                Some(err) => assert_eq!(res.unwrap_err(), err, "{}", test.name),
+                None => {
+                    assert_eq!(res.unwrap().contract, test.stored_contract, "{}", test.name);
+                    let stored = get_contract_version(&storage).unwrap();
+                    assert_eq!(stored.contract, "crates.io:router", "{}", test.name);
+                    assert_eq!(stored.version, "0.1.0", "{}", test.name);
+                }
+            }
+        }
+    }
+}
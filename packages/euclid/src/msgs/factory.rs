@@ -1,8 +1,9 @@
 use crate::{
-    chain::{ChainUid, CrossChainUserWithLimit},
+    chain::{ChainUid, CrossChainUser, CrossChainUserWithLimit},
     fee::{DenomFees, PartnerFee},
-    liquidity::{AddLiquidityRequest, RemoveLiquidityRequest},
-    swap::{NextSwapPair, SwapRequest},
+    liquidity::{AddLiquidityRequest, InitialLiquidity, RemoveLiquidityRequest},
+    pool::PoolCreationFee,
+    swap::{DcaSchedule, NextSwapPair, SwapRequest},
     token::{Pair, PairWithDenom, Token, TokenType, TokenWithDenom},
     utils::Pagination,
 };
@@ -29,6 +30,50 @@ pub enum ExecuteMsg {
         slippage_tolerance: u64,
         timeout: Option<u64>,
     },
+    // Deposit one token of a liquidity intent, the other token can come from any chain
+    AddLiquidityLegRequest {
+        intent_id: String,
+        pair: Pair,
+        // Sender of the other leg on its chain
+        counterparty: CrossChainUser,
+        token: TokenWithDenom,
+        amount: Uint128,
+        slippage_tolerance: u64,
+        recipient: CrossChainUser,
+        // Unix timestamp in seconds after which an incomplete intent can be refunded
+        deadline: u64,
+        timeout: Option<u64>,
+    },
+    // Provide liquidity from virtual balance, lp tokens are minted on the recipient chain
+    AddLiquidityFromVirtualBalance {
+        pair: Pair,
+        token_1_liquidity: Uint128,
+        token_2_liquidity: Uint128,
+        slippage_tolerance: u64,
+        // Defaults to the sender on this chain
+        recipient: Option<CrossChainUser>,
+        timeout: Option<u64>,
+    },
+    // Create a hub pool of 3 to 8 tokens that can be swapped between each other, charges the same
+    // pool creation fee as pair pools
+    RequestMultiAssetPoolCreation {
+        tokens: Vec<Token>,
+        timeout: Option<u64>,
+    },
+    // Provide liquidity to a multi asset pool from virtual balance, the lp shares are held by
+    // the pool on the hub instead of being minted as lp tokens on this chain
+    AddMultiAssetLiquidityFromVirtualBalance {
+        vlp_address: String,
+        liquidity: Vec<(Token, Uint128)>,
+        min_lp_allocation: Uint128,
+        timeout: Option<u64>,
+    },
+    // Released liquidity is kept as virtual balance, use WithdrawVirtualBalance to withdraw it
+    RemoveMultiAssetLiquidity {
+        vlp_address: String,
+        lp_allocation: Uint128,
+        timeout: Option<u64>,
+    },
     ExecuteSwapRequest {
         asset_in: TokenWithDenom,
         asset_out: Token,
@@ -41,6 +86,26 @@ pub enum ExecuteMsg {
 
         partner_fee: Option<PartnerFee>,
     },
+    // Deposit amount_per_swap * total_swaps to be swapped over time, one swap every interval seconds
+    CreateDcaSchedule {
+        asset_in: TokenWithDenom,
+        asset_out: Token,
+        amount_per_swap: Uint128,
+        min_amount_out: Uint128,
+        total_swaps: u64,
+        interval: u64,
+        swaps: Vec<NextSwapPair>,
+        timeout: Option<u64>,
+        cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+    },
+    // Permissionless, executes the next swap of a schedule once its interval has passed
+    ExecuteDcaSwap {
+        schedule_id: u64,
+    },
+    // Refunds the part of the deposit that hasn't been swapped yet
+    CancelDcaSchedule {
+        schedule_id: u64,
+    },
     RequestRegisterDenom {
         token: TokenWithDenom,
     },
@@ -54,14 +119,26 @@ pub enum ExecuteMsg {
         lp_token_symbol: String,
         lp_token_decimal: u8,
         lp_token_marketing: Option<cw20_base::msg::InstantiateMarketingInfo>,
+        // Seeds the pool in the same request, native funds are sent with the message and cw20
+        // tokens need an allowance for the factory
+        initial_liquidity: Option<InitialLiquidity>,
     },
     RequestRegisterEscrow {
         token: TokenWithDenom,
         timeout: Option<u64>,
     },
+    // Stops new deposits for the token and removes its escrow once the hub balance is withdrawn
+    RequestRetireEscrow {
+        token: Token,
+        timeout: Option<u64>,
+    },
     UpdateHubChannel {
         new_channel: String,
     },
+    // Fee paid with every pool creation request, None makes pool creation free. Admin only
+    UpdatePoolCreationFee {
+        fee: Option<PoolCreationFee>,
+    },
     WithdrawVirtualBalance {
         token: Token,
         amount: Uint128,
@@ -100,6 +177,9 @@ pub enum QueryMsg {
     #[returns(PartnerFeesCollectedResponse)]
     GetPartnerFeesCollected {},
 
+    #[returns(PoolCreationFeeResponse)]
+    GetPoolCreationFee {},
+
     // Query to get all pools in the factory
     #[returns(AllPoolsResponse)]
     GetAllPools {},
@@ -127,6 +207,15 @@ pub enum QueryMsg {
 
     #[returns(GetEscrowResponse)]
     GetEscrow { token_id: String },
+
+    #[returns(DcaScheduleResponse)]
+    GetDcaSchedule { schedule_id: u64 },
+    #[returns(DcaSchedulesResponse)]
+    GetDcaSchedules { pagination: Pagination<u64> },
+
+    // Tokens whose escrow is being retired
+    #[returns(AllTokensResponse)]
+    GetRetiringTokens {},
 }
 
 #[cw_serde]
@@ -143,6 +232,7 @@ pub struct GetLPTokenResponse {
 pub struct GetEscrowResponse {
     pub escrow_address: Option<Addr>,
     pub denoms: Vec<TokenType>,
+    pub retiring: bool,
 }
 // We define a custom struct for each query response
 #[cw_serde]
@@ -164,6 +254,11 @@ pub struct PartnerFeesCollectedResponse {
     pub total: DenomFees,
 }
 
+#[cw_serde]
+pub struct PoolCreationFeeResponse {
+    pub fee: Option<PoolCreationFee>,
+}
+
 #[cw_serde]
 pub struct PartnerFeesCollectedPerDenomResponse {
     pub total: Uint128,
@@ -197,6 +292,23 @@ pub struct ReleaseEscrowResponse {
     pub to_address: String,
 }
 
+#[cw_serde]
+pub struct DelistPoolResponse {
+    pub factory_address: String,
+    pub chain_id: String,
+    pub pair: Pair,
+    pub vlp_address: String,
+}
+
+#[cw_serde]
+pub struct MintLpTokensResponse {
+    pub factory_address: String,
+    pub chain_id: String,
+    pub vlp_address: String,
+    pub recipient: String,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct GetPendingSwapsResponse {
     pub pending_swaps: Vec<SwapRequest>,
@@ -211,6 +323,17 @@ pub struct GetPendingRemoveLiquidityResponse {
     pub pending_remove_liquidity: Vec<RemoveLiquidityRequest>,
 }
 
+#[cw_serde]
+pub struct DcaScheduleResponse {
+    pub schedule_id: u64,
+    pub schedule: DcaSchedule,
+}
+
+#[cw_serde]
+pub struct DcaSchedulesResponse {
+    pub schedules: Vec<DcaScheduleResponse>,
+}
+
 #[cw_serde]
 pub struct AllTokensResponse {
     pub tokens: Vec<Token>, // Assuming pool addresses are strings
@@ -0,0 +1,104 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::{chain::CrossChainUser, token::Token, utils::Pagination};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub router: String,
+    // Token every fee is converted into
+    pub target_token: Token,
+    // Receives the converted fees on the hub
+    pub recipient: CrossChainUser,
+    // Largest loss against the pool spot prices accepted for a conversion, pool fees included
+    pub max_slippage_bps: u64,
+    // Intermediate tokens tried when a token has no direct pool with the target token
+    pub route_tokens: Vec<Token>,
+    // Besides the admin, only these addresses can convert fees
+    pub keepers: Vec<String>,
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Swaps the held fees of a token into the target token and forwards them to the recipient.
+    // The minimum output comes from the route's spot prices in the same block, so only the admin and
+    // the keepers can call it. Converts the whole balance when amount is None
+    Convert {
+        token: Token,
+        amount: Option<Uint128>,
+    },
+    // Admin only
+    UpdateConfig {
+        recipient: Option<CrossChainUser>,
+        max_slippage_bps: Option<u64>,
+        route_tokens: Option<Vec<Token>>,
+        keepers: Option<Vec<String>>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    GetConfig {},
+    // Fees held by the converter that are not converted yet
+    #[returns(PendingFeesResponse)]
+    GetPendingFees {},
+    // Amounts converted so far for each token
+    #[returns(ConversionsResponse)]
+    GetConversions { pagination: Pagination<Token> },
+}
+
+#[cw_serde]
+pub struct Config {
+    pub router: Addr,
+    pub virtual_balance: Addr,
+    pub target_token: Token,
+    pub recipient: CrossChainUser,
+    pub max_slippage_bps: u64,
+    pub route_tokens: Vec<Token>,
+    #[serde(default)]
+    pub keepers: Vec<Addr>,
+    pub admin: Addr,
+}
+
+#[cw_serde]
+pub struct Conversion {
+    pub amount_in: Uint128,
+    // Target token received for amount_in
+    pub amount_out: Uint128,
+    pub conversions: u64,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub config: Config,
+}
+
+#[cw_serde]
+pub struct PendingFee {
+    pub token: Token,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingFeesResponse {
+    pub fees: Vec<PendingFee>,
+}
+
+#[cw_serde]
+pub struct ConversionResponse {
+    pub token: Token,
+    pub conversion: Conversion,
+}
+
+#[cw_serde]
+pub struct ConversionsResponse {
+    pub conversions: Vec<ConversionResponse>,
+    // Target token sent to the recipient over all conversions
+    pub total_converted: Uint128,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
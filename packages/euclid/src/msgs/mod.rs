@@ -1,6 +1,8 @@
 pub mod cw20;
 pub mod escrow;
 pub mod factory;
+pub mod fee_converter;
+pub mod multi_vlp;
 pub mod router;
 pub mod virtual_balance;
 pub mod vlp;
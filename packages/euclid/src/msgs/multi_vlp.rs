@@ -0,0 +1,143 @@
+use crate::{
+    chain::CrossChainUser,
+    fee::{Fee, TotalFees},
+    swap::{DepthTarget, NextSwapVlp},
+    token::Token,
+};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub router: String,
+    pub virtual_balance: String,
+    // Sorted and unique, between 3 and 8 tokens
+    pub tokens: Vec<Token>,
+    pub fee: Fee,
+    pub admin: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Admin only
+    UpdateFee {
+        lp_fee_bps: Option<u64>,
+        euclid_fee_bps: Option<u64>,
+        recipient: Option<CrossChainUser>,
+    },
+
+    // Sends the accumulated euclid fees to the fee recipient, callable by anyone
+    DistributeFees {},
+
+    // Same message as the vlp swap so that multi asset pools can be part of any swap route
+    Swap {
+        sender: CrossChainUser,
+        tx_id: String,
+        asset_in: Token,
+        amount_in: Uint128,
+        min_token_out: Uint128,
+        next_swaps: Vec<NextSwapVlp>,
+        test_fail: Option<bool>,
+        asset_out: Option<Token>,
+    },
+
+    // Liquidity is transferred to the pool's virtual balance by the router before this is called.
+    // Only the proportional part of each token is added, the rest goes back to the sender
+    AddLiquidity {
+        sender: CrossChainUser,
+        tx_id: String,
+        liquidity: Vec<(Token, Uint128)>,
+        min_lp_allocation: Uint128,
+    },
+
+    // Releases the sender's share of every token to its virtual balance, called by the router
+    RemoveLiquidity {
+        sender: CrossChainUser,
+        tx_id: String,
+        lp_allocation: Uint128,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(StateResponse)]
+    State {},
+
+    // Same query as the vlp simulate swap, asset_out is required
+    #[returns(crate::msgs::vlp::GetSwapResponse)]
+    SimulateSwap {
+        asset: Token,
+        asset_amount: Uint128,
+        swaps: Vec<NextSwapVlp>,
+        asset_out: Option<Token>,
+    },
+
+    // Same query as the vlp liquidity depth, asset_out is required
+    #[returns(crate::msgs::vlp::LiquidityDepthResponse)]
+    LiquidityDepth {
+        asset_in: Token,
+        target: DepthTarget,
+        asset_out: Option<Token>,
+    },
+
+    // Reserve of every token in the pool
+    #[returns(LiquidityResponse)]
+    Liquidity {},
+
+    // Lp shares held by a user
+    #[returns(LpSharesResponse)]
+    LpShares { user: CrossChainUser },
+
+    // Euclid fees accumulated since the last distribution
+    #[returns(UndistributedFeesResponse)]
+    UndistributedFees {},
+}
+
+#[cw_serde]
+pub struct StateResponse {
+    pub tokens: Vec<Token>,
+    pub router: String,
+    pub virtual_balance: String,
+    pub fee: Fee,
+    pub total_fees_collected: TotalFees,
+    pub total_lp_tokens: Uint128,
+    pub admin: String,
+}
+
+#[cw_serde]
+pub struct LiquidityResponse {
+    pub reserves: Vec<(Token, Uint128)>,
+    pub total_lp_tokens: Uint128,
+}
+
+#[cw_serde]
+pub struct LpSharesResponse {
+    pub user: CrossChainUser,
+    pub lp_shares: Uint128,
+}
+
+#[cw_serde]
+pub struct UndistributedFeesResponse {
+    pub fees: Vec<(Token, Uint128)>,
+}
+
+#[cw_serde]
+pub struct MultiVlpAddLiquidityResponse {
+    pub sender: CrossChainUser,
+    pub tx_id: String,
+    pub mint_lp_tokens: Uint128,
+    pub vlp_address: String,
+}
+
+#[cw_serde]
+pub struct MultiVlpRemoveLiquidityResponse {
+    pub sender: CrossChainUser,
+    pub tx_id: String,
+    pub liquidity_released: Vec<(Token, Uint128)>,
+    pub burn_lp_tokens: Uint128,
+    pub vlp_address: String,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
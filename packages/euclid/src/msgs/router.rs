@@ -1,9 +1,15 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, IbcPacketAckMsg, IbcPacketReceiveMsg, Uint128};
+use cosmwasm_std::{
+    Addr, Binary, Decimal, IbcPacketAckMsg, IbcPacketReceiveMsg, Timestamp, Uint128,
+};
 
 use crate::{
     chain::{Chain, ChainUid, CrossChainUser, CrossChainUserWithLimit},
-    swap::NextSwapPair,
+    escrow::FailedEscrowRelease,
+    fee::FeeRecipient,
+    liquidity::{FailedLpMint, LiquidityIntent},
+    pool::PoolCreationMode,
+    swap::{LimitOrder, NextSwapPair, SwapHop},
     token::{Pair, Token},
     utils::Pagination,
 };
@@ -31,10 +37,34 @@ pub enum ExecuteMsg {
     UpdateVLPCodeId {
         new_vlp_code_id: u64,
     },
+    // Code id of multi asset pools, admin only
+    UpdateMultiVlpCodeId {
+        new_code_id: u64,
+    },
+    // Migrate the next batch of vlps to the current vlp code id. The router has to be the wasm admin of
+    // each vlp, older vlps need a MsgUpdateAdmin to the router from the admin before they can be migrated.
+    // Once a pass is finished, calling it again retries the vlps that failed
+    MigrateVlps {
+        limit: Option<u64>,
+    },
     RegisterFactory {
         chain_uid: ChainUid,
         chain_info: RegisterFactoryChainType,
     },
+    // Stop swaps and new liquidity on a vlp, it gets delisted once all liquidity is withdrawn
+    SunsetVlp {
+        pair: Pair,
+    },
+    // Refund the held leg of a liquidity intent once its deadline has passed
+    RefundLiquidityIntent {
+        sender: CrossChainUser,
+        intent_id: String,
+    },
+    // Send the lp tokens of a failed mint to the recipient chain again
+    RetryLpMint {
+        recipient: CrossChainUser,
+        tx_id: String,
+    },
     WithdrawVoucher {
         token: Token,
         amount: Option<Uint128>,
@@ -48,6 +78,81 @@ pub enum ExecuteMsg {
         cross_chain_addresses: Vec<CrossChainUserWithLimit>,
         timeout: Option<u64>,
         tx_id: String,
+        // Set when retrying a failed release, tx id of the release that was first attempted
+        original_tx_id: Option<String>,
+    },
+
+    // Swap, add and remove liquidity directly from the sender's virtual balance on the hub
+    VslSwap {
+        asset_in: Token,
+        amount_in: Uint128,
+        asset_out: Token,
+        min_amount_out: Uint128,
+        swaps: Vec<NextSwapPair>,
+    },
+    VslAddLiquidity {
+        pair: Pair,
+        token_1_liquidity: Uint128,
+        token_2_liquidity: Uint128,
+        slippage_tolerance: u64,
+    },
+    VslRemoveLiquidity {
+        pair: Pair,
+        lp_allocation: Uint128,
+    },
+    // Create a pool of 3 to 8 tokens that can be swapped between each other
+    CreateMultiAssetPool {
+        tokens: Vec<Token>,
+    },
+    // Add liquidity to a multi asset pool from the sender's virtual balance, lp shares are held by the pool
+    VslAddMultiAssetLiquidity {
+        vlp_address: String,
+        liquidity: Vec<(Token, Uint128)>,
+        min_lp_allocation: Uint128,
+    },
+    VslRemoveMultiAssetLiquidity {
+        vlp_address: String,
+        lp_allocation: Uint128,
+    },
+    // Who can request pool creation from the chain, chains default to open. Admin only
+    UpdatePoolCreationMode {
+        chain_uid: ChainUid,
+        mode: PoolCreationMode,
+    },
+    // Default euclid fee split for vlps without their own fee recipients, None clears it. Admin only
+    UpdateDefaultFeeRecipients {
+        recipients: Option<Vec<FeeRecipient>>,
+    },
+    // Claim the lp fees earned by the sender's hub lp shares while the vlp accrues claimable lp fees.
+    // There is no claim for lp tokens held on other chains, their share of the fees compounds
+    ClaimVslLpFees {
+        pair: Pair,
+    },
+    // Escrow the sender's virtual balance until the swap can be done at limit_price or better
+    PlaceLimitOrder {
+        asset_in: Token,
+        amount_in: Uint128,
+        asset_out: Token,
+        limit_price: Decimal,
+        swaps: Vec<NextSwapPair>,
+    },
+    CancelLimitOrder {
+        order_id: u64,
+    },
+    // Permissionless, fills every listed order whose simulated output satisfies its limit
+    ExecuteOrders {
+        order_ids: Vec<u64>,
+    },
+    ExecuteLimitOrderInternal {
+        order_id: u64,
+    },
+    // Provide liquidity from the sender's virtual balance, lp tokens are minted on the recipient chain
+    AddLiquidityFromVirtualBalance {
+        pair: Pair,
+        token_1_liquidity: Uint128,
+        token_2_liquidity: Uint128,
+        slippage_tolerance: u64,
+        recipient: CrossChainUser,
     },
 
     // IBC Callbacks
@@ -80,8 +185,64 @@ pub enum QueryMsg {
     GetAllVlps {
         pagination: Pagination<(Token, Token)>,
     },
+    // Address of the pair's vlp, predicted from the current vlp code id if the pair has no vlp yet
+    #[returns(PredictVlpAddressResponse)]
+    PredictVlpAddress { pair: Pair },
+    // Vlps that have the token on either side of their pair
+    #[returns(AllVlpResponse)]
+    GetVlpsByToken {
+        token: Token,
+        pagination: Pagination<(Token, Token)>,
+    },
+    #[returns(AllVlpResponse)]
+    GetSunsetVlps { pagination: Pagination<String> },
+    // Swap volume of a page of vlps, only the swaps coming from chain_uid when set
+    #[returns(AllVlpVolumeResponse)]
+    GetVolume {
+        chain_uid: Option<ChainUid>,
+        pagination: Pagination<(Token, Token)>,
+    },
+    #[returns(MultiAssetVlpResponse)]
+    GetMultiAssetVlp { tokens: Vec<Token> },
+    #[returns(AllMultiAssetVlpResponse)]
+    GetAllMultiAssetVlps { pagination: Pagination<String> },
+    #[returns(VlpMigrationStatusResponse)]
+    GetVlpMigrationStatus { pagination: Pagination<String> },
+    #[returns(VslLpSharesResponse)]
+    GetVslLpShares {
+        user: String,
+        pagination: Pagination<String>,
+    },
+    #[returns(FeeRecipientsResponse)]
+    GetDefaultFeeRecipients {},
+    #[returns(PoolCreationModeResponse)]
+    GetPoolCreationMode { chain_uid: ChainUid },
+    #[returns(UnclaimedVslLpFeesResponse)]
+    GetUnclaimedVslLpFees { user: String, pair: Pair },
+    #[returns(LiquidityIntentResponse)]
+    GetLiquidityIntent {
+        sender: CrossChainUser,
+        intent_id: String,
+    },
+    #[returns(LimitOrderResponse)]
+    GetLimitOrder { order_id: u64 },
+    #[returns(LimitOrdersResponse)]
+    GetLimitOrders { pagination: Pagination<u64> },
+    #[returns(LimitOrdersResponse)]
+    GetUserLimitOrders {
+        user: String,
+        pagination: Pagination<u64>,
+    },
     #[returns(SimulateSwapResponse)]
     SimulateSwap(QuerySimulateSwap),
+    // Depth of every pool that can swap asset_in to asset_out directly, until the price of asset_in
+    // drops by price_move_bps
+    #[returns(LiquidityDepthResponse)]
+    GetLiquidityDepth {
+        asset_in: Token,
+        asset_out: Token,
+        price_move_bps: u64,
+    },
 
     #[returns(SimulateEscrowReleaseResponse)]
     SimulateReleaseEscrow {
@@ -98,6 +259,23 @@ pub enum QueryMsg {
 
     #[returns(AllTokensResponse)]
     QueryAllTokens { pagination: Pagination<Token> },
+
+    #[returns(RetiringEscrowsResponse)]
+    GetRetiringEscrows {
+        pagination: Pagination<(Token, ChainUid)>,
+    },
+
+    #[returns(FailedEscrowReleasesResponse)]
+    GetFailedEscrowReleases {
+        user: CrossChainUser,
+        pagination: Pagination<String>,
+    },
+
+    #[returns(FailedLpMintsResponse)]
+    GetFailedLpMints {
+        user: CrossChainUser,
+        pagination: Pagination<String>,
+    },
 }
 // We define a custom struct for each query response
 #[cw_serde]
@@ -125,11 +303,66 @@ pub struct AllVlpResponse {
     pub vlps: Vec<VlpResponse>,
 }
 
+#[cw_serde]
+pub struct VlpVolumeResponse {
+    pub vlp: String,
+    pub token_1: Token,
+    pub token_2: Token,
+    pub token_1_volume: Uint128,
+    pub token_2_volume: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenVolume {
+    pub token: Token,
+    pub volume: Uint128,
+}
+
+#[cw_serde]
+pub struct AllVlpVolumeResponse {
+    pub vlps: Vec<VlpVolumeResponse>,
+    // Volume of each token added up over the vlps of the page
+    pub totals: Vec<TokenVolume>,
+    // Vlps of the page left out of the totals because their query failed
+    #[serde(default)]
+    pub failed_vlps: Vec<PoolQueryError>,
+}
+
 #[cw_serde]
 pub struct VlpResponse {
     pub vlp: String,
     pub token_1: Token,
     pub token_2: Token,
+    // Unset for vlps created before pool metadata was recorded
+    pub metadata: Option<VlpMetadata>,
+}
+
+#[cw_serde]
+pub struct PredictVlpAddressResponse {
+    pub vlp_address: Addr,
+    // True if the vlp already exists, a prediction changes with the vlp code id until then
+    pub registered: bool,
+}
+
+#[cw_serde]
+pub struct VlpMetadata {
+    pub created_at: Timestamp,
+    // Chain the pool creation request came from
+    pub creator_chain: ChainUid,
+    // Fee tier the vlp was created with, the vlp admin can change the fee afterwards
+    pub lp_fee_bps: u64,
+    pub euclid_fee_bps: u64,
+}
+
+#[cw_serde]
+pub struct MultiAssetVlpResponse {
+    pub vlp: String,
+    pub tokens: Vec<Token>,
+}
+
+#[cw_serde]
+pub struct AllMultiAssetVlpResponse {
+    pub vlps: Vec<MultiAssetVlpResponse>,
 }
 
 #[cw_serde]
@@ -147,6 +380,36 @@ pub struct AllChainResponse {
 pub struct SimulateSwapResponse {
     pub amount_out: Uint128,
     pub asset_out: Token,
+    // Swap of every vlp in the route, in order
+    pub hops: Vec<SwapHop>,
+    // Amount out per amount in of the whole route, fees included
+    pub execution_price: Decimal,
+    pub price_impact_bps: u64,
+    // Effective fees of the route, compounded over every hop
+    #[serde(default)]
+    pub lp_fee_bps: u64,
+    #[serde(default)]
+    pub euclid_fee_bps: u64,
+}
+
+#[cw_serde]
+pub struct LiquidityDepthResponse {
+    pub asset_in: Token,
+    pub asset_out: Token,
+    pub pools: Vec<crate::msgs::vlp::LiquidityDepthResponse>,
+    // Added up over every pool
+    pub total_amount_in: Uint128,
+    pub total_amount_out: Uint128,
+    // Pools left out of the totals because their query failed
+    #[serde(default)]
+    pub failed_pools: Vec<PoolQueryError>,
+}
+
+// Pool that couldn't be queried while aggregating over several pools
+#[cw_serde]
+pub struct PoolQueryError {
+    pub vlp_address: String,
+    pub error: String,
 }
 
 #[cw_serde]
@@ -177,6 +440,29 @@ pub struct AllTokensResponse {
     pub tokens: Vec<TokenResponse>,
 }
 
+#[cw_serde]
+pub struct RetiringEscrowsResponse {
+    pub escrows: Vec<RetiringEscrowResponse>,
+}
+
+#[cw_serde]
+pub struct RetiringEscrowResponse {
+    pub token: Token,
+    pub chain_uid: ChainUid,
+    // Balance that still needs to be withdrawn before the escrow can be removed
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct FailedEscrowReleasesResponse {
+    pub releases: Vec<FailedEscrowRelease>,
+}
+
+#[cw_serde]
+pub struct FailedLpMintsResponse {
+    pub mints: Vec<FailedLpMint>,
+}
+
 #[cw_serde]
 pub enum RegisterFactoryChainType {
     Native(RegisterFactoryChainNative),
@@ -192,3 +478,66 @@ pub struct RegisterFactoryChainIbc {
     pub channel: String,
     pub timeout: Option<u64>,
 }
+
+#[cw_serde]
+pub struct VslLpSharesResponse {
+    pub shares: Vec<VslLpShareResponse>,
+}
+
+#[cw_serde]
+pub struct LiquidityIntentResponse {
+    pub intent_id: String,
+    pub intent: LiquidityIntent,
+}
+
+#[cw_serde]
+pub struct LimitOrderResponse {
+    pub order_id: u64,
+    pub order: LimitOrder,
+}
+
+#[cw_serde]
+pub struct LimitOrdersResponse {
+    pub orders: Vec<LimitOrderResponse>,
+}
+
+#[cw_serde]
+pub struct VslLpShareResponse {
+    pub vlp: String,
+    pub lp_shares: Uint128,
+}
+
+#[cw_serde]
+pub struct FeeRecipientsResponse {
+    pub recipients: Option<Vec<FeeRecipient>>,
+}
+
+#[cw_serde]
+pub struct PoolCreationModeResponse {
+    pub chain_uid: ChainUid,
+    pub mode: PoolCreationMode,
+}
+
+#[cw_serde]
+pub struct UnclaimedVslLpFeesResponse {
+    pub vlp: String,
+    pub token_1_fees: Uint128,
+    pub token_2_fees: Uint128,
+}
+
+#[cw_serde]
+pub struct VlpMigrationResult {
+    pub vlp: String,
+    pub code_id: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct VlpMigrationStatusResponse {
+    pub code_id: u64,
+    // Last pair that was picked up for migration, next batch starts after it
+    pub last_pair: Option<(Token, Token)>,
+    pub finished: bool,
+    pub results: Vec<VlpMigrationResult>,
+}
@@ -1,11 +1,13 @@
 use crate::{
     chain::{ChainUid, CrossChainUser},
-    fee::{Fee, TotalFees},
-    swap::NextSwapVlp,
+    error::ContractError,
+    fee::{DynamicFeeConfig, Fee, FeeRecipient, TotalFees},
+    swap::{get_execution_price, get_route_price_impact_bps, DepthTarget, NextSwapVlp, SwapHop},
     token::{Pair, Token},
+    utils::Pagination,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Binary, Decimal, Decimal256, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -33,6 +35,37 @@ pub enum ExecuteMsg {
         recipient: Option<CrossChainUser>,
     },
 
+    // Enable, update or disable (None) the dynamic lp fee mode, admin only
+    UpdateDynamicFee {
+        config: Option<DynamicFeeConfig>,
+    },
+
+    // Split the euclid fee between several recipients, None falls back to the router default
+    // and then to the fee recipient. Admin only
+    UpdateFeeRecipients {
+        recipients: Option<Vec<FeeRecipient>>,
+    },
+
+    // Sends the accumulated euclid fees to the fee recipients, callable by anyone
+    DistributeFees {},
+
+    // Switch lp fees between compounding into the reserves and accruing as claimable fees, admin only.
+    // Only the share of hub lp tokens becomes claimable. Lp tokens of other chains are cw20 balances on
+    // those chains with no per holder accounting on the hub, so their share keeps compounding and is
+    // earned when the liquidity is removed
+    UpdateLpFeeMode {
+        claimable: bool,
+    },
+
+    // Sends the claimable lp fees earned by the hub lp tokens to the recipient's virtual balance,
+    // called by the router for ClaimVslLpFees. Claims everything when amounts is None, any other
+    // chain_uid is rejected since other chains' lp fees compound
+    ClaimLpFees {
+        chain_uid: ChainUid,
+        recipient: CrossChainUser,
+        amounts: Option<(Uint128, Uint128)>,
+    },
+
     Swap {
         sender: CrossChainUser,
         tx_id: String,
@@ -41,6 +74,10 @@ pub enum ExecuteMsg {
         min_token_out: Uint128,
         next_swaps: Vec<NextSwapVlp>,
         test_fail: Option<bool>,
+        // Must be the other token of the pair when set, left out when unset so vlps without the
+        // field still accept the message
+        #[serde(skip_serializing_if = "Option::is_none")]
+        asset_out: Option<Token>,
     },
     AddLiquidity {
         sender: CrossChainUser,
@@ -54,6 +91,17 @@ pub enum ExecuteMsg {
         tx_id: String,
         lp_allocation: Uint128,
     },
+    // Stops swaps and new liquidity so LPs can only withdraw, called by the router
+    Sunset {},
+    // Sends amount_out to the caller's hub virtual balance and invokes FlashSwapCallback on it,
+    // the pool invariant with fees must hold once the callback returns
+    FlashSwap {
+        asset_out: Token,
+        amount_out: Uint128,
+        data: Binary,
+    },
+    // Verifies the invariant after the flash swap callback, only callable by the vlp itself
+    SettleFlashSwap {},
     /*
 
     // Update the fee for the VLP
@@ -65,6 +113,17 @@ pub enum ExecuteMsg {
     */
 }
 
+// Callback executed on the flash swap caller, it must pay the vlp back before returning
+#[cw_serde]
+pub enum FlashSwapCallbackMsg {
+    FlashSwapCallback {
+        vlp_address: String,
+        asset_out: Token,
+        amount_out: Uint128,
+        data: Binary,
+    },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 
@@ -77,6 +136,19 @@ pub enum QueryMsg {
         asset: Token,
         asset_amount: Uint128,
         swaps: Vec<NextSwapVlp>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        asset_out: Option<Token>,
+    },
+    // Price of base in the other token of the pair, from the current reserves
+    #[returns(SpotPriceResponse)]
+    SpotPrice { base: Token },
+    // Amount of asset_in that moves the pool price of asset_in to the target
+    #[returns(LiquidityDepthResponse)]
+    LiquidityDepth {
+        asset_in: Token,
+        target: DepthTarget,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        asset_out: Option<Token>,
     },
     // Queries the total reserve of the pair in the VLP
     #[returns(GetLiquidityResponse)]
@@ -98,6 +170,25 @@ pub enum QueryMsg {
     // Query to get all pools
     #[returns(AllPoolsResponse)]
     GetAllPools {},
+
+    // Euclid fees accumulated since the last distribution
+    #[returns(UndistributedFeesResponse)]
+    UndistributedFees {},
+
+    // Claimable lp fees accrued per hub lp token since the pool started
+    #[returns(FeeGrowthResponse)]
+    FeeGrowth {},
+    // Claimable lp fees earned by a chain's lp tokens. Only hub lp tokens earn claimable fees, so this is
+    // always zero for other chains
+    #[returns(UnclaimedLpFeesResponse)]
+    UnclaimedLpFees { chain_uid: ChainUid },
+
+    // Swapped amounts since the pool started, only the swaps coming from chain_uid when set
+    #[returns(VolumeResponse)]
+    Volume { chain_uid: Option<ChainUid> },
+    // Swapped amounts per day, days are counted from the unix epoch
+    #[returns(DailyVolumeResponse)]
+    DailyVolume { pagination: Pagination<u64> },
 }
 
 // We define a custom struct for each query response
@@ -105,6 +196,62 @@ pub enum QueryMsg {
 pub struct GetSwapResponse {
     pub amount_out: Uint128,
     pub asset_out: Token,
+    // Effective fees charged by the swap, compounded over every hop
+    #[serde(default)]
+    pub lp_fee_bps: u64,
+    #[serde(default)]
+    pub euclid_fee_bps: u64,
+    // Swap of every pool in the route, in order
+    #[serde(default)]
+    pub hops: Vec<SwapHop>,
+    // Amount out per amount in of the whole route, fees included
+    #[serde(default)]
+    pub execution_price: Decimal,
+    #[serde(default)]
+    pub price_impact_bps: u64,
+}
+
+impl GetSwapResponse {
+    // Response of a route from its hops, amount_in is the amount sent to the first pool
+    pub fn new(
+        amount_in: Uint128,
+        amount_out: Uint128,
+        asset_out: Token,
+        hops: Vec<SwapHop>,
+        lp_fee_bps: u64,
+        euclid_fee_bps: u64,
+    ) -> Result<Self, ContractError> {
+        Ok(Self {
+            amount_out,
+            asset_out,
+            lp_fee_bps,
+            euclid_fee_bps,
+            execution_price: get_execution_price(amount_in, amount_out)?,
+            price_impact_bps: get_route_price_impact_bps(&hops)?,
+            hops,
+        })
+    }
+}
+
+// Prices are asset out per asset in, everything is zero while the pool has no liquidity
+#[cw_serde]
+pub struct LiquidityDepthResponse {
+    pub vlp_address: String,
+    pub asset_in: Token,
+    pub asset_out: Token,
+    // Fees included
+    pub amount_in: Uint128,
+    pub amount_out: Uint128,
+    pub spot_price: Decimal,
+    pub target_price: Decimal,
+}
+
+#[cw_serde]
+pub struct SpotPriceResponse {
+    pub base: Token,
+    pub quote: Token,
+    // Amount of quote per base
+    pub price: Decimal,
 }
 
 #[cw_serde]
@@ -117,6 +264,9 @@ pub struct GetStateResponse {
     pub last_updated: u64,
     pub total_lp_tokens: Uint128,
     pub admin: String,
+    pub sunset: bool,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    pub claimable_lp_fees: bool,
 }
 
 #[cw_serde]
@@ -125,11 +275,25 @@ pub struct GetLiquidityResponse {
     pub token_1_reserve: Uint128,
     pub token_2_reserve: Uint128,
     pub total_lp_tokens: Uint128,
+    // Part of total_lp_tokens locked by the first deposit, the pool is drained once only these remain
+    #[serde(default)]
+    pub locked_lp_tokens: Uint128,
 }
 
 #[cw_serde]
 pub struct FeeResponse {
     pub fee: Fee,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    // Lp fee a swap would currently be charged
+    pub effective_lp_fee_bps: u64,
+    // Euclid fee split set on this vlp
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+}
+
+#[cw_serde]
+pub struct UndistributedFeesResponse {
+    pub token_1_fees: Uint128,
+    pub token_2_fees: Uint128,
 }
 
 #[cw_serde]
@@ -160,6 +324,39 @@ pub struct AllPoolsResponse {
     pub pools: Vec<PoolInfo>,
 }
 
+#[cw_serde]
+pub struct FeeGrowthResponse {
+    pub token_1_fee_growth: Decimal256,
+    pub token_2_fee_growth: Decimal256,
+}
+
+#[cw_serde]
+pub struct UnclaimedLpFeesResponse {
+    pub chain_uid: ChainUid,
+    pub token_1_fees: Uint128,
+    pub token_2_fees: Uint128,
+}
+
+// Volumes are amounts swapped into the pool, fees included
+#[cw_serde]
+pub struct VolumeResponse {
+    pub chain_uid: Option<ChainUid>,
+    pub token_1_volume: Uint128,
+    pub token_2_volume: Uint128,
+}
+
+#[cw_serde]
+pub struct DayVolume {
+    pub day: u64,
+    pub token_1_volume: Uint128,
+    pub token_2_volume: Uint128,
+}
+
+#[cw_serde]
+pub struct DailyVolumeResponse {
+    pub days: Vec<DayVolume>,
+}
+
 #[cw_serde]
 pub struct MigrateMsg {}
 
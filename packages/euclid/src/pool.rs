@@ -1,13 +1,30 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{ensure, Binary, Coin, Uint128};
+use sha2::{Digest, Sha256};
 
 use crate::{
     error::ContractError,
+    liquidity::InitialLiquidity,
     token::{Pair, PairWithDenom, Token, TokenWithDenom},
 };
 
 pub const MINIMUM_LIQUIDITY: u128 = 1000;
 
+// Bounds on the number of tokens in a multi asset pool, two token pools use a regular vlp
+pub const MIN_MULTI_ASSET_POOL_TOKENS: usize = 3;
+pub const MAX_MULTI_ASSET_POOL_TOKENS: usize = 8;
+
+// Instantiate2 salt of a pair's vlp. The generation is bumped when the pair's vlp is delisted so that
+// a new vlp of the pair doesn't collide with the address of the old one
+pub fn get_vlp_salt(pair: &Pair, generation: u64) -> Binary {
+    let (token_1, token_2) = pair.get_tupple();
+    let mut seed = format!("{token_1}:{token_2}");
+    if generation > 0 {
+        seed.push_str(&format!(":{generation}"));
+    }
+    Binary::from(Sha256::digest(seed.as_bytes()).to_vec())
+}
+
 #[cw_serde]
 pub struct Pool {
     pub pair: Pair,
@@ -47,6 +64,45 @@ pub struct PoolCreateRequest {
     // Pool Pair
     pub pair_info: PairWithDenom,
     pub lp_token_instantiate_msg: cw20_base::msg::InstantiateMsg,
+    // Deposit held by the factory until the pool creation ack
+    pub initial_liquidity: Option<InitialLiquidity>,
+    // Creation fee paid with the request, sent to the treasury on success and refunded on failure
+    pub creation_fee: Option<PoolCreationFee>,
+}
+
+// Fee charged by a factory for every pool creation request
+#[cw_serde]
+pub struct PoolCreationFee {
+    pub amount: Coin,
+    // Protocol treasury on the factory chain
+    pub treasury: String,
+}
+
+// Who can request pool creation from a chain, enforced by the router
+#[cw_serde]
+#[derive(Default)]
+pub enum PoolCreationMode {
+    // Any user of the chain
+    #[default]
+    Open,
+    // Only the listed addresses of the chain
+    Allowlist {
+        creators: Vec<String>,
+    },
+    // Only the protocol's admin address on that chain, for chains whose pools are listed by the protocol
+    AdminOnly {
+        admin: String,
+    },
+}
+
+impl PoolCreationMode {
+    pub fn is_allowed(&self, creator: &str) -> bool {
+        match self {
+            PoolCreationMode::Open => true,
+            PoolCreationMode::Allowlist { creators } => creators.iter().any(|c| c == creator),
+            PoolCreationMode::AdminOnly { admin } => creator == admin,
+        }
+    }
 }
 
 #[cw_serde]
@@ -63,7 +119,54 @@ pub struct EscrowCreateRequest {
 #[cw_serde]
 pub struct PoolCreationResponse {
     pub vlp_contract: String,
+    // Lp tokens minted to the creator if the pool was created with initial liquidity
+    pub mint_lp_tokens: Option<Uint128>,
 }
 
 #[cw_serde]
 pub struct EscrowCreationResponse {}
+
+#[cw_serde]
+pub struct EscrowRetirementResponse {
+    pub token: Token,
+    // Escrow balance left on the chain, retirement is completed only when this is zero
+    pub remaining_balance: Uint128,
+    pub retired: bool,
+}
+
+// Sorts the tokens of a multi asset pool and validates them
+pub fn sort_pool_tokens(mut tokens: Vec<Token>) -> Result<Vec<Token>, ContractError> {
+    ensure!(
+        (MIN_MULTI_ASSET_POOL_TOKENS..=MAX_MULTI_ASSET_POOL_TOKENS).contains(&tokens.len()),
+        ContractError::new("Multi asset pools need between 3 and 8 tokens")
+    );
+    tokens.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    for token in tokens.iter() {
+        token.validate()?;
+    }
+    ensure!(
+        tokens.windows(2).all(|window| window[0] != window[1]),
+        ContractError::DuplicateTokens {}
+    );
+    Ok(tokens)
+}
+
+// Every pair of tokens a multi asset pool can swap between
+pub fn get_pool_pairs(tokens: &[Token]) -> Result<Vec<Pair>, ContractError> {
+    let mut pairs = vec![];
+    for (i, token_1) in tokens.iter().enumerate() {
+        for token_2 in tokens.iter().skip(i + 1) {
+            pairs.push(Pair::new(token_1.clone(), token_2.clone())?);
+        }
+    }
+    Ok(pairs)
+}
+
+// Unique id of a multi asset pool, the sorted token ids joined together
+pub fn get_pool_id(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.to_string())
+        .collect::<Vec<String>>()
+        .join(":")
+}
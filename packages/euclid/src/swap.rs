@@ -1,8 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, IbcTimeout, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Decimal, Decimal256, IbcTimeout, Isqrt, StdError, Uint128, Uint512,
+};
 
 use crate::{
     chain::CrossChainUserWithLimit,
+    error::ContractError,
     token::{Token, TokenWithDenom},
 };
 
@@ -35,6 +38,10 @@ pub struct SwapRequest {
 pub struct NextSwapVlp {
     pub vlp_address: String,
     pub test_fail: Option<bool>,
+    // Token to receive, only needed by multi asset pools that hold more than two tokens.
+    // Left out when unset so vlps without the field still accept the swap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_out: Option<Token>,
 }
 
 #[cw_serde]
@@ -44,6 +51,219 @@ pub struct NextSwapPair {
     pub test_fail: Option<bool>,
 }
 
+// Swap from the owner's virtual balance that rests until the pool price reaches the limit
+#[cw_serde]
+pub struct LimitOrder {
+    pub owner: Addr,
+    pub asset_in: Token,
+    pub amount_in: Uint128,
+    pub asset_out: Token,
+    // Min amount of asset out received per unit of asset in
+    pub limit_price: Decimal,
+    pub swaps: Vec<NextSwapPair>,
+    pub created_at: u64,
+}
+
+impl LimitOrder {
+    // Amount out the order has to receive to be filled
+    pub fn min_amount_out(&self) -> Result<Uint128, ContractError> {
+        Ok(self.amount_in.checked_mul_ceil(self.limit_price)?)
+    }
+}
+
+// Deposit split into equal swaps that keepers execute once every interval
+#[cw_serde]
+pub struct DcaSchedule {
+    pub owner: Addr,
+    pub asset_in: TokenWithDenom,
+    pub asset_out: Token,
+    pub amount_per_swap: Uint128,
+    pub min_amount_out: Uint128,
+    pub swaps: Vec<NextSwapPair>,
+    pub cross_chain_addresses: Vec<CrossChainUserWithLimit>,
+    pub timeout: Option<u64>,
+    // Seconds between two swaps
+    pub interval: u64,
+    pub total_swaps: u64,
+    pub executed_swaps: u64,
+    // Unix timestamp in seconds after which the next swap can be executed
+    pub next_execution: u64,
+}
+
+impl DcaSchedule {
+    // Deposit that hasn't been swapped yet
+    pub fn remaining_amount(&self) -> Result<Uint128, ContractError> {
+        let remaining_swaps =
+            self.total_swaps
+                .checked_sub(self.executed_swaps)
+                .ok_or(ContractError::new(
+                    "Executed swaps exceed the total swaps of the schedule",
+                ))?;
+        Ok(self
+            .amount_per_swap
+            .checked_mul(Uint128::from(remaining_swaps))?)
+    }
+}
+
+// Swap done by one pool of a simulated route
+#[cw_serde]
+pub struct SwapHop {
+    pub vlp_address: String,
+    pub asset_in: Token,
+    pub amount_in: Uint128,
+    pub asset_out: Token,
+    pub amount_out: Uint128,
+    // Fees taken from amount_in before the swap
+    pub lp_fee: Uint128,
+    pub euclid_fee: Uint128,
+    // Asset out per asset in before the swap
+    pub spot_price: Decimal,
+    // Output lost to the pool curve compared to swapping the amount after fees at the spot price
+    pub price_impact_bps: u64,
+}
+
+impl SwapHop {
+    // Amount actually swapped once the fees are taken
+    pub fn swap_amount(&self) -> Result<Uint128, ContractError> {
+        Ok(self
+            .amount_in
+            .checked_sub(self.lp_fee)?
+            .checked_sub(self.euclid_fee)?)
+    }
+}
+
+// Spot price of a pool, quote per base
+pub fn get_spot_price(
+    base_reserve: Uint128,
+    quote_reserve: Uint128,
+) -> Result<Decimal, ContractError> {
+    ensure!(
+        !(base_reserve.is_zero() || quote_reserve.is_zero()),
+        ContractError::new("Pool has no liquidity")
+    );
+    Decimal::checked_from_ratio(quote_reserve, base_reserve)
+        .map_err(|_err| ContractError::new("Spot price overflow"))
+}
+
+// Asset out received per asset in
+pub fn get_execution_price(
+    amount_in: Uint128,
+    amount_out: Uint128,
+) -> Result<Decimal, ContractError> {
+    Decimal::checked_from_ratio(amount_out, amount_in)
+        .map_err(|_err| ContractError::new("Execution price overflow"))
+}
+
+// Price impact of a route, compounded over every hop. Fees are not counted as price impact
+pub fn get_route_price_impact_bps(hops: &[SwapHop]) -> Result<u64, ContractError> {
+    let mut received_ratio = Decimal256::one();
+    for hop in hops {
+        let spot_amount_out = Decimal256::from_ratio(hop.swap_amount()?, 1u128)
+            .checked_mul(Decimal256::from(hop.spot_price))?;
+        if spot_amount_out.is_zero() {
+            continue;
+        }
+        let hop_ratio = Decimal256::from_ratio(hop.amount_out, 1u128)
+            .checked_div(spot_amount_out)
+            .map_err(|_err| ContractError::new("Price impact overflow"))?;
+        received_ratio = received_ratio.checked_mul(hop_ratio.min(Decimal256::one()))?;
+    }
+    let impact_bps = Decimal256::one()
+        .checked_sub(received_ratio)?
+        .checked_mul(Decimal256::from_ratio(10_000u128, 1u128))?
+        .to_uint_floor();
+    // Impact is at most 10_000 bps
+    Ok(Uint128::try_from(impact_bps)
+        .map_err(StdError::from)?
+        .u128() as u64)
+}
+
+// Price a liquidity depth query moves the pool to, prices are asset out per asset in
+#[cw_serde]
+pub enum DepthTarget {
+    // Drop of the spot price in bps, below 10_000
+    PriceMoveBps(u64),
+    // Target price below the spot price
+    Price(Decimal),
+}
+
+impl DepthTarget {
+    pub fn get_target_price(&self, spot_price: Decimal) -> Result<Decimal, ContractError> {
+        match self {
+            DepthTarget::PriceMoveBps(bps) => {
+                ensure!(
+                    (1..10_000).contains(bps),
+                    ContractError::new("Price move should be between 1 and 9999 bps")
+                );
+                Ok(spot_price.checked_mul(Decimal::one().checked_sub(Decimal::bps(*bps))?)?)
+            }
+            DepthTarget::Price(price) => {
+                ensure!(
+                    !price.is_zero(),
+                    ContractError::new("Target price cannot be zero")
+                );
+                Ok(*price)
+            }
+        }
+    }
+}
+
+// Amount to swap into a constant product pool, after fees, to bring its price down to target_price.
+// compounded_fee is the lp fee that stays in reserve_in per unit swapped. With x = reserve_in + swap
+// amount and m = 1 + compounded_fee, the price after the swap is (k / x) / (m * x - (m - 1) * reserve_in),
+// so the target is the positive root of x^2 - b * x - c with b = (m - 1) * reserve_in / m and
+// c = k / (m * target_price). Without a compounded fee this is sqrt(k / target_price)
+pub fn get_swap_amount_to_price(
+    reserve_in: Uint128,
+    reserve_out: Uint128,
+    target_price: Decimal,
+    compounded_fee: Decimal,
+) -> Result<Uint128, ContractError> {
+    let one = Uint512::from(Decimal::one().atomics());
+    let m = one.checked_add(Uint512::from(compounded_fee.atomics()))?;
+    let k = Uint512::from(reserve_in).checked_mul(Uint512::from(reserve_out))?;
+    let b = Uint512::from(reserve_in)
+        .checked_mul(Uint512::from(compounded_fee.atomics()))?
+        .checked_div(m)?;
+    let c = k
+        .checked_mul(one)?
+        .checked_mul(one)?
+        .checked_div(Uint512::from(target_price.atomics()).checked_mul(m)?)?;
+    let discriminant = b
+        .checked_mul(b)?
+        .checked_add(c.checked_mul(Uint512::from(4u8))?)?;
+    let target_reserve_in = b
+        .checked_add(discriminant.isqrt())?
+        .checked_div(Uint512::from(2u8))?;
+    let swap_amount = target_reserve_in.saturating_sub(Uint512::from(reserve_in));
+    Ok(Uint128::try_from(swap_amount).map_err(StdError::from)?)
+}
+
+// Lp fee left in reserve_in per unit swapped, when compounded_share of the lp fee stays in the pool
+pub fn get_compounded_fee(
+    lp_fee_bps: u64,
+    total_fee_bps: u64,
+    compounded_share: Decimal,
+) -> Result<Decimal, ContractError> {
+    ensure!(
+        total_fee_bps < 10_000,
+        ContractError::new("Fee should be below 10000 bps")
+    );
+    Ok(Decimal::from_ratio(lp_fee_bps, 10_000 - total_fee_bps).checked_mul(compounded_share)?)
+}
+
+// Amount in that leaves swap_amount once fee_bps are taken
+pub fn get_amount_in_before_fees(
+    swap_amount: Uint128,
+    fee_bps: u64,
+) -> Result<Uint128, ContractError> {
+    ensure!(
+        fee_bps < 10_000,
+        ContractError::new("Fee should be below 10000 bps")
+    );
+    Ok(swap_amount.checked_mul_ceil(Decimal::from_ratio(10_000u128, 10_000 - fee_bps))?)
+}
+
 #[cw_serde]
 pub struct SwapResponse {
     pub amount_out: Uint128,
@@ -55,3 +275,35 @@ pub struct WithdrawResponse {
     pub token: Token,
     pub tx_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_amount_to_price_with_compounded_fee() {
+        let (reserve_in, reserve_out) = (Uint128::new(1_000_000), Uint128::new(2_000_000));
+        let target_price = Decimal::from_ratio(198u128, 100u128);
+
+        // Without a compounded fee the reserves stay on the constant product curve
+        let swap_amount =
+            get_swap_amount_to_price(reserve_in, reserve_out, target_price, Decimal::zero())
+                .unwrap();
+        assert_eq!(swap_amount, Uint128::new(5_037));
+
+        // A 1% lp fee out of 2% total fees stays in reserve_in, so less has to be swapped
+        let compounded_fee = get_compounded_fee(100, 200, Decimal::one()).unwrap();
+        let swap_amount =
+            get_swap_amount_to_price(reserve_in, reserve_out, target_price, compounded_fee)
+                .unwrap();
+        assert_eq!(swap_amount, Uint128::new(5_012));
+
+        let amount_in = get_amount_in_before_fees(swap_amount, 200).unwrap();
+        let lp_fee = amount_in.mul_floor(Decimal::bps(100));
+        let new_reserve_in = reserve_in + swap_amount + lp_fee;
+        let new_reserve_out =
+            reserve_out - reserve_out.multiply_ratio(swap_amount, reserve_in + swap_amount);
+        let price = get_spot_price(new_reserve_in, new_reserve_out).unwrap();
+        assert!(price.abs_diff(target_price) < Decimal::bps(1));
+    }
+}
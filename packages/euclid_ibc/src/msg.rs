@@ -8,6 +8,7 @@ use cw_storage_plus::{Item, Map};
 use euclid::{
     chain::{Chain, ChainUid, CrossChainUser, CrossChainUserWithLimit},
     error::ContractError,
+    liquidity::InitialLiquidity,
     msgs::{factory, router},
     swap::NextSwapPair,
     token::{Pair, Token},
@@ -29,6 +30,8 @@ pub enum ChainIbcExecuteMsg {
         sender: CrossChainUser,
         tx_id: String,
         pair: Pair,
+        // Liquidity added to the pool right after it is created, in the order of the pair
+        initial_liquidity: Option<InitialLiquidity>,
     },
     // Request Pool Creation
     RequestEscrowCreation {
@@ -36,6 +39,12 @@ pub enum ChainIbcExecuteMsg {
         tx_id: String,
         token: Token,
     },
+    // Request to retire the escrow of a token, completed once the escrow balance reaches zero
+    RequestEscrowRetirement {
+        sender: CrossChainUser,
+        tx_id: String,
+        token: Token,
+    },
     AddLiquidity {
         // Factory will set this using info.sender
         sender: CrossChainUser,
@@ -53,9 +62,28 @@ pub enum ChainIbcExecuteMsg {
         tx_id: String,
     },
 
+    // One token of a liquidity intent, the router adds liquidity once both tokens arrive
+    AddLiquidityLeg(ChainIbcAddLiquidityLegExecuteMsg),
+
+    // Add liquidity using the sender's virtual balance instead of fresh deposits
+    AddLiquidityFromVirtualBalance(ChainIbcAddLiquidityFromVirtualBalanceExecuteMsg),
+
     // Remove liquidity from a chain pool to VLP
     RemoveLiquidity(ChainIbcRemoveLiquidityExecuteMsg),
 
+    // Request creation of a pool with 3 or more tokens
+    RequestMultiAssetPoolCreation {
+        sender: CrossChainUser,
+        tx_id: String,
+        tokens: Vec<Token>,
+    },
+
+    // Add liquidity to a multi asset pool from the sender's virtual balance
+    AddMultiAssetLiquidity(ChainIbcAddMultiAssetLiquidityExecuteMsg),
+
+    // Remove liquidity from a multi asset pool to the sender's virtual balance
+    RemoveMultiAssetLiquidity(ChainIbcRemoveMultiAssetLiquidityExecuteMsg),
+
     // Swap tokens on VLP
     Swap(ChainIbcSwapExecuteMsg),
 
@@ -89,10 +117,16 @@ impl ChainIbcExecuteMsg {
         match self {
             Self::AddLiquidity { tx_id, .. } => tx_id.clone(),
             Self::RequestPoolCreation { tx_id, .. } => tx_id.clone(),
+            Self::AddLiquidityLeg(msg) => msg.tx_id.clone(),
+            Self::AddLiquidityFromVirtualBalance(msg) => msg.tx_id.clone(),
             Self::RemoveLiquidity(msg) => msg.tx_id.clone(),
             Self::Swap(msg) => msg.tx_id.clone(),
             Self::Withdraw(msg) => msg.tx_id.clone(),
             Self::RequestEscrowCreation { tx_id, .. } => tx_id.clone(),
+            Self::RequestEscrowRetirement { tx_id, .. } => tx_id.clone(),
+            Self::RequestMultiAssetPoolCreation { tx_id, .. } => tx_id.clone(),
+            Self::AddMultiAssetLiquidity(msg) => msg.tx_id.clone(),
+            Self::RemoveMultiAssetLiquidity(msg) => msg.tx_id.clone(),
         }
     }
 
@@ -146,6 +180,71 @@ impl ChainIbcExecuteMsg {
     }
 }
 
+#[cw_serde]
+pub struct ChainIbcAddLiquidityLegExecuteMsg {
+    // Factory will set this using info.sender
+    pub sender: CrossChainUser,
+
+    // Shared by both legs of the intent
+    pub intent_id: String,
+    pub pair: Pair,
+    // Sender of the other leg, a leg from anyone else is rejected
+    pub counterparty: CrossChainUser,
+
+    // Factory will verify this using info funds
+    pub token: Token,
+    pub amount: Uint128,
+
+    pub slippage_tolerance: u64,
+    pub recipient: CrossChainUser,
+    pub deadline: u64,
+
+    // Unique per tx
+    pub tx_id: String,
+}
+
+#[cw_serde]
+pub struct ChainIbcAddLiquidityFromVirtualBalanceExecuteMsg {
+    // Factory will set this using info.sender
+    pub sender: CrossChainUser,
+
+    pub pair: Pair,
+    pub token_1_liquidity: Uint128,
+    pub token_2_liquidity: Uint128,
+    pub slippage_tolerance: u64,
+
+    // Lp tokens are minted to this user on its chain
+    pub recipient: CrossChainUser,
+
+    // Unique per tx
+    pub tx_id: String,
+}
+
+#[cw_serde]
+pub struct ChainIbcAddMultiAssetLiquidityExecuteMsg {
+    // Factory will set this using info.sender
+    pub sender: CrossChainUser,
+
+    pub vlp_address: String,
+    pub liquidity: Vec<(Token, Uint128)>,
+    pub min_lp_allocation: Uint128,
+
+    // Unique per tx
+    pub tx_id: String,
+}
+
+#[cw_serde]
+pub struct ChainIbcRemoveMultiAssetLiquidityExecuteMsg {
+    // Factory will set this using info.sender
+    pub sender: CrossChainUser,
+
+    pub vlp_address: String,
+    pub lp_allocation: Uint128,
+
+    // Unique per tx
+    pub tx_id: String,
+}
+
 #[cw_serde]
 pub struct ChainIbcRemoveLiquidityExecuteMsg {
     // Factory will set this using info.sender
@@ -157,6 +256,11 @@ pub struct ChainIbcRemoveLiquidityExecuteMsg {
     // First element in array has highest priority
     pub cross_chain_addresses: Vec<CrossChainUserWithLimit>,
 
+    // Keep the released tokens as hub virtual balance instead of releasing escrow,
+    // defaulted so requests stored before this field existed still parse
+    #[serde(default)]
+    pub keep_in_virtual_balance: bool,
+
     // Unique per tx
     pub tx_id: String,
 }
@@ -224,6 +328,33 @@ pub enum HubIbcExecuteMsg {
         // Unique per tx
         tx_id: String,
     },
+
+    // Mint lp tokens for liquidity added on behalf of a user of this chain
+    MintLpTokens {
+        chain_uid: ChainUid,
+        recipient: String,
+        vlp_address: String,
+        amount: Uint128,
+        // Unique per tx
+        tx_id: String,
+    },
+
+    // Remove a sunset pool from the factory once all liquidity is withdrawn
+    DelistPool {
+        chain_uid: ChainUid,
+        pair: Pair,
+        vlp_address: String,
+        // Unique per tx
+        tx_id: String,
+    },
+
+    // Remove a retiring escrow from the factory once its hub balance is fully withdrawn
+    RetireEscrow {
+        chain_uid: ChainUid,
+        token: Token,
+        // Unique per tx
+        tx_id: String,
+    },
 }
 
 impl HubIbcExecuteMsg {
@@ -232,6 +363,9 @@ impl HubIbcExecuteMsg {
             Self::RegisterFactory { tx_id, .. } => tx_id.clone(),
             Self::ReleaseEscrow { tx_id, .. } => tx_id.clone(),
             Self::UpdateFactoryChannel { tx_id, .. } => tx_id.clone(),
+            Self::DelistPool { tx_id, .. } => tx_id.clone(),
+            Self::MintLpTokens { tx_id, .. } => tx_id.clone(),
+            Self::RetireEscrow { tx_id, .. } => tx_id.clone(),
         }
     }
 
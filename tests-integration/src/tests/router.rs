@@ -1,12 +1,22 @@
 #![cfg(not(target_arch = "wasm32"))]
 
-use cosmwasm_std::{coin, Addr};
-use euclid::msgs::router::StateResponse;
+use cosmwasm_std::{coin, to_json_binary, Addr, CosmosMsg, Uint128, WasmMsg};
+use cw_multi_test::Executor;
+use euclid::chain::{ChainUid, CrossChainUser};
+use euclid::fee::Fee;
+use euclid::msgs::router::{ExecuteMsg, PredictVlpAddressResponse, QueryMsg, StateResponse};
+use euclid::msgs::virtual_balance::{ExecuteMint, GetBalanceResponse};
+use euclid::pool::get_vlp_salt;
+use euclid::swap::NextSwapPair;
+use euclid::token::{Pair, Token};
+use euclid::virtual_balance::BalanceKey;
+use mock::mock::MockApp;
 use mock::{mock::mock_app, mock_builder::MockEuclidBuilder};
 
 use router::mock::{mock_router, MockRouter};
+use router::state::{VlpInfo, VLPS};
 use virtual_balance::mock::mock_virtual_balance;
-use vlp::mock::mock_vlp;
+use vlp::mock::{mock_vlp, MockVlp};
 
 const _USER: &str = "user";
 const _NATIVE_DENOM: &str = "native";
@@ -56,3 +66,255 @@ fn test_proper_instantiation() {
     };
     assert_eq!(state, expected_state_response);
 }
+
+#[test]
+fn test_predict_vlp_address() {
+    let mut router = mock_app(None);
+    let andr = MockEuclidBuilder::new(&mut router, "admin")
+        .with_wallets(vec![("owner", vec![coin(1000, "eucl")])])
+        .with_contracts(vec![
+            ("router", mock_router()),
+            ("vlp", mock_vlp()),
+            ("virtual_balance", mock_virtual_balance()),
+        ])
+        .build(&mut router);
+    let owner = andr.get_wallet("owner");
+
+    let router_code_id = 1;
+    let vlp_code_id = 2;
+    let virtual_balance_code_id = 3;
+
+    let mock_router = MockRouter::instantiate(
+        &mut router,
+        router_code_id,
+        owner.clone(),
+        vlp_code_id,
+        virtual_balance_code_id,
+    );
+
+    let pair = Pair::new(
+        Token::create("eth".to_string()).unwrap(),
+        Token::create("usdc".to_string()).unwrap(),
+    )
+    .unwrap();
+    let prediction: PredictVlpAddressResponse = router
+        .wrap()
+        .query_wasm_smart(
+            mock_router.addr(),
+            &QueryMsg::PredictVlpAddress { pair: pair.clone() },
+        )
+        .unwrap();
+    assert!(!prediction.registered);
+
+    // The router instantiates vlps with the pair salt, so doing the same lands on the predicted address
+    let instantiate_msg = euclid::msgs::vlp::InstantiateMsg {
+        router: mock_router.addr().to_string(),
+        virtual_balance: "virtual_balance".to_string(),
+        pair: pair.clone(),
+        fee: Fee {
+            lp_fee_bps: 10,
+            euclid_fee_bps: 10,
+            recipient: CrossChainUser {
+                chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                address: owner.to_string(),
+            },
+        },
+        execute: None,
+        admin: owner.to_string(),
+    };
+    let res = router
+        .execute(
+            mock_router.addr().clone(),
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin: None,
+                code_id: vlp_code_id,
+                label: "VLP".to_string(),
+                msg: to_json_binary(&instantiate_msg).unwrap(),
+                funds: vec![],
+                salt: get_vlp_salt(&pair, 0),
+            }),
+        )
+        .unwrap();
+    let vlp_address = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "_contract_address")
+        .map(|attr| attr.value.clone())
+        .unwrap();
+    assert_eq!(prediction.vlp_address, Addr::unchecked(vlp_address));
+}
+
+fn vsl_balance(app: &MockApp, virtual_balance: &Addr, address: &Addr, token: &Token) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart::<GetBalanceResponse>(
+            virtual_balance,
+            &euclid::msgs::virtual_balance::QueryMsg::GetBalance {
+                balance_key: BalanceKey {
+                    cross_chain_user: CrossChainUser {
+                        chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                        address: address.to_string(),
+                    },
+                    token_id: token.to_string(),
+                },
+            },
+        )
+        .unwrap()
+        .amount
+}
+
+fn mint_vsl_balance(
+    app: &mut MockApp,
+    router: &Addr,
+    virtual_balance: &Addr,
+    address: &Addr,
+    token: &Token,
+    amount: u128,
+) {
+    app.execute_contract(
+        router.clone(),
+        virtual_balance.clone(),
+        &euclid::msgs::virtual_balance::ExecuteMsg::Mint(ExecuteMint {
+            amount: Uint128::new(amount),
+            balance_key: BalanceKey {
+                cross_chain_user: CrossChainUser {
+                    chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                    address: address.to_string(),
+                },
+                token_id: token.to_string(),
+            },
+        }),
+        &[],
+    )
+    .unwrap();
+}
+
+// Router with a registered eth/usdc vlp holding 10000 of each token and a user holding 1000 eth
+fn setup_vsl_swap() -> (MockApp, Addr, Addr, Addr, Pair) {
+    let mut app = mock_app(None);
+    let andr = MockEuclidBuilder::new(&mut app, "admin")
+        .with_wallets(vec![("owner", vec![]), ("user", vec![])])
+        .with_contracts(vec![
+            ("router", mock_router()),
+            ("vlp", mock_vlp()),
+            ("virtual_balance", mock_virtual_balance()),
+        ])
+        .build(&mut app);
+    let owner = andr.get_wallet("owner").clone();
+    let user = andr.get_wallet("user").clone();
+
+    let mock_router = MockRouter::instantiate(&mut app, 1, owner.clone(), 2, 3);
+    let router = mock_router.addr().clone();
+    let virtual_balance = mock_router
+        .query_state(&app)
+        .virtual_balance_address
+        .unwrap();
+
+    let pair = Pair::new(
+        Token::create("eth".to_string()).unwrap(),
+        Token::create("usdc".to_string()).unwrap(),
+    )
+    .unwrap();
+    let vlp = MockVlp::instantiate(
+        &mut app,
+        2,
+        router.clone(),
+        router.to_string(),
+        virtual_balance.to_string(),
+        pair.clone(),
+        Fee {
+            lp_fee_bps: 10,
+            euclid_fee_bps: 10,
+            recipient: CrossChainUser {
+                chain_uid: ChainUid::vsl_chain_uid().unwrap(),
+                address: owner.to_string(),
+            },
+        },
+        None,
+        owner.to_string(),
+    );
+    let vlp = vlp.addr().clone();
+
+    VLPS.save(
+        app.contract_storage_mut(&router).as_mut(),
+        pair.get_tupple(),
+        &VlpInfo {
+            vlp: vlp.to_string(),
+            pair: pair.clone(),
+            metadata: None,
+        },
+    )
+    .unwrap();
+    for token in [&pair.token_1, &pair.token_2] {
+        vlp::state::BALANCES
+            .save(
+                app.contract_storage_mut(&vlp).as_mut(),
+                token.clone(),
+                &Uint128::new(10000),
+            )
+            .unwrap();
+        mint_vsl_balance(&mut app, &router, &virtual_balance, &vlp, token, 10000);
+    }
+    mint_vsl_balance(
+        &mut app,
+        &router,
+        &virtual_balance,
+        &user,
+        &pair.token_1,
+        1000,
+    );
+
+    (app, router, virtual_balance, user, pair)
+}
+
+fn vsl_swap_msg(pair: &Pair, min_amount_out: u128) -> ExecuteMsg {
+    ExecuteMsg::VslSwap {
+        asset_in: pair.token_1.clone(),
+        amount_in: Uint128::new(1000),
+        asset_out: pair.token_2.clone(),
+        min_amount_out: Uint128::new(min_amount_out),
+        swaps: vec![NextSwapPair {
+            token_in: pair.token_1.clone(),
+            token_out: pair.token_2.clone(),
+            test_fail: None,
+        }],
+    }
+}
+
+#[test]
+fn test_vsl_swap() {
+    let (mut app, router, virtual_balance, user, pair) = setup_vsl_swap();
+
+    app.execute_contract(user.clone(), router, &vsl_swap_msg(&pair, 900), &[])
+        .unwrap();
+
+    // 1000 eth less 0.2% fees swapped against 10000/10000 reserves, credited back to the caller
+    assert_eq!(
+        vsl_balance(&app, &virtual_balance, &user, &pair.token_1),
+        Uint128::zero()
+    );
+    assert_eq!(
+        vsl_balance(&app, &virtual_balance, &user, &pair.token_2),
+        Uint128::new(908)
+    );
+}
+
+#[test]
+fn test_vsl_swap_slippage_exceeded() {
+    let (mut app, router, virtual_balance, user, pair) = setup_vsl_swap();
+
+    let err = app
+        .execute_contract(user.clone(), router, &vsl_swap_msg(&pair, 909), &[])
+        .unwrap_err();
+    assert!(err.root_cause().to_string().contains("Slippage"));
+
+    // The failed swap reverts the transfer out of the caller's virtual balance
+    assert_eq!(
+        vsl_balance(&app, &virtual_balance, &user, &pair.token_1),
+        Uint128::new(1000)
+    );
+    assert_eq!(
+        vsl_balance(&app, &virtual_balance, &user, &pair.token_2),
+        Uint128::zero()
+    );
+}
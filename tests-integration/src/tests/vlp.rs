@@ -107,6 +107,9 @@ fn test_proper_instantiation() {
         last_updated: 0,
         total_lp_tokens: Uint128::zero(),
         admin: "admin".to_string(),
+        sunset: false,
+        dynamic_fee: None,
+        claimable_lp_fees: false,
     };
     assert_eq!(token_id_response, expected_token_id);
 }